@@ -10,7 +10,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let white = Color::new(1.0, 1.0, 1.0);
 
     for i in 0..=11 {
-        let transform = Matrix::new(4, 4)
+        let transform = Matrix::identity(4)
             .scale(35.0, 35.0, 1.0)
             .rotate_z(i as f64 * std::f64::consts::PI / 6.0);
         let point = transform * Tuple::point(0.0, 1.0, 0.0);
@@ -1,8 +1,8 @@
-//! Example of a purple shaded sphere on a black canvas with a single point 
+//! Example of a purple shaded sphere on a black canvas with a single point
 //! light
 
 use libray::graphics::{Canvas, Color, Light};
-use libray::math::{Intersection, Ray, Sphere, Tuple, normal_at};
+use libray::math::{Intersection, Ray, Sphere, Tuple};
 
 use std::fs;
 
@@ -10,7 +10,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let n_pixels = 400;
     let mut canvas = Canvas::new(n_pixels, n_pixels);
     let mut s = Sphere::new();
-    s.material.color = Color::new(1.0, 0.2, 0.7); 
+    s.material.color = Color::new(1.0, 0.2, 0.7);
     let pixel_size = 7.0 / n_pixels as f64;
     let half = 7.0 / 2.0;
     let light = Light::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
@@ -28,16 +28,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
             let intersections = ray.intersect(&s);
 
-            match Intersection::hit(&intersections) {
-                Some(hit) => {
-                    let eye = -ray.direction;
-                    let point = ray.position(hit.t);
-                    let normal = normal_at(hit.sphere.clone(), point);
-                    let color = s.material.lighting(light, point, eye, normal);
+            if let Some(hit) = Intersection::hit(&intersections) {
+                let eye = -ray.direction;
+                let point = ray.position(hit.t);
+                let normal = hit.object.normal_at(point);
+                let color = s.material.lighting(&s, &light, point, eye, normal, false);
 
-                    canvas.write_pixel(x, y, color)
-                },
-                None => (),
+                canvas.write_pixel(x, y, color)
             }
         }
     }
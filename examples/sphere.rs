@@ -26,9 +26,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
             let intersections = ray.intersect(&s);
 
-            match Intersection::hit(&intersections) {
-                Some(_) => canvas.write_pixel(x, y, red),
-                None => (),
+            if Intersection::hit(&intersections).is_some() {
+                canvas.write_pixel(x, y, red);
             }
         }
     }
@@ -1,59 +1,47 @@
-//! Example of vector ballistics
+//! Example of vector ballistics, bouncing off a floor
 
-use libray::{graphics, math::Tuple};
+use libray::graphics::{Canvas, Color, Plot};
+use libray::math::{Plane, Point2, Tuple};
+use libray::scene::World;
+use libray::{Environment, Projectile};
 use std::fs;
 
-/// Projectile struct
-#[derive(Debug, Clone, Copy)]
-pub struct Projectile {
-    /// Tuple representing projectile position
-    pub position: Tuple,
-    /// Tuple representing projectile velocity
-    pub velocity: Tuple,
-}
-
-/// Environment struct
-#[derive(Debug, Clone, Copy)]
-pub struct Environment {
-    /// Tuple representing environment gravity
-    pub gravity: Tuple,
-    /// Tuple representing environment gravity wind
-    pub wind: Tuple,
-}
-
-impl Environment {
-    /// Tick environment one step
-    ///
-    /// # Arguments:
-    ///
-    /// * `projectile` - Projectile to be affected by environment properties
-    pub fn tick(&self, projectile: Projectile) -> Projectile {
-        let position = projectile.position + projectile.velocity;
-        let velocity = projectile.velocity + self.gravity + self.wind;
-
-        Projectile { position, velocity }
-    }
-}
-
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut p = Projectile {
-        position: Tuple::point(0.0, 1.0, 0.0),
-        velocity: Tuple::vector(1.0, 1.8, 0.0).normalize() * 11.25,
-    };
-    let e = Environment {
-        gravity: Tuple::vector(0.0, -0.1, 0.0),
-        wind: Tuple::vector(-0.01, 0.0, 0.0),
-    };
-    let mut c = graphics::Canvas::new(900, 550);
-    let red = graphics::Color::new(1.0, 0.0, 0.0);
-
-    while p.position.y > 0.0 {
-        p = e.tick(p);
-        c.write_pixel(
-            ((p.position.x).ceil() as usize) - 1,
-            (550 - ((p.position.y).ceil() as usize)) - 1,
-            red,
-        );
+    let mut world = World::new();
+    world.objects.push(Box::new(Plane::new()));
+
+    let p = Projectile::new(
+        Tuple::point(0.0, 1.0, 0.0),
+        Tuple::vector(1.0, 1.8, 0.0).normalize() * 7.5,
+    );
+    let mut e = Environment::new(
+        Tuple::vector(0.0, -0.1, 0.0),
+        Tuple::vector(-0.01, 0.0, 0.0),
+    );
+    e.restitution = 0.6;
+    let mut c = Canvas::new(900, 300);
+    let red = Color::new(1.0, 0.0, 0.0);
+    let gray = Color::new(0.5, 0.5, 0.5);
+    let mut plot = Plot::new();
+    plot.origin = Point2::new(0.0, 20.0);
+
+    // the floor, as seen from the side
+    let width = c.width as f64;
+    plot.polyline(
+        &mut c,
+        [Point2::new(0.0, 0.0), Point2::new(width, 0.0)],
+        gray,
+    );
+
+    // stop once the wind has pushed the projectile to a halt
+    for p in e
+        .trajectory_in(p, &world)
+        .skip(1)
+        .take_while(|p| p.velocity.x > 0.0)
+    {
+        if !plot.point(&mut c, Point2::new(p.position.x, p.position.y), red) {
+            break;
+        }
     }
 
     fs::write("ballistics.ppm", c.to_ppm())?;
@@ -0,0 +1,62 @@
+//! Example of a small scene rendered through a camera
+
+use libray::graphics::{Color, Light};
+use libray::math::{scaling, translation, view_transform, Sphere, Tuple};
+use libray::{Camera, RenderSettings, World};
+
+use std::f64::consts::PI;
+use std::fs;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut floor = Sphere::new();
+    floor.transform(scaling(10.0, 0.01, 10.0));
+    floor.material.color = Color::new(1.0, 0.9, 0.9);
+    floor.material.specular = 0.0;
+
+    let mut middle = Sphere::new();
+    middle.transform(translation(-0.5, 1.0, 0.5));
+    middle.material.color = Color::new(0.1, 1.0, 0.5);
+    middle.material.diffuse = 0.7;
+    middle.material.specular = 0.3;
+
+    let mut right = Sphere::new();
+    right.transform(translation(1.5, 0.5, -0.5) * scaling(0.5, 0.5, 0.5));
+    right.material.color = Color::new(0.5, 1.0, 0.1);
+    right.material.diffuse = 0.7;
+    right.material.specular = 0.3;
+
+    let mut left = Sphere::new();
+    left.transform(translation(-1.5, 0.33, -0.75) * scaling(0.33, 0.33, 0.33));
+    left.material.color = Color::new(1.0, 0.8, 0.1);
+    left.material.diffuse = 0.7;
+    left.material.specular = 0.3;
+
+    let mut world = World::new();
+    world.objects = vec![
+        Box::new(floor),
+        Box::new(middle),
+        Box::new(right),
+        Box::new(left),
+    ];
+    world.lights.push(Light::new(
+        Tuple::point(-10.0, 10.0, -10.0),
+        Color::new(1.0, 1.0, 1.0),
+    ));
+
+    let mut camera = Camera::new(200, 100, PI / 3.0);
+    camera.transform(view_transform(
+        Tuple::point(0.0, 1.5, -5.0),
+        Tuple::point(0.0, 1.0, 0.0),
+        Tuple::vector(0.0, 1.0, 0.0),
+    ));
+
+    let settings = RenderSettings {
+        samples: 2,
+        threads: 0,
+        ..Default::default()
+    };
+
+    fs::write("world.ppm", camera.render(&world, &settings).to_ppm())?;
+
+    Ok(())
+}
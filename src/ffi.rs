@@ -0,0 +1,579 @@
+//! Foreign function interface
+//!
+//! Flat C API behind the `ffi` feature, for embedding the ray tracer in C,
+//! C++ or Python through ctypes. Build a shared library with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! Worlds are passed around as opaque handles created by
+//! `libray_world_new` and released by `libray_world_free`. Functions which
+//! can fail return a negative value and store a message, retrieved with
+//! `libray_last_error`, for the calling thread. A panic inside the library
+//! is reported the same way rather than aborting the process, though the
+//! world it happened in may be left partially modified.
+//!
+//! ```c
+//! LibrayWorld *world = libray_world_new();
+//! int64_t id = libray_world_add_sphere(world, 0.0, 0.0, 0.0, 1.0);
+//! libray_world_set_material(world, id, 1.0, 0.2, 0.2, 0.1, 0.9, 0.9, 200.0);
+//! libray_world_add_light(world, -10.0, 10.0, -10.0, 1.0, 1.0, 1.0);
+//!
+//! uint8_t pixels[100 * 100 * 3];
+//! double from[3] = {0.0, 1.5, -5.0}, to[3] = {0.0, 0.0, 0.0};
+//! if (libray_render(world, 100, 100, 1.0, from, to, pixels, sizeof pixels) < 0) {
+//!     fprintf(stderr, "%s\n", libray_last_error());
+//! }
+//! libray_world_free(world);
+//! ```
+
+#![allow(unsafe_code)]
+
+use crate::graphics::{Color, Light};
+use crate::math::{scaling, translation, view_transform, Cylinder, Sphere, Tuple};
+use crate::scene::{Camera, RenderSettings, World};
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// Opaque handle to a world, named `LibrayWorld` in C headers
+pub type LibrayWorld = World;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Stores the message returned by `libray_last_error` and returns `-1`
+fn fail(message: String) -> i32 {
+    let message = CString::new(message).unwrap_or_default();
+    // must not panic, even while the thread is exiting, as `guard` calls it
+    // outside of `catch_unwind`
+    let _ = LAST_ERROR.try_with(|e| *e.borrow_mut() = message);
+
+    -1
+}
+
+/// Runs the body of an entry point, reporting a panic as an error through
+/// `libray_last_error` instead of letting it unwind into foreign code, which
+/// aborts the process
+///
+/// # Arguments
+///
+/// * `failed` - value returned when the body panics
+/// * `body` - body of the entry point
+fn guard<T>(failed: T, body: impl FnOnce() -> T) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "unknown panic".to_string(),
+                },
+            };
+            fail(format!("internal error: {}", message));
+
+            failed
+        }
+    }
+}
+
+/// Returns the world behind the handle, or an error if it is null
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread while the reference lives.
+unsafe fn world_mut<'a>(world: *mut LibrayWorld) -> Result<&'a mut World, String> {
+    world
+        .as_mut()
+        .ok_or_else(|| "world handle is null".to_string())
+}
+
+/// Returns the message describing the last error on the calling thread,
+/// valid until the next failing call on that thread, or an empty string if
+/// nothing failed yet
+#[no_mangle]
+pub extern "C" fn libray_last_error() -> *const c_char {
+    LAST_ERROR
+        .try_with(|e| e.borrow().as_ptr())
+        .unwrap_or(c"".as_ptr())
+}
+
+/// Creates a new, empty world and returns its handle, to be released with
+/// `libray_world_free`, or null on error
+#[no_mangle]
+pub extern "C" fn libray_world_new() -> *mut LibrayWorld {
+    guard(ptr::null_mut(), || Box::into_raw(Box::new(World::new())))
+}
+
+/// Releases a world created by `libray_world_new`, doing nothing when given
+/// null
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new` and not freed
+/// before.
+#[no_mangle]
+pub unsafe extern "C" fn libray_world_free(world: *mut LibrayWorld) {
+    guard((), || {
+        if !world.is_null() {
+            drop(Box::from_raw(world));
+        }
+    })
+}
+
+/// Adds a sphere with the default material and returns its index, or `-1`
+/// on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `x` - x coordinate of the center
+/// * `y` - y coordinate of the center
+/// * `z` - z coordinate of the center
+/// * `radius` - radius of the sphere, greater than zero
+#[no_mangle]
+pub unsafe extern "C" fn libray_world_add_sphere(
+    world: *mut LibrayWorld,
+    x: f64,
+    y: f64,
+    z: f64,
+    radius: f64,
+) -> i64 {
+    guard(-1, || {
+        let world = match world_mut(world) {
+            Ok(world) => world,
+            Err(e) => return fail(e).into(),
+        };
+
+        if radius.is_nan() || radius <= 0.0 {
+            return fail(format!("invalid radius {}", radius)).into();
+        }
+
+        let mut sphere = Sphere::new();
+        sphere.transform(translation(x, y, z) * scaling(radius, radius, radius));
+        world.objects.push(Box::new(sphere));
+
+        world.objects.len() as i64 - 1
+    })
+}
+
+/// Adds an upright cylinder with the default material and returns its
+/// index, or `-1` on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `x` - x coordinate of the axis
+/// * `z` - z coordinate of the axis
+/// * `radius` - radius of the cylinder, greater than zero
+/// * `bottom` - height of the lower end
+/// * `top` - height of the upper end, greater than `bottom`
+/// * `closed` - nonzero to close both ends with caps
+#[no_mangle]
+pub unsafe extern "C" fn libray_world_add_cylinder(
+    world: *mut LibrayWorld,
+    x: f64,
+    z: f64,
+    radius: f64,
+    bottom: f64,
+    top: f64,
+    closed: i32,
+) -> i64 {
+    guard(-1, || {
+        let world = match world_mut(world) {
+            Ok(world) => world,
+            Err(e) => return fail(e).into(),
+        };
+
+        if radius.is_nan() || radius <= 0.0 {
+            return fail(format!("invalid radius {}", radius)).into();
+        }
+        if bottom.is_nan() || top.is_nan() || bottom >= top {
+            return fail(format!("invalid height range {} to {}", bottom, top)).into();
+        }
+
+        let mut cylinder = Cylinder::new();
+        cylinder.transform(translation(x, 0.0, z) * scaling(radius, 1.0, radius));
+        cylinder.minimum = bottom;
+        cylinder.maximum = top;
+        cylinder.closed = closed != 0;
+        world.objects.push(Box::new(cylinder));
+
+        world.objects.len() as i64 - 1
+    })
+}
+
+/// Sets the color and shading coefficients of an object, returning `0` on
+/// success or `-1` on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `id` - index returned by `libray_world_add_sphere` or
+///   `libray_world_add_cylinder`
+/// * `r` - red component of the surface color
+/// * `g` - green component of the surface color
+/// * `b` - blue component of the surface color
+/// * `ambient` - ambient reflection
+/// * `diffuse` - diffuse reflection
+/// * `specular` - specular reflection
+/// * `shininess` - tightness of the specular highlight
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn libray_world_set_material(
+    world: *mut LibrayWorld,
+    id: i64,
+    r: f64,
+    g: f64,
+    b: f64,
+    ambient: f64,
+    diffuse: f64,
+    specular: f64,
+    shininess: f64,
+) -> i32 {
+    guard(-1, || {
+        let world = match world_mut(world) {
+            Ok(world) => world,
+            Err(e) => return fail(e),
+        };
+        let count = world.objects.len();
+        let Some(object) = usize::try_from(id)
+            .ok()
+            .and_then(|i| world.objects.get_mut(i))
+        else {
+            return fail(format!("no object {} in a world of {}", id, count));
+        };
+
+        let material = object.material_mut();
+        material.color = Color::new(r, g, b);
+        material.ambient = ambient;
+        material.diffuse = diffuse;
+        material.specular = specular;
+        material.shininess = shininess;
+
+        0
+    })
+}
+
+/// Adds a point light, returning `0` on success or `-1` on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `x` - x coordinate of the light
+/// * `y` - y coordinate of the light
+/// * `z` - z coordinate of the light
+/// * `r` - red intensity
+/// * `g` - green intensity
+/// * `b` - blue intensity
+#[no_mangle]
+pub unsafe extern "C" fn libray_world_add_light(
+    world: *mut LibrayWorld,
+    x: f64,
+    y: f64,
+    z: f64,
+    r: f64,
+    g: f64,
+    b: f64,
+) -> i32 {
+    guard(-1, || match world_mut(world) {
+        Ok(world) => {
+            world
+                .lights
+                .push(Light::new(Tuple::point(x, y, z), Color::new(r, g, b)));
+            0
+        }
+        Err(e) => fail(e),
+    })
+}
+
+/// Sets the ambient light reaching every surface, returning `0` on success
+/// or `-1` on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `r` - red intensity
+/// * `g` - green intensity
+/// * `b` - blue intensity
+#[no_mangle]
+pub unsafe extern "C" fn libray_world_set_ambient(
+    world: *mut LibrayWorld,
+    r: f64,
+    g: f64,
+    b: f64,
+) -> i32 {
+    guard(-1, || match world_mut(world) {
+        Ok(world) => {
+            world.ambient = Color::new(r, g, b);
+            0
+        }
+        Err(e) => fail(e),
+    })
+}
+
+/// Renders the world into a buffer of 8-bit red, green and blue values,
+/// row by row from the top left corner, returning `0` on success or `-1`
+/// on error
+///
+/// # Safety
+///
+/// The handle must be null or returned by `libray_world_new`, not freed
+/// and not used from another thread during the call. `from` and `to` must
+/// point to three doubles each and `buffer` to at least `len` writable
+/// bytes.
+///
+/// # Arguments
+///
+/// * `world` - world handle
+/// * `width` - width of the image in pixels
+/// * `height` - height of the image in pixels
+/// * `field_of_view` - field of view in radians
+/// * `from` - position of the camera
+/// * `to` - point the camera looks at
+/// * `buffer` - buffer receiving `width * height * 3` bytes
+/// * `len` - size of the buffer in bytes
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn libray_render(
+    world: *mut LibrayWorld,
+    width: usize,
+    height: usize,
+    field_of_view: f64,
+    from: *const f64,
+    to: *const f64,
+    buffer: *mut u8,
+    len: usize,
+) -> i32 {
+    guard(-1, || {
+        let world = match world_mut(world) {
+            Ok(world) => world,
+            Err(e) => return fail(e),
+        };
+
+        if from.is_null() || to.is_null() || buffer.is_null() {
+            return fail("camera position, target or buffer is null".to_string());
+        }
+        if width == 0 || height == 0 {
+            return fail(format!("invalid image size {}x{}", width, height));
+        }
+
+        let Some(required) = width.checked_mul(height).and_then(|n| n.checked_mul(3)) else {
+            return fail(format!("image size {}x{} is too large", width, height));
+        };
+
+        if len < required {
+            return fail(format!(
+                "buffer of {} bytes cannot hold a {}x{} image",
+                len, width, height
+            ));
+        }
+
+        let from = slice::from_raw_parts(from, 3);
+        let to = slice::from_raw_parts(to, 3);
+        let mut camera = Camera::new(width, height, field_of_view);
+        camera.transform(view_transform(
+            Tuple::point(from[0], from[1], from[2]),
+            Tuple::point(to[0], to[1], to[2]),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let pixels = camera.render(world, &RenderSettings::default()).to_rgb8();
+
+        ptr::copy_nonoverlapping(pixels.as_ptr(), buffer, required);
+
+        0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn last_error() -> String {
+        // SAFETY: the pointer refers to the thread local error message
+        unsafe { CStr::from_ptr(libray_last_error()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn should_build_and_render_world() {
+        let world = libray_world_new();
+        // SAFETY: the handle is live and only used from this thread
+        let id = unsafe { libray_world_add_sphere(world, 0.0, 0.0, 0.0, 1.0) };
+
+        assert_eq!(0, id);
+        // SAFETY: as above
+        unsafe {
+            assert_eq!(
+                0,
+                libray_world_set_material(world, id, 1.0, 0.0, 0.0, 0.1, 0.9, 0.9, 200.0)
+            );
+            assert_eq!(
+                0,
+                libray_world_add_light(world, -10.0, 10.0, -10.0, 1.0, 1.0, 1.0)
+            );
+            assert_eq!(0, libray_world_set_ambient(world, 0.1, 0.1, 0.1));
+        }
+
+        let mut pixels = vec![0u8; 11 * 11 * 3];
+        let (from, to) = ([0.0, 0.0, -5.0], [0.0, 0.0, 0.0]);
+        // SAFETY: the handle is live and every pointer covers its length
+        let status = unsafe {
+            libray_render(
+                world,
+                11,
+                11,
+                1.0,
+                from.as_ptr(),
+                to.as_ptr(),
+                pixels.as_mut_ptr(),
+                pixels.len(),
+            )
+        };
+        let center = (5 * 11 + 5) * 3;
+
+        assert_eq!(0, status);
+        assert!(pixels[center] > 0);
+        assert!(pixels[center] > pixels[center + 1]);
+        assert_eq!(0, pixels[0]);
+
+        // SAFETY: the handle was created above and is not used afterwards
+        unsafe { libray_world_free(world) };
+    }
+
+    #[test]
+    fn should_add_cylinders() {
+        let world = libray_world_new();
+        // SAFETY: the handle is live and only used from this thread
+        let id = unsafe { libray_world_add_cylinder(world, 1.0, 0.0, 0.5, -1.0, 1.0, 1) };
+
+        assert_eq!(0, id);
+        // SAFETY: as above
+        assert_eq!(0, unsafe {
+            libray_world_set_material(world, id, 1.0, 0.0, 0.0, 0.1, 0.9, 0.9, 200.0)
+        });
+
+        // SAFETY: as above, and no other reference to the world is live
+        let object = unsafe { &*world }.objects[0].clone();
+        let cylinder = object.downcast_ref::<Cylinder>().unwrap();
+
+        assert_eq!(
+            translation(1.0, 0.0, 0.0) * scaling(0.5, 1.0, 0.5),
+            cylinder.transform
+        );
+        assert_eq!(
+            (-1.0, 1.0, true),
+            (cylinder.minimum, cylinder.maximum, cylinder.closed)
+        );
+        assert_eq!(Color::new(1.0, 0.0, 0.0), cylinder.material.color);
+
+        // SAFETY: the handle was created above and is not used afterwards
+        unsafe { libray_world_free(world) };
+    }
+
+    #[test]
+    fn should_report_panics_as_errors() {
+        assert_eq!(-1, guard(-1, || panic!("invalid state {}", 3)));
+        assert_eq!("internal error: invalid state 3", last_error());
+        assert_eq!(7, guard(-1, || 7));
+    }
+
+    #[test]
+    fn should_report_errors() {
+        let world = libray_world_new();
+
+        // SAFETY: the handle is live or null and only used from this thread
+        unsafe {
+            assert_eq!(-1, libray_world_add_sphere(world, 0.0, 0.0, 0.0, -1.0));
+            assert_eq!("invalid radius -1", last_error());
+            assert_eq!(
+                -1,
+                libray_world_add_cylinder(world, 0.0, 0.0, 1.0, 2.0, 1.0, 1)
+            );
+            assert_eq!("invalid height range 2 to 1", last_error());
+            assert_eq!(
+                -1,
+                libray_world_set_material(world, 3, 1.0, 1.0, 1.0, 0.1, 0.9, 0.9, 200.0)
+            );
+            assert_eq!("no object 3 in a world of 0", last_error());
+            assert_eq!(
+                -1,
+                libray_world_add_light(ptr::null_mut(), 0.0, 0.0, 0.0, 1.0, 1.0, 1.0)
+            );
+            assert_eq!("world handle is null", last_error());
+            assert_eq!(-1, libray_world_set_ambient(ptr::null_mut(), 0.1, 0.1, 0.1));
+        }
+
+        let mut pixels = [0u8; 3];
+        let origin = [0.0; 3];
+        // SAFETY: every pointer covers the length passed alongside it
+        let status = unsafe {
+            libray_render(
+                world,
+                2,
+                2,
+                1.0,
+                origin.as_ptr(),
+                origin.as_ptr(),
+                pixels.as_mut_ptr(),
+                pixels.len(),
+            )
+        };
+
+        assert_eq!(-1, status);
+        assert_eq!("buffer of 3 bytes cannot hold a 2x2 image", last_error());
+
+        // SAFETY: as above, the size is rejected before anything is written
+        let status = unsafe {
+            libray_render(
+                world,
+                usize::MAX,
+                2,
+                1.0,
+                origin.as_ptr(),
+                origin.as_ptr(),
+                pixels.as_mut_ptr(),
+                pixels.len(),
+            )
+        };
+
+        assert_eq!(-1, status);
+        assert_eq!(
+            format!("image size {}x2 is too large", usize::MAX),
+            last_error()
+        );
+
+        // SAFETY: the handle was created above and is not used afterwards
+        unsafe { libray_world_free(world) };
+    }
+}
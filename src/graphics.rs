@@ -2,12 +2,36 @@
 //!
 //! Contains graphical utility functions.
 
+mod accumulator;
 mod canvas;
 mod color;
+mod contact;
+mod dither;
+mod font;
+mod framebuffer;
+#[cfg(feature = "std")]
+mod frames;
 mod lighting;
 mod material;
+mod palette;
+mod pattern;
+mod plot;
+mod tonemap;
+mod uv;
 
+pub use accumulator::Accumulator;
 pub use canvas::Canvas;
-pub use color::Color;
-pub use lighting::Light;
-pub use material::Material;
+pub use color::{Color, OrderedColor};
+pub use contact::ContactSheet;
+pub use dither::Dither;
+pub use framebuffer::Framebuffer;
+#[cfg(feature = "std")]
+pub use frames::FrameWriter;
+pub use lighting::{Light, LightLinking};
+pub use material::{presets, Material};
+pub use palette::Palette;
+pub use pattern::{pattern_at_shape, CheckerMode, Pattern, PatternKind};
+pub(crate) use plot::line;
+pub use plot::Plot;
+pub use tonemap::ToneMap;
+pub use uv::{CubeFace, TextureMap, UvAddress, UvMapping, UvPattern};
@@ -0,0 +1,191 @@
+use super::{Canvas, Color};
+use alloc::{vec, vec::Vec};
+
+/// Buffer merging independent render passes of the same image, keeping the
+/// running mean and variance of every pixel
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Accumulator, Canvas, Color};
+///
+/// let mut accumulator = Accumulator::new(1, 1);
+///
+/// for value in [0.2, 0.4] {
+///     let mut pass = Canvas::new(1, 1);
+///     pass.write_pixel(0, 0, Color::new(value, value, value));
+///     accumulator.add_pass(&pass);
+/// }
+///
+/// assert_eq!(Color::new(0.3, 0.3, 0.3), accumulator.mean().pixel_at(0, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Accumulator {
+    /// Width of the merged image
+    pub width: usize,
+    /// Height of the merged image
+    pub height: usize,
+    passes: usize,
+    mean: Vec<Color>,
+    squared_deviations: Vec<Color>,
+}
+
+impl Accumulator {
+    /// Returns a new accumulator without any passes
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - width of the passes to merge
+    /// * `height` - height of the passes to merge
+    pub fn new(width: usize, height: usize) -> Accumulator {
+        Accumulator {
+            width,
+            height,
+            passes: 0,
+            mean: vec![Color::default(); width * height],
+            squared_deviations: vec![Color::default(); width * height],
+        }
+    }
+
+    /// Returns the number of passes merged so far
+    pub fn passes(&self) -> usize {
+        self.passes
+    }
+
+    /// Merges another render pass into the running statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - independently rendered pass of the image
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvas differs in size from the accumulator
+    pub fn add_pass(&mut self, canvas: &Canvas) {
+        assert!(
+            canvas.width == self.width && canvas.height == self.height,
+            "cannot add a {}x{} pass to a {}x{} accumulator",
+            canvas.width,
+            canvas.height,
+            self.width,
+            self.height
+        );
+
+        self.passes += 1;
+        let n = self.passes as f64;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = y * self.width + x;
+                let value = canvas.pixel_at(x, y);
+                let delta = value - self.mean[i];
+
+                self.mean[i] = self.mean[i] + delta / n;
+                self.squared_deviations[i] =
+                    self.squared_deviations[i] + delta * (value - self.mean[i]);
+            }
+        }
+    }
+
+    /// Returns the mean of all passes merged so far
+    pub fn mean(&self) -> Canvas {
+        self.to_canvas(|i| self.mean[i])
+    }
+
+    /// Returns the sample variance of every pixel over all passes, zero
+    /// until at least two passes are merged
+    pub fn variance(&self) -> Canvas {
+        self.to_canvas(|i| self.pixel_variance(i))
+    }
+
+    /// Returns the largest variance of the mean of any pixel channel, i.e.
+    /// the sample variance divided by the number of passes, which shrinks as
+    /// more passes are merged
+    pub fn max_error(&self) -> f64 {
+        if self.passes < 2 {
+            return f64::INFINITY;
+        }
+
+        (0..self.mean.len())
+            .map(|i| self.pixel_variance(i).max_component())
+            .fold(0.0, f64::max)
+            / self.passes as f64
+    }
+
+    /// Returns true once at least two passes are merged and the variance of
+    /// the mean of every pixel channel is at most the given threshold
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - largest accepted variance of the mean
+    pub fn is_converged(&self, threshold: f64) -> bool {
+        self.max_error() <= threshold
+    }
+
+    fn pixel_variance(&self, i: usize) -> Color {
+        if self.passes < 2 {
+            Color::default()
+        } else {
+            self.squared_deviations[i] / (self.passes - 1) as f64
+        }
+    }
+
+    fn to_canvas<F: Fn(usize) -> Color>(&self, pixel: F) -> Canvas {
+        let mut image = Canvas::new(self.width, self.height);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                image.write_pixel(x, y, pixel(y * self.width + x));
+            }
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pass(value: f64) -> Canvas {
+        let mut c = Canvas::new(2, 1);
+        c.write_pixel(0, 0, Color::new(value, 0.5, 0.0));
+        c
+    }
+
+    #[test]
+    fn should_track_mean_and_variance_of_passes() {
+        let mut accumulator = Accumulator::new(2, 1);
+
+        for value in [1.0, 2.0, 3.0, 4.0] {
+            accumulator.add_pass(&pass(value));
+        }
+
+        assert_eq!(4, accumulator.passes());
+        assert_eq!(Color::new(2.5, 0.5, 0.0), accumulator.mean().pixel_at(0, 0));
+        assert_eq!(
+            Color::new(5.0 / 3.0, 0.0, 0.0),
+            accumulator.variance().pixel_at(0, 0)
+        );
+        assert_eq!(Color::default(), accumulator.variance().pixel_at(1, 0));
+        assert_eq!(5.0 / 12.0, accumulator.max_error());
+    }
+
+    #[test]
+    fn should_converge_once_passes_agree() {
+        let mut accumulator = Accumulator::new(2, 1);
+        accumulator.add_pass(&pass(1.0));
+
+        assert!(!accumulator.is_converged(1.0));
+
+        accumulator.add_pass(&pass(1.0));
+
+        assert!(accumulator.is_converged(0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot add a 1x1 pass to a 2x1 accumulator")]
+    fn should_panic_when_sizes_differ() {
+        Accumulator::new(2, 1).add_pass(&Canvas::new(1, 1));
+    }
+}
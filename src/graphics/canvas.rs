@@ -1,7 +1,26 @@
 use super::color;
+use super::tonemap::{srgb_encode, ToneMap};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
 
 /// Represents a two-dimensional grid of pixels
+///
+/// With the `serde` feature the pixels are serialized as a single flat list
+/// of red, green and blue values in row-major order.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "CanvasData", try_from = "CanvasData")
+)]
 pub struct Canvas {
     /// Width of the canvas
     pub width: usize,
@@ -10,6 +29,67 @@ pub struct Canvas {
     pixels: Vec<Vec<color::Color>>,
 }
 
+/// Serialized form of a canvas, storing the channels of all pixels in a
+/// single flat list
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CanvasData {
+    width: usize,
+    height: usize,
+    pixels: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Canvas> for CanvasData {
+    fn from(canvas: Canvas) -> CanvasData {
+        CanvasData {
+            width: canvas.width,
+            height: canvas.height,
+            pixels: canvas
+                .pixels
+                .iter()
+                .flatten()
+                .flat_map(|c| [c.r, c.g, c.b])
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CanvasData> for Canvas {
+    type Error = String;
+
+    fn try_from(data: CanvasData) -> Result<Canvas, String> {
+        let expected = data
+            .width
+            .checked_mul(data.height)
+            .and_then(|n| n.checked_mul(3))
+            .ok_or_else(|| format!("canvas of {}x{} is too large", data.width, data.height))?;
+
+        if data.pixels.len() != expected {
+            return Err(format!(
+                "expected {} channel values for a {}x{} canvas, found {}",
+                expected,
+                data.width,
+                data.height,
+                data.pixels.len()
+            ));
+        }
+
+        let mut canvas = Canvas::new(data.width, data.height);
+
+        for (i, c) in data.pixels.chunks_exact(3).enumerate() {
+            canvas.write_pixel(
+                i % data.width,
+                i / data.width,
+                color::Color::new(c[0], c[1], c[2]),
+            );
+        }
+
+        Ok(canvas)
+    }
+}
+
 impl Canvas {
     /// Creates a new canvas, given a width and a height
     pub fn new(width: usize, height: usize) -> Canvas {
@@ -21,7 +101,7 @@ impl Canvas {
     }
 
     /// Writes a pixel to the canvas at a point with the given color
-    pub fn write_pixel(&mut self, width: usize, height: usize, color: color::Color) -> () {
+    pub fn write_pixel(&mut self, width: usize, height: usize, color: color::Color) {
         self.pixels[height][width] = color;
     }
 
@@ -30,7 +110,8 @@ impl Canvas {
         self.pixels[height][width]
     }
 
-    /// Converts the canvas to a PPM-encoded string
+    /// Converts the canvas to a PPM-encoded string, encoding rows in parallel
+    /// on every available core
     ///
     /// # Examples
     ///
@@ -42,64 +123,227 @@ impl Canvas {
     /// std::fs::write("ballistics.ppm", c.to_ppm());
     /// ```
     pub fn to_ppm(&self) -> String {
-        let mut s = String::from(format!("P3\n{} {}\n255", self.width, self.height));
-
-        for i in &self.pixels {
-            s.push('\n');
-
-            let mut z = String::new();
+        #[cfg(not(feature = "std"))]
+        let rows: Vec<String> = self.pixels.iter().map(|row| ppm_row(row)).collect();
+
+        #[cfg(feature = "std")]
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(self.height)
+            .max(1);
+        #[cfg(feature = "std")]
+        let chunk = self.height.div_ceil(threads).max(1);
+        #[cfg(feature = "std")]
+        let rows: Vec<String> = std::thread::scope(|scope| {
+            let workers: Vec<_> = self
+                .pixels
+                .chunks(chunk)
+                .map(|rows| {
+                    scope.spawn(move || rows.iter().map(|row| ppm_row(row)).collect::<Vec<_>>())
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("encoding thread panicked"))
+                .collect()
+        });
+
+        let mut s = ppm_header(self.width, self.height);
+
+        for row in rows {
+            s.push_str(&row);
+        }
 
-            for j in i {
-                let st = &format!("{}", clamp(j.r)).to_owned();
+        s.push('\n');
+        s
+    }
 
-                if z.len() + st.len() >= 70 {
-                    z.remove(z.len() - 1);
-                    z.push('\n');
-                    s.push_str(&z.to_owned());
-                    z = String::new();
-                }
+    /// Writes the canvas to the given writer as PPM, encoding and writing
+    /// one row at a time instead of building the whole file in memory
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the encoded image
+    #[cfg(feature = "std")]
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(ppm_header(self.width, self.height).as_bytes())?;
 
-                z.push_str(&format!("{} ", st));
+        for row in &self.pixels {
+            writer.write_all(ppm_row(row).as_bytes())?;
+        }
 
-                let st = &format!("{}", clamp(j.g)).to_owned();
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
 
-                if z.len() + st.len() >= 70 {
-                    z.remove(z.len() - 1);
-                    z.push('\n');
-                    s.push_str(&z.to_owned());
-                    z = String::new();
-                }
+    /// Combines the images of a left and right eye into a red-cyan
+    /// anaglyph, taking the red channel from the left image and the green
+    /// and blue channels from the right one
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - image seen by the left eye
+    /// * `right` - image seen by the right eye
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvases differ in size
+    pub fn anaglyph(left: &Canvas, right: &Canvas) -> Canvas {
+        assert!(
+            left.width == right.width && left.height == right.height,
+            "cannot combine a {}x{} canvas with a {}x{} canvas",
+            left.width,
+            left.height,
+            right.width,
+            right.height
+        );
+
+        let mut image = Canvas::new(left.width, left.height);
+
+        for y in 0..left.height {
+            for x in 0..left.width {
+                let (l, r) = (left.pixel_at(x, y), right.pixel_at(x, y));
+
+                image.write_pixel(x, y, color::Color::new(l.r, r.g, r.b));
+            }
+        }
 
-                z.push_str(&format!("{} ", st));
+        image
+    }
 
-                let st = &format!("{}", clamp(j.b)).to_owned();
+    /// Returns a text rendering of the canvas with one character per pixel,
+    /// darker characters for darker pixels, meant for inspecting small
+    /// renders in a terminal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libray::graphics::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(3, 2);
+    /// c.write_pixel(1, 0, Color::new(1.0, 1.0, 1.0));
+    ///
+    /// assert_eq!(" @ \n   \n", c.ascii_preview());
+    /// ```
+    pub fn ascii_preview(&self) -> String {
+        let ramp: Vec<char> = ASCII_RAMP.chars().collect();
+        let mut s = String::with_capacity((self.width + 1) * self.height);
 
-                if z.len() + st.len() >= 70 {
-                    z.remove(z.len() - 1);
-                    z.push('\n');
-                    s.push_str(&z.to_owned());
-                    z = String::new();
-                }
+        for row in &self.pixels {
+            for pixel in row {
+                let luminance = 0.2126 * pixel.r + 0.7152 * pixel.g + 0.0722 * pixel.b;
+                let level = luminance.clamp(0.0, 1.0) * (ramp.len() - 1) as f64;
 
-                z.push_str(&format!("{} ", st));
+                s.push(ramp[level.round() as usize]);
             }
 
-            z.remove(z.len() - 1);
-            s.push_str(&z.to_owned());
+            s.push('\n');
         }
 
-        s.push('\n');
         s
     }
+
+    /// Returns the pixels as 8-bit red, green and blue values, row by row
+    /// from the top left corner, quantized the same way as `to_ppm`
+    pub fn to_rgb8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flatten()
+            .flat_map(|c| [clamp(c.r), clamp(c.g), clamp(c.b)])
+            .collect()
+    }
+
+    /// Converts the pixels into 8-bit sRGB encoded red, green, blue and
+    /// opaque alpha values written into the given buffer, row by row from
+    /// the top left corner, without allocating
+    ///
+    /// The layout matches `Rgba8UnormSrgb` textures in wgpu and unmultiplied
+    /// RGBA images in egui, so a buffer of `width * height * 4` bytes can be
+    /// reused to upload every frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - destination of exactly `width * height * 4` bytes
+    /// * `tone_map` - operator mapping the linear colors into display range
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer length differs from `width * height * 4`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::{Canvas, Color, ToneMap};
+    ///
+    /// let mut canvas = Canvas::new(2, 1);
+    /// canvas.write_pixel(1, 0, Color::new(1.0, 0.5, 3.0));
+    /// let mut buffer = vec![0; 2 * 1 * 4];
+    ///
+    /// canvas.to_rgba8_into(&mut buffer, ToneMap::Clamp);
+    ///
+    /// assert_eq!([0, 0, 0, 255, 255, 188, 255, 255], buffer[..]);
+    /// ```
+    pub fn to_rgba8_into(&self, buffer: &mut [u8], tone_map: ToneMap) {
+        assert_eq!(
+            self.width * self.height * 4,
+            buffer.len(),
+            "buffer length differs from width * height * 4"
+        );
+
+        for (pixel, rgba) in self.pixels.iter().flatten().zip(buffer.chunks_exact_mut(4)) {
+            let c = tone_map.apply(*pixel);
+
+            rgba[0] = srgb_encode(c.r);
+            rgba[1] = srgb_encode(c.g);
+            rgba[2] = srgb_encode(c.b);
+            rgba[3] = u8::MAX;
+        }
+    }
 }
 
+/// Returns the PPM header for an image of the given size, without the final
+/// line break
+fn ppm_header(width: usize, height: usize) -> String {
+    format!("P3\n{} {}\n255", width, height)
+}
+
+/// Encodes a single row of pixels as PPM, preceded by a line break and with
+/// lines wrapped before reaching 70 characters
+fn ppm_row(row: &[color::Color]) -> String {
+    let mut s = String::new();
+    let mut line = String::new();
+
+    for value in row.iter().flat_map(|c| [c.r, c.g, c.b]) {
+        let value = clamp(value).to_string();
+
+        if line.len() + value.len() >= 70 {
+            line.pop();
+            s.push('\n');
+            s.push_str(&line);
+            line.clear();
+        }
+
+        line.push_str(&value);
+        line.push(' ');
+    }
+
+    line.pop();
+    s.push('\n');
+    s.push_str(&line);
+    s
+}
+
+/// Characters used by `Canvas::ascii_preview`, ordered from dark to bright
+const ASCII_RAMP: &str = " .:-=+*#%@";
+
 fn clamp(v: f64) -> u8 {
     if v < 0.0 {
         0
     } else if v > 1.0 {
         255
     } else {
-        (v * (255 as f64)).ceil() as u8
+        (v * 255.0).ceil() as u8
     }
 }
 
@@ -108,6 +352,14 @@ mod tests {
     use super::color::Color;
     use super::*;
 
+    #[test]
+    fn should_export_rgb8_pixels() {
+        let mut c = Canvas::new(2, 1);
+        c.write_pixel(1, 0, Color::new(1.5, 0.5, -0.5));
+
+        assert_eq!(vec![0, 0, 0, 255, 128, 0], c.to_rgb8());
+    }
+
     #[test]
     fn should_contain_correct_data() {
         let c = Canvas::new(10, 20);
@@ -130,21 +382,21 @@ mod tests {
     fn should_construct_ppm_header() {
         let c = Canvas::new(5, 3);
 
-        assert_eq!(true, c.to_ppm().contains("P3\n5 3\n255"));
+        assert!(c.to_ppm().contains("P3\n5 3\n255"));
     }
 
     #[test]
     fn should_construct_ppm_pixel_data_correctly() {
         let mut d = Canvas::new(5, 3);
-        let a = color::Color::new(1.5, 0.0, 0.0);
-        let b = color::Color::new(0.0, 0.5, 0.0);
-        let c = color::Color::new(-0.5, 0.0, 1.0);
+        let a = Color::new(1.5, 0.0, 0.0);
+        let b = Color::new(0.0, 0.5, 0.0);
+        let c = Color::new(-0.5, 0.0, 1.0);
 
         d.write_pixel(0, 0, a);
         d.write_pixel(2, 1, b);
         d.write_pixel(4, 2, c);
 
-        assert_eq!(true, d.to_ppm().contains("255 0 0 0 0 0 0 0 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 128 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 0 0 0 0 0 0 0 255"));
+        assert!(d.to_ppm().contains("255 0 0 0 0 0 0 0 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 128 0 0 0 0 0 0 0\n0 0 0 0 0 0 0 0 0 0 0 0 0 0 255"));
     }
 
     #[test]
@@ -153,13 +405,13 @@ mod tests {
 
         for i in 0..10 {
             for j in 0..2 {
-                c.write_pixel(i, j, color::Color::new(1.0, 0.8, 0.6));
+                c.write_pixel(i, j, Color::new(1.0, 0.8, 0.6));
             }
         }
 
         eprintln!("{}", c.to_ppm());
 
-        assert_eq!(true, c.to_ppm().contains("255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204\n153 255 204 153 255 204 153 255 204 153 255 204 153\n255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204\n153 255 204 153 255 204 153 255 204 153 255 204 153"));
+        assert!(c.to_ppm().contains("255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204\n153 255 204 153 255 204 153 255 204 153 255 204 153\n255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204\n153 255 204 153 255 204 153 255 204 153 255 204 153"));
     }
 
     #[test]
@@ -168,4 +420,75 @@ mod tests {
 
         assert_eq!('\n', c.to_ppm().chars().last().unwrap());
     }
+
+    #[test]
+    fn ascii_preview_should_map_brightness_to_characters() {
+        let mut c = Canvas::new(4, 1);
+
+        c.write_pixel(1, 0, Color::new(0.5, 0.5, 0.5));
+        c.write_pixel(2, 0, Color::new(1.0, 1.0, 1.0));
+        c.write_pixel(3, 0, Color::new(5.0, 5.0, 5.0));
+
+        assert_eq!(" +@@\n", c.ascii_preview());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_compact_binary_layout() {
+        let mut c = Canvas::new(3, 2);
+        c.write_pixel(2, 1, Color::new(0.25, 0.5, 1.5));
+        let bytes = bincode::serialize(&c).unwrap();
+        let d: Canvas = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(8 * 2 + 8 + 3 * 2 * 3 * 8, bytes.len());
+        assert_eq!(c.pixel_at(2, 1), d.pixel_at(2, 1));
+        assert_eq!(c.to_ppm(), d.to_ppm());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_reject_pixel_data_of_wrong_length() {
+        let json = r#"{"width":2,"height":2,"pixels":[0.0,0.0,0.0]}"#;
+
+        assert!(serde_json::from_str::<Canvas>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_reject_sizes_overflowing_pixel_count() {
+        let json = format!(r#"{{"width":{},"height":2,"pixels":[]}}"#, usize::MAX);
+        let err = serde_json::from_str::<Canvas>(&json).unwrap_err();
+
+        assert!(err.to_string().contains("too large"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn should_write_same_ppm_as_encoded_string() {
+        let mut c = Canvas::new(30, 7);
+
+        for y in 0..7 {
+            for x in 0..30 {
+                c.write_pixel(x, y, Color::new(x as f64 / 30.0, y as f64 / 7.0, 0.5));
+            }
+        }
+
+        let mut bytes = vec![];
+        c.write_ppm(&mut bytes).unwrap();
+
+        assert_eq!(c.to_ppm(), String::from_utf8(bytes).unwrap());
+        assert!(c.to_ppm().ends_with('\n'));
+    }
+
+    #[test]
+    fn anaglyph_should_take_red_from_left_and_cyan_from_right() {
+        let mut left = Canvas::new(2, 1);
+        let mut right = Canvas::new(2, 1);
+        left.write_pixel(0, 0, Color::new(0.8, 0.5, 0.5));
+        right.write_pixel(0, 0, Color::new(0.1, 0.2, 0.3));
+        let image = Canvas::anaglyph(&left, &right);
+
+        assert_eq!(Color::new(0.8, 0.2, 0.3), image.pixel_at(0, 0));
+        assert_eq!(Color::default(), image.pixel_at(1, 0));
+    }
 }
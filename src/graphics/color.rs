@@ -1,10 +1,14 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
 /// Fundamental color component
-use std::ops::{Add, Div, Mul, Neg, Sub};
-
-const EPSILON: f64 = 0.00001;
+use crate::math::{format_f64, quantize, EPSILON};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Three-dimensional color representation
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Color {
     /// Red value
     pub r: f64,
@@ -12,28 +16,142 @@ pub struct Color {
     pub g: f64,
     /// Blue value
     pub b: f64,
-    _private: (),
 }
 
 impl Color {
     /// Creates a new color
     pub fn new(r: f64, g: f64, b: f64) -> Color {
-        Color {
-            r,
-            g,
-            b,
-            _private: (),
+        Color { r, g, b }
+    }
+
+    /// Returns the largest of the three components
+    pub fn max_component(&self) -> f64 {
+        self.r.max(self.g).max(self.b)
+    }
+
+    /// Returns the color scaled down so that no component exceeds the given
+    /// limit, keeping the ratio between components intact
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - largest value any component may have
+    pub fn limit(self, limit: f64) -> Color {
+        let max = self.max_component();
+
+        if max > limit {
+            self * (limit / max)
+        } else {
+            self
+        }
+    }
+
+    /// Returns a color on a blue, green, red heat map ramp
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - position on the ramp, clamped to `0.0..=1.0`
+    pub fn heat(value: f64) -> Color {
+        let v = value.clamp(0.0, 1.0);
+
+        if v < 0.5 {
+            Color::new(0.0, v * 2.0, 1.0 - v * 2.0)
+        } else {
+            Color::new(v * 2.0 - 1.0, 2.0 - v * 2.0, 0.0)
+        }
+    }
+
+    /// Returns the color of light emitted by a black body at the given
+    /// temperature, normalized so that its brightest component is one
+    ///
+    /// Uses a curve fit of the Planckian locus, valid from 1000 K to
+    /// 40000 K. Temperatures outside of that range are clamped to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `kelvin` - temperature of the black body in kelvin
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::Color;
+    ///
+    /// let candle = Color::blackbody(1900.0);
+    /// let daylight = Color::blackbody(6600.0);
+    ///
+    /// assert!(candle.r > candle.g && candle.g > candle.b);
+    /// assert_eq!(Color::new(1.0, 1.0, 1.0), daylight);
+    /// ```
+    pub fn blackbody(kelvin: f64) -> Color {
+        let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+        let channel = |value: f64| (value / 255.0).clamp(0.0, 1.0);
+
+        let r = if t <= 66.0 {
+            1.0
+        } else {
+            channel(329.698727446 * (t - 60.0).powf(-0.1332047592))
+        };
+        let g = if t <= 66.0 {
+            channel(99.4708025861 * t.ln() - 161.1195681661)
+        } else {
+            channel(288.1221695283 * (t - 60.0).powf(-0.0755148492))
+        };
+        let b = if t >= 66.0 {
+            1.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            channel(138.5177312231 * (t - 10.0).ln() - 305.0447927307)
+        };
+
+        Color::new(r, g, b)
+    }
+}
+
+/// Canonical, hashable and totally ordered form of a `Color`
+///
+/// Every component is snapped to a grid of `EPSILON` sized steps so that
+/// colors can be used as keys for palette extraction, deduplication and
+/// caching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OrderedColor {
+    r: i64,
+    g: i64,
+    b: i64,
+}
+
+impl OrderedColor {
+    /// Returns the canonical form of the given color
+    pub fn new(color: Color) -> OrderedColor {
+        OrderedColor {
+            r: quantize(color.r),
+            g: quantize(color.g),
+            b: quantize(color.b),
         }
     }
 }
 
+impl From<Color> for OrderedColor {
+    fn from(color: Color) -> OrderedColor {
+        OrderedColor::new(color)
+    }
+}
+
+impl From<OrderedColor> for Color {
+    fn from(c: OrderedColor) -> Color {
+        Color::new(
+            c.r as f64 * EPSILON,
+            c.g as f64 * EPSILON,
+            c.b as f64 * EPSILON,
+        )
+    }
+}
+
 impl Default for Color {
     fn default() -> Color {
         Color {
             r: 0.0,
             g: 0.0,
             b: 0.0,
-            _private: (),
         }
     }
 }
@@ -110,6 +228,22 @@ impl Div<f64> for Color {
     }
 }
 
+impl fmt::Display for Color {
+    /// Formats the color components, rounded to the formatter precision or
+    /// five decimals
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(5);
+
+        write!(
+            f,
+            "color({}, {}, {})",
+            format_f64(self.r, precision),
+            format_f64(self.g, precision),
+            format_f64(self.b, precision)
+        )
+    }
+}
+
 impl PartialEq for Color {
     fn eq(&self, other: &Color) -> bool {
         (self.r - other.r).abs() < EPSILON
@@ -122,6 +256,19 @@ impl PartialEq for Color {
 mod tests {
     use super::*;
 
+    #[test]
+    fn blackbody_should_shift_from_red_to_blue() {
+        let ember = Color::blackbody(1000.0);
+        let sky = Color::blackbody(20000.0);
+
+        assert_eq!(1.0, ember.r);
+        assert_eq!(0.0, ember.b);
+        assert!(ember.g < 0.3);
+        assert_eq!(1.0, sky.b);
+        assert!(sky.r < sky.g && sky.g < sky.b);
+        assert_eq!(Color::blackbody(40000.0), Color::blackbody(1.0e6));
+    }
+
     #[test]
     fn new_color_contains_correct_values() {
         let c = Color::new(-0.5, 0.4, 1.7);
@@ -165,4 +312,46 @@ mod tests {
 
         assert_eq!(reference, a * b);
     }
+
+    #[test]
+    fn should_display_rounded_components() {
+        let c = Color::new(1.0, 0.25, -0.000001);
+
+        assert_eq!("color(1.00000, 0.25000, 0.00000)", format!("{}", c));
+        assert_eq!("color(1.0, 0.3, 0.0)", format!("{:.1}", c));
+    }
+
+    #[test]
+    fn ordered_colors_should_deduplicate_and_sort() {
+        use std::collections::BTreeSet;
+
+        let colors = [
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 0.5, 0.0),
+            Color::new(1.0 + 1e-9, 0.0, 0.0),
+        ];
+        let set: BTreeSet<OrderedColor> = colors.iter().map(|c| (*c).into()).collect();
+        let palette: Vec<Color> = set.into_iter().map(Color::from).collect();
+
+        assert_eq!(
+            vec![Color::new(0.0, 0.5, 0.0), Color::new(1.0, 0.0, 0.0)],
+            palette
+        );
+    }
+
+    #[test]
+    fn limit_should_scale_down_keeping_hue() {
+        let c = Color::new(4.0, 2.0, 1.0);
+
+        assert_eq!(4.0, c.max_component());
+        assert_eq!(Color::new(1.0, 0.5, 0.25), c.limit(1.0));
+        assert_eq!(c, c.limit(10.0));
+    }
+
+    #[test]
+    fn heat_should_ramp_from_blue_over_green_to_red() {
+        assert_eq!(Color::new(0.0, 0.0, 1.0), Color::heat(-1.0));
+        assert_eq!(Color::new(0.0, 1.0, 0.0), Color::heat(0.5));
+        assert_eq!(Color::new(1.0, 0.0, 0.0), Color::heat(2.0));
+    }
 }
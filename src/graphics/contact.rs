@@ -0,0 +1,239 @@
+use super::font::{draw_text, ADVANCE, GLYPH_HEIGHT};
+use super::{Canvas, Color};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use alloc::vec::Vec;
+
+/// Layout of a contact sheet, a labeled grid of thumbnails assembled from
+/// many canvases such as animation frames or material test renders for
+/// review at a glance
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Canvas, ContactSheet};
+///
+/// let frames: Vec<Canvas> = (0..5).map(|_| Canvas::new(64, 48)).collect();
+/// let labels: Vec<String> = (0..5).map(|i| format!("frame {}", i)).collect();
+///
+/// let mut layout = ContactSheet::new(3);
+/// layout.thumbnail = Some((32, 32));
+/// let sheet = layout.build(labels.iter().map(String::as_str).zip(&frames));
+///
+/// // three columns and two rows of 32x24 thumbnails with labels below
+/// assert_eq!(3 * (32 + 4) + 4, sheet.width);
+/// assert_eq!(2 * (24 + 4 + 5 + 4) + 4, sheet.height);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactSheet {
+    /// Number of thumbnails per row
+    pub columns: usize,
+    /// Largest width and height of a thumbnail, larger canvases are scaled
+    /// down keeping their aspect ratio, `None` to keep every canvas at its
+    /// size
+    pub thumbnail: Option<(usize, usize)>,
+    /// Space in pixels around and between the thumbnails
+    pub padding: usize,
+    /// Size in pixels of a single pixel of the label font, `0` to leave out
+    /// the labels
+    pub label_scale: usize,
+    /// Color of the space between the thumbnails
+    pub background: Color,
+    /// Color of the labels
+    pub label_color: Color,
+}
+
+impl ContactSheet {
+    /// Returns a layout with the given number of columns, full size
+    /// thumbnails and white labels on a dark gray background
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - number of thumbnails per row
+    pub fn new(columns: usize) -> ContactSheet {
+        ContactSheet {
+            columns,
+            thumbnail: None,
+            padding: 4,
+            label_scale: 1,
+            background: Color::new(0.1, 0.1, 0.1),
+            label_color: Color::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Assembles the given canvases into a contact sheet, filling rows from
+    /// left to right and labeling every thumbnail below it
+    ///
+    /// Every cell is as large as the largest thumbnail, and labels are
+    /// clipped to the width of their cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - labels and canvases to show, in order
+    pub fn build<'a, I>(&self, items: I) -> Canvas
+    where
+        I: IntoIterator<Item = (&'a str, &'a Canvas)>,
+    {
+        let thumbnails: Vec<(&str, Canvas)> = items
+            .into_iter()
+            .map(|(label, canvas)| match self.thumbnail {
+                Some((width, height)) => (label, canvas.thumbnail(width, height)),
+                None => (label, canvas.clone()),
+            })
+            .collect();
+
+        let columns = self.columns.max(1);
+        let rows = thumbnails.len().div_ceil(columns);
+        let cell_width = thumbnails.iter().map(|(_, t)| t.width).max().unwrap_or(0);
+        let cell_height = thumbnails.iter().map(|(_, t)| t.height).max().unwrap_or(0);
+        let label_height = if self.label_scale > 0 {
+            self.padding + GLYPH_HEIGHT * self.label_scale
+        } else {
+            0
+        };
+        let step_x = cell_width + self.padding;
+        let step_y = cell_height + label_height + self.padding;
+
+        let mut sheet = Canvas::new(
+            columns.min(thumbnails.len()) * step_x + self.padding,
+            rows * step_y + self.padding,
+        );
+
+        for y in 0..sheet.height {
+            for x in 0..sheet.width {
+                sheet.write_pixel(x, y, self.background);
+            }
+        }
+
+        for (i, (label, thumbnail)) in thumbnails.iter().enumerate() {
+            let left = self.padding + (i % columns) * step_x;
+            let top = self.padding + (i / columns) * step_y;
+            let offset = (cell_width - thumbnail.width) / 2;
+
+            for y in 0..thumbnail.height {
+                for x in 0..thumbnail.width {
+                    sheet.write_pixel(left + offset + x, top + y, thumbnail.pixel_at(x, y));
+                }
+            }
+
+            if self.label_scale > 0 {
+                let text_width = label.chars().count() * ADVANCE * self.label_scale;
+                let indent = cell_width.saturating_sub(text_width) / 2;
+
+                draw_text(
+                    &mut sheet,
+                    left + indent,
+                    top + cell_height + self.padding,
+                    left + cell_width,
+                    label,
+                    self.label_scale,
+                    self.label_color,
+                );
+            }
+        }
+
+        sheet
+    }
+}
+
+impl Default for ContactSheet {
+    fn default() -> ContactSheet {
+        ContactSheet::new(4)
+    }
+}
+
+impl Canvas {
+    /// Returns a copy of the canvas scaled down to fit the given size,
+    /// keeping its aspect ratio and averaging the pixels every thumbnail
+    /// pixel covers
+    ///
+    /// Canvases already fitting the size are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` - largest width of the thumbnail
+    /// * `max_height` - largest height of the thumbnail
+    pub fn thumbnail(&self, max_width: usize, max_height: usize) -> Canvas {
+        if self.width <= max_width && self.height <= max_height {
+            return self.clone();
+        }
+
+        let factor =
+            (max_width as f64 / self.width as f64).min(max_height as f64 / self.height as f64);
+        let width = ((self.width as f64 * factor).round() as usize).clamp(1, max_width.max(1));
+        let height = ((self.height as f64 * factor).round() as usize).clamp(1, max_height.max(1));
+        let mut thumbnail = Canvas::new(width, height);
+
+        for ty in 0..height {
+            let rows = ty * self.height / height
+                ..((ty + 1) * self.height / height).max(ty * self.height / height + 1);
+
+            for tx in 0..width {
+                let columns = tx * self.width / width
+                    ..((tx + 1) * self.width / width).max(tx * self.width / width + 1);
+                let count = (rows.len() * columns.len()) as f64;
+                let sum = rows
+                    .clone()
+                    .flat_map(|y| columns.clone().map(move |x| (x, y)))
+                    .fold(Color::default(), |sum, (x, y)| sum + self.pixel_at(x, y));
+
+                thumbnail.write_pixel(tx, ty, sum / count);
+            }
+        }
+
+        thumbnail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumbnail_should_average_and_keep_aspect_ratio() {
+        let mut c = Canvas::new(4, 2);
+        c.write_pixel(0, 0, Color::new(1.0, 1.0, 1.0));
+        c.write_pixel(1, 1, Color::new(1.0, 1.0, 1.0));
+
+        let t = c.thumbnail(2, 2);
+
+        assert_eq!((2, 1), (t.width, t.height));
+        assert_eq!(Color::new(0.5, 0.5, 0.5), t.pixel_at(0, 0));
+        assert_eq!(Color::default(), t.pixel_at(1, 0));
+        assert_eq!(4, c.thumbnail(10, 10).width);
+    }
+
+    #[test]
+    fn build_should_place_thumbnails_in_rows_with_labels() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut a = Canvas::new(4, 4);
+        let b = Canvas::new(2, 2);
+        for y in 0..4 {
+            for x in 0..4 {
+                a.write_pixel(x, y, red);
+            }
+        }
+
+        let mut layout = ContactSheet::new(1);
+        layout.padding = 2;
+        let sheet = layout.build([("A", &a), ("B", &b)]);
+
+        // one column of 4x4 cells, each followed by a 5 pixel label
+        assert_eq!((8, 2 * (4 + 2 + 5 + 2) + 2), (sheet.width, sheet.height));
+        assert_eq!(red, sheet.pixel_at(2, 2));
+        assert_eq!(layout.background, sheet.pixel_at(0, 0));
+        // the smaller canvas is centered in its cell
+        assert_eq!(layout.background, sheet.pixel_at(2, 15));
+        assert_eq!(Color::default(), sheet.pixel_at(3, 15));
+        // the top of the A label is its middle column
+        assert_eq!(layout.label_color, sheet.pixel_at(3, 8));
+        assert_eq!(layout.background, sheet.pixel_at(2, 8));
+    }
+
+    #[test]
+    fn build_should_handle_no_canvases() {
+        let sheet = ContactSheet::default().build([]);
+
+        assert_eq!((4, 4), (sheet.width, sheet.height));
+    }
+}
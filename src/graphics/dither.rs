@@ -0,0 +1,154 @@
+use super::{Canvas, Color};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use alloc::{vec, vec::Vec};
+
+/// 4x4 Bayer threshold matrix used by ordered dithering
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Strategy distributing the rounding error when colors are reduced to 8
+/// bits per component, which breaks up visible banding in smooth gradients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dither {
+    /// Round every component to the nearest level
+    None,
+    /// Offset the rounding threshold by a repeating 4x4 Bayer pattern,
+    /// cheap and stable between animation frames
+    Ordered,
+    /// Diffuse the rounding error of every pixel onto its unvisited
+    /// neighbours, with the Floyd-Steinberg weights
+    FloydSteinberg,
+}
+
+impl Canvas {
+    /// Returns the pixels as 8-bit red, green and blue values, row by row
+    /// from the top left corner, dithered with the given strategy
+    ///
+    /// Components are clamped to `0.0..=1.0` and scaled linearly.
+    ///
+    /// # Arguments
+    ///
+    /// * `dither` - strategy distributing the rounding error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::{Canvas, Color, Dither};
+    ///
+    /// let mut canvas = Canvas::new(4, 4);
+    /// for y in 0..4 {
+    ///     for x in 0..4 {
+    ///         canvas.write_pixel(x, y, Color::new(0.5, 0.5, 0.5));
+    ///     }
+    /// }
+    ///
+    /// let bytes = canvas.to_rgb8_dithered(Dither::Ordered);
+    ///
+    /// assert!(bytes.contains(&127) && bytes.contains(&128));
+    /// ```
+    pub fn to_rgb8_dithered(&self, dither: Dither) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.width * self.height * 3);
+        // errors diffused onto the current and the next row, padded by one
+        // pixel on both sides
+        let mut current = vec![Color::default(); self.width + 2];
+        let mut next = vec![Color::default(); self.width + 2];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = self.pixel_at(x, y);
+                let level = Color::new(level(c.r), level(c.g), level(c.b));
+
+                let quantized = match dither {
+                    Dither::None => level.map(f64::round),
+                    Dither::Ordered => {
+                        let threshold = (f64::from(BAYER[y % 4][x % 4]) + 0.5) / 16.0;
+
+                        level.map(|v| (v + threshold - 0.5).round())
+                    }
+                    Dither::FloydSteinberg => {
+                        let target = level + current[x + 1];
+                        let quantized = target.map(|v| v.round().clamp(0.0, 255.0));
+                        let error = target - quantized;
+
+                        current[x + 2] = current[x + 2] + error * (7.0 / 16.0);
+                        next[x] = next[x] + error * (3.0 / 16.0);
+                        next[x + 1] = next[x + 1] + error * (5.0 / 16.0);
+                        next[x + 2] = next[x + 2] + error * (1.0 / 16.0);
+
+                        quantized
+                    }
+                };
+
+                bytes.extend([byte(quantized.r), byte(quantized.g), byte(quantized.b)]);
+            }
+
+            core::mem::swap(&mut current, &mut next);
+            next.fill(Color::default());
+        }
+
+        bytes
+    }
+}
+
+impl Color {
+    fn map<F: Fn(f64) -> f64>(self, f: F) -> Color {
+        Color::new(f(self.r), f(self.g), f(self.b))
+    }
+}
+
+/// Returns a component clamped to the displayable range and scaled to
+/// `0.0..=255.0`
+fn level(v: f64) -> f64 {
+    v.clamp(0.0, 1.0) * 255.0
+}
+
+fn byte(v: f64) -> u8 {
+    v.clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(value: f64) -> Canvas {
+        let mut c = Canvas::new(8, 8);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                c.write_pixel(x, y, Color::new(value, value, value));
+            }
+        }
+
+        c
+    }
+
+    fn mean(bytes: &[u8]) -> f64 {
+        bytes.iter().map(|&b| f64::from(b)).sum::<f64>() / bytes.len() as f64
+    }
+
+    #[test]
+    fn undithered_should_round_to_a_single_level() {
+        let bytes = flat(0.5).to_rgb8_dithered(Dither::None);
+
+        assert!(bytes.iter().all(|&b| b == 128));
+    }
+
+    #[test]
+    fn dithering_should_mix_levels_preserving_the_mean() {
+        for dither in [Dither::Ordered, Dither::FloydSteinberg] {
+            let bytes = flat(0.5).to_rgb8_dithered(dither);
+
+            assert!(bytes.contains(&127) && bytes.contains(&128));
+            assert!((mean(&bytes) - 127.5).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn dithering_should_keep_extremes_exact() {
+        for dither in [Dither::None, Dither::Ordered, Dither::FloydSteinberg] {
+            assert!(flat(0.0).to_rgb8_dithered(dither).iter().all(|&b| b == 0));
+            assert!(flat(2.0).to_rgb8_dithered(dither).iter().all(|&b| b == 255));
+        }
+    }
+}
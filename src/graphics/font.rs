@@ -0,0 +1,134 @@
+use super::{Canvas, Color};
+
+/// Width of a glyph in font pixels
+pub(crate) const GLYPH_WIDTH: usize = 3;
+/// Height of a glyph in font pixels
+pub(crate) const GLYPH_HEIGHT: usize = 5;
+/// Horizontal distance between the starts of two glyphs in font pixels
+pub(crate) const ADVANCE: usize = GLYPH_WIDTH + 1;
+
+/// Returns the rows of a 3x5 pixel glyph from top to bottom, the highest of
+/// the three bits of a row being its leftmost pixel
+///
+/// Lowercase letters are drawn as uppercase, characters without a glyph as
+/// a question mark.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        _ => [0b111, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+/// Draws a single line of text with its top left corner at the given
+/// pixel, clipping it to the canvas and to the given right edge
+///
+/// # Arguments
+///
+/// * `canvas` - canvas to draw onto
+/// * `x` - horizontal pixel coordinate of the left edge of the text
+/// * `y` - vertical pixel coordinate of the top edge of the text
+/// * `right` - horizontal pixel coordinate the text is clipped before
+/// * `text` - text to draw
+/// * `scale` - size in canvas pixels of a single font pixel
+/// * `color` - color of the text
+pub(crate) fn draw_text(
+    canvas: &mut Canvas,
+    x: usize,
+    y: usize,
+    right: usize,
+    text: &str,
+    scale: usize,
+    color: Color,
+) {
+    let right = right.min(canvas.width);
+
+    for (i, c) in text.chars().enumerate() {
+        let left = x + i * ADVANCE * scale;
+
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+
+                for py in y + row * scale..y + (row + 1) * scale {
+                    for px in left + column * scale..left + (column + 1) * scale {
+                        if px < right && py < canvas.height {
+                            canvas.write_pixel(px, py, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_text_should_scale_and_clip_glyphs() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let mut c = Canvas::new(10, 12);
+
+        draw_text(&mut c, 0, 0, 6, "1l", 2, white);
+
+        // the top of the 1 is its middle column
+        assert_eq!(Color::default(), c.pixel_at(0, 0));
+        assert_eq!(white, c.pixel_at(2, 0));
+        assert_eq!(white, c.pixel_at(3, 1));
+        // the bottom row of the 1 is filled
+        assert_eq!(white, c.pixel_at(0, 9));
+        // the L is clipped at the right edge
+        assert_eq!(Color::default(), c.pixel_at(6, 9));
+    }
+}
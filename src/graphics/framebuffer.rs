@@ -0,0 +1,70 @@
+use super::{Canvas, Color};
+
+/// Destination of rendered pixels, implemented by `Canvas` and by external
+/// buffers such as GUI framebuffers, shared memory or mapped GPU memory so
+/// that frames can be rendered into them without an intermediate canvas
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Color, Framebuffer};
+///
+/// /// Packed `0x00RRGGBB` pixels as used by many windowing libraries
+/// struct Packed<'a> {
+///     pixels: &'a mut [u32],
+///     width: usize,
+/// }
+///
+/// impl Framebuffer for Packed<'_> {
+///     fn width(&self) -> usize {
+///         self.width
+///     }
+///
+///     fn height(&self) -> usize {
+///         self.pixels.len() / self.width
+///     }
+///
+///     fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+///         let channel = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u32;
+///
+///         self.pixels[y * self.width + x] =
+///             channel(color.r) << 16 | channel(color.g) << 8 | channel(color.b);
+///     }
+/// }
+///
+/// let mut pixels = vec![0; 4];
+/// let mut buffer = Packed { pixels: &mut pixels, width: 2 };
+/// buffer.set_pixel(1, 1, Color::new(1.0, 0.0, 1.0));
+///
+/// assert_eq!(0xff00ff, pixels[3]);
+/// ```
+pub trait Framebuffer {
+    /// Returns the width of the buffer in pixels
+    fn width(&self) -> usize;
+
+    /// Returns the height of the buffer in pixels
+    fn height(&self) -> usize;
+
+    /// Stores the color of a pixel, in linear unclamped components
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - horizontal pixel coordinate from the left
+    /// * `y` - vertical pixel coordinate from the top
+    /// * `color` - color of the pixel
+    fn set_pixel(&mut self, x: usize, y: usize, color: Color);
+}
+
+impl Framebuffer for Canvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        self.write_pixel(x, y, color);
+    }
+}
@@ -0,0 +1,219 @@
+use super::Canvas;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// Callback receiving the number and canvas of every written frame
+type FrameSink = Box<dyn FnMut(usize, &Canvas) -> io::Result<()> + Send>;
+
+/// Writes numbered frames of an animation to files named after a template,
+/// optionally handing each frame to a callback as well
+///
+/// Frames are encoded as PPM. The template holds a single placeholder for
+/// the frame number, either `{}` or `{:N}` and `{:0N}` for a number padded
+/// with spaces or zeros to a width of `N`.
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::FrameWriter;
+///
+/// let writer = FrameWriter::new("frames", "frame_{:04}.ppm");
+///
+/// assert_eq!(
+///     Some(std::path::Path::new("frames").join("frame_0042.ppm")),
+///     writer.path_for(42)
+/// );
+/// ```
+pub struct FrameWriter {
+    /// Directory the frames are written to, or `None` to only call the sink
+    pub directory: Option<PathBuf>,
+    /// File name template holding a placeholder for the frame number
+    pub pattern: String,
+    /// Number given to the next written frame
+    pub next: usize,
+    sink: Option<FrameSink>,
+}
+
+impl FrameWriter {
+    /// Returns a new frame writer numbering frames from zero
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - directory to write the frames to
+    /// * `pattern` - file name template, e.g. `frame_{:04}.ppm`
+    pub fn new<P: AsRef<Path>>(directory: P, pattern: &str) -> FrameWriter {
+        FrameWriter {
+            directory: Some(directory.as_ref().to_path_buf()),
+            pattern: pattern.to_string(),
+            next: 0,
+            sink: None,
+        }
+    }
+
+    /// Returns a new frame writer which writes no files and only hands each
+    /// frame to the given callback, e.g. to pipe it into an encoder
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - callback receiving the frame number and canvas
+    pub fn piped<F>(sink: F) -> FrameWriter
+    where
+        F: FnMut(usize, &Canvas) -> io::Result<()> + Send + 'static,
+    {
+        FrameWriter {
+            directory: None,
+            pattern: String::new(),
+            next: 0,
+            sink: Some(Box::new(sink)),
+        }
+    }
+
+    /// Hands each frame to the given callback after writing it
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - callback receiving the frame number and canvas
+    pub fn with_sink<F>(mut self, sink: F) -> FrameWriter
+    where
+        F: FnMut(usize, &Canvas) -> io::Result<()> + Send + 'static,
+    {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Returns the path the given frame is written to, or `None` if no
+    /// directory is set or the template holds no valid placeholder
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - number of the frame
+    pub fn path_for(&self, frame: usize) -> Option<PathBuf> {
+        let directory = self.directory.as_ref()?;
+
+        Some(directory.join(format_frame(&self.pattern, frame)?))
+    }
+
+    /// Writes the canvas as the next frame, returning its number
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - frame to write
+    ///
+    /// # Errors
+    ///
+    /// Fails if the template is invalid, the file cannot be written or the
+    /// sink fails
+    pub fn write(&mut self, canvas: &Canvas) -> io::Result<usize> {
+        let frame = self.next;
+
+        if self.directory.is_some() {
+            let path = self.path_for(frame).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid frame name template: {}", self.pattern),
+                )
+            })?;
+
+            canvas.write_ppm(BufWriter::new(File::create(path)?))?;
+        }
+
+        if let Some(sink) = &mut self.sink {
+            sink(frame, canvas)?;
+        }
+
+        self.next += 1;
+        Ok(frame)
+    }
+}
+
+impl fmt::Debug for FrameWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameWriter")
+            .field("directory", &self.directory)
+            .field("pattern", &self.pattern)
+            .field("next", &self.next)
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
+}
+
+/// Replaces the placeholder in the template with the frame number
+fn format_frame(pattern: &str, frame: usize) -> Option<String> {
+    let start = pattern.find('{')?;
+    let end = start + pattern[start..].find('}')?;
+    let spec = &pattern[start + 1..end];
+    let number = match spec.strip_prefix(':') {
+        None if spec.is_empty() => frame.to_string(),
+        Some(width) if width.starts_with('0') => {
+            format!("{:0width$}", frame, width = width.parse().ok()?)
+        }
+        Some(width) => format!("{:width$}", frame, width = width.parse().ok()?),
+        None => return None,
+    };
+
+    Some(format!(
+        "{}{}{}",
+        &pattern[..start],
+        number,
+        &pattern[end + 1..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::Color;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn should_format_frame_numbers() {
+        assert_eq!(Some("f7.ppm".to_string()), format_frame("f{}.ppm", 7));
+        assert_eq!(Some("f007.ppm".to_string()), format_frame("f{:03}.ppm", 7));
+        assert_eq!(Some("f  7.ppm".to_string()), format_frame("f{:3}.ppm", 7));
+        assert_eq!(Some("f1234".to_string()), format_frame("f{:02}", 1234));
+        assert_eq!(None, format_frame("frame.ppm", 7));
+        assert_eq!(None, format_frame("f{:x}.ppm", 7));
+    }
+
+    #[test]
+    fn should_write_numbered_frames_and_call_sink() {
+        let directory = std::env::temp_dir().join(format!("frames-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let seen = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&seen);
+        let mut writer = FrameWriter::new(&directory, "frame_{:02}.ppm").with_sink(
+            move |frame, canvas: &Canvas| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((frame, canvas.pixel_at(0, 0)));
+                Ok(())
+            },
+        );
+        let mut c = Canvas::new(2, 2);
+
+        assert_eq!(0, writer.write(&c).unwrap());
+        c.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        assert_eq!(1, writer.write(&c).unwrap());
+
+        let written = std::fs::read_to_string(directory.join("frame_01.ppm")).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(c.to_ppm(), written);
+        assert_eq!(
+            vec![(0, Color::default()), (1, Color::new(1.0, 0.0, 0.0))],
+            *seen.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_templates_without_placeholder() {
+        let mut writer = FrameWriter::new(std::env::temp_dir(), "frame.ppm");
+        let error = writer.write(&Canvas::new(1, 1)).unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        assert_eq!(0, writer.next);
+    }
+}
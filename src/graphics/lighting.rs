@@ -1,14 +1,49 @@
 use crate::graphics::Color;
 use crate::math::Tuple;
+use crate::scene::SceneScale;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::f64::consts::PI;
 
 /// Representation of a point light - a light source with no size
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Light {
     /// Position of the point light
     pub position: Tuple,
     /// Brightness of the light
     pub intensity: Color,
-    _private: (),
+    /// Fraction of the light passing through occluders in each channel,
+    /// black for fully opaque shadows
+    pub shadow_color: Color,
+    /// Radius around the position from which shadow rays are cast, softening
+    /// shadow edges, `0.0` for hard shadows
+    pub softness: f64,
+    /// Number of shadow rays cast when the light has a softness
+    pub shadow_samples: usize,
+    /// Name of the group the light belongs to, which objects can opt in to
+    /// or out of through their `LightLinking`
+    pub group: Option<String>,
+    /// Scale of the scene when the brightness of the light falls off with
+    /// the square of the distance in meters, `None` for a constant
+    /// brightness
+    pub falloff: Option<SceneScale>,
+}
+
+/// Selection of the lights illuminating an object, based on light groups
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LightLinking {
+    /// Illuminated by every light
+    #[default]
+    All,
+    /// Illuminated only by lights in one of the named groups
+    Include(Vec<String>),
+    /// Illuminated by every light except those in one of the named groups
+    Exclude(Vec<String>),
 }
 
 impl Light {
@@ -22,9 +57,96 @@ impl Light {
         Light {
             position,
             intensity,
-            _private: (),
+            shadow_color: Color::new(0.0, 0.0, 0.0),
+            softness: 0.0,
+            shadow_samples: 16,
+            group: None,
+            falloff: None,
+        }
+    }
+
+    /// Creates a new photometric point light given its luminous intensity,
+    /// whose brightness falls off with the inverse square of the distance
+    /// measured in meters
+    ///
+    /// The intensity is divided by pi so that a white, fully diffuse surface
+    /// facing the light reflects its luminance in candela per square meter,
+    /// ready to be exposed by a camera `Exposure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - position of the point light
+    /// * `color` - color of the light, white for an unfiltered light
+    /// * `candela` - luminous intensity of the light
+    pub fn from_candela(position: Tuple, color: Color, candela: f64) -> Light {
+        Light {
+            falloff: Some(SceneScale::METERS),
+            ..Light::new(position, color * (candela / PI))
+        }
+    }
+
+    /// Creates a new photometric point light given its luminous flux, as
+    /// printed on light bulbs, emitted evenly in every direction
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - position of the point light
+    /// * `color` - color of the light, white for an unfiltered light
+    /// * `lumens` - luminous flux of the light
+    pub fn from_lumens(position: Tuple, color: Color, lumens: f64) -> Light {
+        Light::from_candela(position, color, lumens / (4.0 * PI))
+    }
+
+    /// Returns the brightness of the light arriving at the given point
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - illuminated point in world space
+    pub fn intensity_at(&self, point: Tuple) -> Color {
+        match self.falloff {
+            Some(scale) => self.intensity * scale.falloff((self.position - point).magnitude()),
+            None => self.intensity,
         }
     }
+
+    /// Returns the light assigned to the named group
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - name of the light group
+    pub fn in_group(mut self, group: &str) -> Light {
+        self.group = Some(group.to_string());
+        self
+    }
+}
+
+impl LightLinking {
+    /// Returns true if the given light illuminates objects linked this way
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - light to test
+    pub fn is_lit_by(&self, light: &Light) -> bool {
+        let in_any = |groups: &[String]| {
+            light
+                .group
+                .as_ref()
+                .is_some_and(|group| groups.contains(group))
+        };
+
+        match self {
+            LightLinking::All => true,
+            LightLinking::Include(groups) => in_any(groups),
+            LightLinking::Exclude(groups) => !in_any(groups),
+        }
+    }
+}
+
+impl Default for Light {
+    /// Returns a white point light positioned at the origin
+    fn default() -> Light {
+        Light::new(Tuple::point(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0))
+    }
 }
 
 #[cfg(test)]
@@ -41,4 +163,48 @@ mod tests {
         assert_eq!(intensity, light.intensity);
         assert_eq!(position, light.position);
     }
+
+    #[test]
+    fn default_should_be_white_light_at_origin() {
+        let light = Light::default();
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), light.intensity);
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), light.position);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), light.shadow_color);
+        assert_eq!(0.0, light.softness);
+        assert_eq!(None, light.group);
+        assert_eq!(None, light.falloff);
+    }
+
+    #[test]
+    fn photometric_lights_should_fall_off_with_distance() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let light = Light::from_lumens(Tuple::point(0.0, 0.0, 0.0), white, 4.0 * PI * PI);
+
+        assert_eq!(Some(SceneScale::METERS), light.falloff);
+        assert_eq!(white, light.intensity_at(Tuple::point(0.0, 1.0, 0.0)));
+        assert_eq!(
+            white * 0.25,
+            light.intensity_at(Tuple::point(0.0, 2.0, 0.0))
+        );
+        assert_eq!(
+            white,
+            Light::default().intensity_at(Tuple::point(0.0, 9.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn linking_should_select_lights_by_group() {
+        let plain = Light::default();
+        let rim = Light::default().in_group("rim");
+        let include = LightLinking::Include(vec!["rim".to_string()]);
+        let exclude = LightLinking::Exclude(vec!["rim".to_string()]);
+
+        assert!(LightLinking::All.is_lit_by(&plain));
+        assert!(LightLinking::All.is_lit_by(&rim));
+        assert!(!include.is_lit_by(&plain));
+        assert!(include.is_lit_by(&rim));
+        assert!(exclude.is_lit_by(&plain));
+        assert!(!exclude.is_lit_by(&rim));
+    }
 }
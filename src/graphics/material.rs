@@ -1,19 +1,41 @@
-use crate::graphics::{Color, Light};
-use crate::math::{reflect, Tuple};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+pub mod presets;
+
+use crate::graphics::{Color, Light, Pattern};
+use crate::math::{reflect, Shape, Tuple};
 
 /// Representation of a surface material
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Material {
-    /// Surface color
+    /// Surface color, used where no pattern is set
     pub color: Color,
+    /// Pattern overriding the surface color
+    pub pattern: Option<Pattern>,
     /// Ambient reflection, i.e. background lighting
-    ambient: f64,
+    pub ambient: f64,
     /// Diffuse reflection - light reflected from a matte surface
-    diffuse: f64,
+    pub diffuse: f64,
     /// Specular reflection - reflection of the light source itself
-    specular: f64,
-    /// The higher this valye, the smaller and tighter the specular highlight
-    shininess: f64,
+    pub specular: f64,
+    /// The higher this value, the smaller and tighter the specular highlight
+    pub shininess: f64,
+    /// Degree to which light bends when entering or leaving the material
+    pub refractive_index: f64,
+    /// Light emitted by the surface itself, added regardless of any light
+    /// source
+    pub emission: Color,
+    /// Fraction of light passing through the surface, letting transparent
+    /// objects such as glass cast lighter shadows
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transparency: f64,
+    /// Fraction of the light seen in the mirror direction that the surface
+    /// reflects, from `0.0` for a matte surface to `1.0` for a perfect
+    /// mirror
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reflective: f64,
 }
 
 impl Material {
@@ -21,10 +43,46 @@ impl Material {
     pub fn new() -> Material {
         Material {
             color: Color::new(1.0, 1.0, 1.0),
+            pattern: None,
             ambient: 0.1,
             diffuse: 0.9,
             specular: 0.9,
             shininess: 200.0,
+            refractive_index: 1.0,
+            emission: Color::new(0.0, 0.0, 0.0),
+            transparency: 0.0,
+            reflective: 0.0,
+        }
+    }
+
+    /// Creates a new material glowing like a black body at the given
+    /// temperature, such as a light bulb filament or lava
+    ///
+    /// # Arguments
+    ///
+    /// * `kelvin` - temperature of the surface in kelvin
+    /// * `strength` - brightness of the emitted light
+    pub fn incandescent(kelvin: f64, strength: f64) -> Material {
+        let color = Color::blackbody(kelvin);
+
+        Material {
+            color,
+            emission: color * strength,
+            ..Material::new()
+        }
+    }
+
+    /// Returns the surface color of the material at the given point on the
+    /// object, taken from the pattern if one is set
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - object the material is applied to
+    /// * `position` - point in world space
+    pub fn color_at(&self, object: &dyn Shape, position: Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.pattern_at_shape(object, position),
+            None => self.color,
         }
     }
 
@@ -33,12 +91,54 @@ impl Material {
     ///
     /// # Arguments
     ///
+    /// * `object` - object the material is applied to
     /// * `light` - point light illuminating the object
     /// * `position` - position to shade
     /// * `eye` - observing point
     /// * `normal` - normal vector
-    pub fn lighting(&self, light: Light, position: Tuple, eye: Tuple, normal: Tuple) -> Color {
-        let color = self.color * light.intensity;
+    /// * `in_shadow` - whether the light is blocked from reaching `position`
+    pub fn lighting(
+        &self,
+        object: &dyn Shape,
+        light: &Light,
+        position: Tuple,
+        eye: Tuple,
+        normal: Tuple,
+        in_shadow: bool,
+    ) -> Color {
+        let visibility = if in_shadow {
+            light.shadow_color
+        } else {
+            Color::new(1.0, 1.0, 1.0)
+        };
+
+        self.lighting_with_visibility(object, light, position, eye, normal, visibility)
+    }
+
+    /// Shades the material like `lighting`, with the diffuse and specular
+    /// terms scaled per channel by the fraction of the light reaching the
+    /// position
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - object the material is applied to
+    /// * `light` - point light illuminating the object
+    /// * `position` - position to shade
+    /// * `eye` - observing point
+    /// * `normal` - normal vector
+    /// * `visibility` - fraction of the light reaching `position` in each
+    ///   channel, white when unoccluded
+    pub fn lighting_with_visibility(
+        &self,
+        object: &dyn Shape,
+        light: &Light,
+        position: Tuple,
+        eye: Tuple,
+        normal: Tuple,
+        visibility: Color,
+    ) -> Color {
+        let intensity = light.intensity_at(position);
+        let color = self.color_at(object, position) * intensity;
         let light_v = (light.position - position).normalize();
         let dot = Tuple::dot(&light_v, &normal);
         let ambient = color * self.ambient;
@@ -54,17 +154,24 @@ impl Material {
                 specular = Color::new(0.0, 0.0, 0.0);
             } else {
                 let factor = dot_eye.powf(self.shininess);
-                specular = light.intensity * self.specular * factor;
+                specular = intensity * self.specular * factor;
             }
         }
 
-        ambient + diffuse + specular
+        ambient + (diffuse + specular) * visibility
+    }
+}
+
+impl Default for Material {
+    fn default() -> Material {
+        Material::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::Sphere;
 
     #[test]
     fn should_contain_correct_data() {
@@ -75,6 +182,30 @@ mod tests {
         assert_eq!(0.9, m.diffuse);
         assert_eq!(0.9, m.specular);
         assert_eq!(200.0, m.shininess);
+        assert_eq!(1.0, m.refractive_index);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), m.emission);
+        assert_eq!(0.0, m.transparency);
+        assert_eq!(0.0, m.reflective);
+    }
+
+    #[test]
+    fn incandescent_should_emit_blackbody_color() {
+        let m = Material::incandescent(1500.0, 2.0);
+
+        assert_eq!(Color::blackbody(1500.0), m.color);
+        assert_eq!(Color::blackbody(1500.0) * 2.0, m.emission);
+    }
+
+    #[test]
+    fn should_support_struct_update_from_default() {
+        let m = Material {
+            shininess: 10.0,
+            ..Default::default()
+        };
+
+        assert_eq!(Material::new().color, m.color);
+        assert_eq!(Material::new().ambient, m.ambient);
+        assert_eq!(10.0, m.shininess);
     }
 
     #[test]
@@ -84,7 +215,7 @@ mod tests {
         let eye = Tuple::vector(0.0, 0.0, -1.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let lighting = m.lighting(light, position, eye, normal);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, false);
 
         assert_eq!(Color::new(1.9, 1.9, 1.9), lighting);
     }
@@ -96,7 +227,7 @@ mod tests {
         let eye = Tuple::point(0.0, 2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let lighting = m.lighting(light, position, eye, normal);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, false);
 
         assert_eq!(Color::new(1.0, 1.0, 1.0), lighting);
     }
@@ -108,7 +239,7 @@ mod tests {
         let eye = Tuple::vector(0.0, 0.0, -1.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = Light::new(Tuple::point(0.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let lighting = m.lighting(light, position, eye, normal);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, false);
 
         assert_eq!(Color::new(0.7364, 0.7364, 0.7364), lighting);
     }
@@ -120,7 +251,7 @@ mod tests {
         let eye = Tuple::point(0.0, -2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = Light::new(Tuple::point(0.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let lighting = m.lighting(light, position, eye, normal);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, false);
 
         assert_eq!(Color::new(1.6364, 1.6364, 1.6364), lighting);
     }
@@ -132,8 +263,70 @@ mod tests {
         let eye = Tuple::vector(0.0, 0.0, -1.0);
         let normal = Tuple::vector(0.0, 0.0, -1.0);
         let light = Light::new(Tuple::point(0.0, 0.0, 10.0), Color::new(1.0, 1.0, 1.0));
-        let lighting = m.lighting(light, position, eye, normal);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, false);
+
+        assert_eq!(Color::new(0.1, 0.1, 0.1), lighting);
+    }
+
+    #[test]
+    fn should_only_contribute_ambient_light_when_in_shadow() {
+        let m = Material::new();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, true);
 
         assert_eq!(Color::new(0.1, 0.1, 0.1), lighting);
     }
+
+    #[test]
+    fn should_use_pattern_color_when_lighting() {
+        let m = Material {
+            pattern: Some(Pattern::stripe(
+                Color::new(1.0, 1.0, 1.0),
+                Color::new(0.0, 0.0, 0.0),
+            )),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Default::default()
+        };
+        let object = Sphere::new();
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let c1 = m.lighting(
+            &object,
+            &light,
+            Tuple::point(0.9, 0.0, 0.0),
+            eye,
+            normal,
+            false,
+        );
+        let c2 = m.lighting(
+            &object,
+            &light,
+            Tuple::point(1.1, 0.0, 0.0),
+            eye,
+            normal,
+            false,
+        );
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), c1);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), c2);
+    }
+
+    #[test]
+    fn should_tint_light_in_shadow_with_shadow_color() {
+        let m = Material::new();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let mut light = Light::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        light.shadow_color = Color::new(0.5, 0.0, 0.0);
+        let lighting = m.lighting(&Sphere::new(), &light, position, eye, normal, true);
+
+        assert_eq!(Color::new(0.1 + 1.8 * 0.5, 0.1, 0.1), lighting);
+    }
 }
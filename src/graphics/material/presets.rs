@@ -0,0 +1,106 @@
+//! Material presets
+//!
+//! Ready-made materials built from the crate's material parameters, giving
+//! pleasant results without tuning every coefficient by hand.
+
+use crate::graphics::{Color, Material};
+
+/// Returns a bright, mirror-like metal with a tight white highlight
+pub fn chrome() -> Material {
+    Material {
+        color: Color::new(0.55, 0.56, 0.58),
+        ambient: 0.05,
+        diffuse: 0.3,
+        specular: 1.0,
+        shininess: 300.0,
+        ..Material::new()
+    }
+}
+
+/// Returns a warm yellow metal with a strong highlight
+pub fn gold() -> Material {
+    Material {
+        color: Color::new(1.0, 0.71, 0.29),
+        ambient: 0.1,
+        diffuse: 0.6,
+        specular: 0.9,
+        shininess: 150.0,
+        ..Material::new()
+    }
+}
+
+/// Returns a dark, soft surface with a broad and faint highlight
+pub fn rubber() -> Material {
+    Material {
+        color: Color::new(0.08, 0.08, 0.08),
+        ambient: 0.05,
+        diffuse: 0.7,
+        specular: 0.1,
+        shininess: 10.0,
+        ..Material::new()
+    }
+}
+
+/// Returns a pale, slightly blue glass with a diffused surface
+pub fn frosted_glass() -> Material {
+    Material {
+        color: Color::new(0.9, 0.95, 1.0),
+        ambient: 0.1,
+        diffuse: 0.4,
+        specular: 0.6,
+        shininess: 40.0,
+        refractive_index: 1.5,
+        ..Material::new()
+    }
+}
+
+/// Returns a deep green stone with a polished highlight
+pub fn jade() -> Material {
+    Material {
+        color: Color::new(0.33, 0.6, 0.42),
+        ambient: 0.15,
+        diffuse: 0.7,
+        specular: 0.4,
+        shininess: 80.0,
+        refractive_index: 1.66,
+        ..Material::new()
+    }
+}
+
+/// Returns an unglazed terracotta surface without any highlight
+pub fn matte_clay() -> Material {
+    Material {
+        color: Color::new(0.72, 0.45, 0.33),
+        ambient: 0.1,
+        diffuse: 0.9,
+        specular: 0.0,
+        shininess: 1.0,
+        ..Material::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_should_be_distinct_and_plausible() {
+        let presets = [
+            chrome(),
+            gold(),
+            rubber(),
+            frosted_glass(),
+            jade(),
+            matte_clay(),
+        ];
+
+        for (i, a) in presets.iter().enumerate() {
+            assert!(a.ambient + a.diffuse <= 1.0 + f64::EPSILON);
+            assert!(a.refractive_index >= 1.0);
+
+            for b in &presets[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+}
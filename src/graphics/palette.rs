@@ -0,0 +1,239 @@
+use super::{Canvas, Color, OrderedColor};
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+/// Limited set of colors an image is reduced to, as needed for indexed
+/// formats such as GIF and for stylized posterized output
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Canvas, Color};
+///
+/// let mut canvas = Canvas::new(4, 1);
+/// canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+/// canvas.write_pixel(1, 0, Color::new(0.9, 0.0, 0.0));
+/// canvas.write_pixel(2, 0, Color::new(0.0, 0.0, 1.0));
+/// canvas.write_pixel(3, 0, Color::new(0.0, 0.0, 0.9));
+///
+/// let palette = canvas.palette(2);
+/// let quantized = canvas.quantize(2);
+///
+/// assert_eq!(2, palette.colors.len());
+/// assert_eq!(Color::new(0.95, 0.0, 0.0), quantized.pixel_at(0, 0));
+/// assert_eq!(Color::new(0.0, 0.0, 0.95), quantized.pixel_at(3, 0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    /// Colors of the palette
+    pub colors: Vec<Color>,
+}
+
+impl Palette {
+    /// Creates a new palette from the given colors
+    pub fn new(colors: Vec<Color>) -> Palette {
+        Palette { colors }
+    }
+
+    /// Returns a palette of at most `size` colors representing the given
+    /// colors, found by median cut
+    ///
+    /// The colors are recursively split at the median of the component with
+    /// the widest range, and every resulting group is represented by its
+    /// mean color.
+    ///
+    /// # Arguments
+    ///
+    /// * `colors` - colors to represent, typically every pixel of an image
+    /// * `size` - largest number of colors of the palette
+    pub fn median_cut(colors: &[Color], size: usize) -> Palette {
+        if colors.is_empty() || size == 0 {
+            return Palette::new(vec![]);
+        }
+
+        let mut boxes = vec![colors.to_vec()];
+
+        while boxes.len() < size {
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, colors)| colors.len() > 1)
+                .map(|(i, colors)| (i, widest_channel(colors)))
+                .filter(|(_, (_, range))| *range > 0.0)
+                .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+
+            let Some((index, (channel, _))) = widest else {
+                break;
+            };
+
+            let mut group = boxes.swap_remove(index);
+            group.sort_by(|a, b| component(a, channel).total_cmp(&component(b, channel)));
+            let upper = group.split_off(group.len() / 2);
+            boxes.push(group);
+            boxes.push(upper);
+        }
+
+        Palette::new(boxes.iter().map(|colors| mean(colors)).collect())
+    }
+
+    /// Returns the index of the palette color closest to the given color,
+    /// or `None` if the palette is empty
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - color to look up
+    pub fn nearest(&self, color: Color) -> Option<usize> {
+        self.colors
+            .iter()
+            .map(|c| {
+                let d = *c - color;
+                d.r * d.r + d.g * d.g + d.b * d.b
+            })
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the index of the closest palette color for every pixel of the
+    /// canvas, row by row from the top left corner
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - image to index
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty.
+    pub fn indices(&self, canvas: &Canvas) -> Vec<usize> {
+        pixels(canvas)
+            .map(|c| self.nearest(c).expect("palette is empty"))
+            .collect()
+    }
+}
+
+impl Canvas {
+    /// Returns every distinct color of the canvas in ascending order, with
+    /// colors closer than `EPSILON` considered equal
+    pub fn unique_colors(&self) -> Vec<Color> {
+        pixels(self)
+            .map(OrderedColor::from)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(Color::from)
+            .collect()
+    }
+
+    /// Returns a palette of at most `size` colors representing the canvas,
+    /// found by median cut over its pixels
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - largest number of colors of the palette
+    pub fn palette(&self, size: usize) -> Palette {
+        Palette::median_cut(&pixels(self).collect::<Vec<_>>(), size)
+    }
+
+    /// Returns a copy of the canvas with every pixel replaced by the closest
+    /// color of a palette of at most `size` colors
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - largest number of distinct colors of the result
+    pub fn quantize(&self, size: usize) -> Canvas {
+        let palette = self.palette(size);
+        let mut quantized = Canvas::new(self.width, self.height);
+
+        if palette.colors.is_empty() {
+            return quantized;
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = palette.nearest(self.pixel_at(x, y)).unwrap_or_default();
+                quantized.write_pixel(x, y, palette.colors[index]);
+            }
+        }
+
+        quantized
+    }
+}
+
+fn pixels(canvas: &Canvas) -> impl Iterator<Item = Color> + '_ {
+    (0..canvas.height).flat_map(move |y| (0..canvas.width).map(move |x| canvas.pixel_at(x, y)))
+}
+
+fn component(color: &Color, channel: usize) -> f64 {
+    match channel {
+        0 => color.r,
+        1 => color.g,
+        _ => color.b,
+    }
+}
+
+/// Returns the channel with the widest range of values among the colors,
+/// together with that range
+fn widest_channel(colors: &[Color]) -> (usize, f64) {
+    (0..3)
+        .map(|channel| {
+            let (low, high) =
+                colors
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), c| {
+                        let v = component(c, channel);
+                        (low.min(v), high.max(v))
+                    });
+
+            (channel, high - low)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap_or((0, 0.0))
+}
+
+fn mean(colors: &[Color]) -> Color {
+    colors.iter().fold(Color::default(), |sum, c| sum + *c) / colors.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient() -> Canvas {
+        let mut c = Canvas::new(16, 4);
+
+        for y in 0..4 {
+            for x in 0..16 {
+                c.write_pixel(x, y, Color::new(x as f64 / 15.0, y as f64 / 3.0, 0.5));
+            }
+        }
+
+        c
+    }
+
+    #[test]
+    fn median_cut_should_respect_size_and_distinct_colors() {
+        let c = gradient();
+
+        assert_eq!(8, c.palette(8).colors.len());
+        assert_eq!(64, c.unique_colors().len());
+        assert_eq!(64, c.palette(1000).colors.len());
+        assert!(c.palette(0).colors.is_empty());
+        assert!(Canvas::new(0, 0).palette(4).colors.is_empty());
+    }
+
+    #[test]
+    fn quantize_should_limit_distinct_colors() {
+        let q = gradient().quantize(5);
+
+        assert!(q.unique_colors().len() <= 5);
+    }
+
+    #[test]
+    fn indices_should_point_at_nearest_palette_colors() {
+        let palette = Palette::new(vec![Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)]);
+        let mut c = Canvas::new(2, 1);
+        c.write_pixel(0, 0, Color::new(0.9, 0.8, 1.0));
+
+        assert_eq!(vec![1, 0], palette.indices(&c));
+        assert_eq!(None, Palette::new(vec![]).nearest(Color::default()));
+    }
+}
@@ -0,0 +1,462 @@
+use crate::graphics::{Color, CubeFace, TextureMap, UvMapping, UvPattern};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::{Matrix, Shape, Tuple, EPSILON};
+use alloc::boxed::Box;
+
+/// Way a checkers pattern decides which cell a point lies in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CheckerMode {
+    /// Unit cubes in all three dimensions, which shows acne on surfaces lying
+    /// at integer coordinates
+    #[default]
+    Solid,
+    /// Unit cubes with coordinates nudged by a small epsilon before flooring,
+    /// so points a rounding error below an integer land in the same cell as
+    /// the integer itself
+    Nudged,
+    /// Unit squares in the xz plane, ignoring y, which renders floors cleanly
+    Planar,
+}
+
+/// Kind of pattern and the colors it alternates between
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PatternKind {
+    /// Single color everywhere
+    Solid(Color),
+    /// Returns the pattern space point itself as a color, used to test the
+    /// transformations applied to patterns
+    Test,
+    /// Alternates between two colors in unit wide stripes along x
+    Stripe(Color, Color),
+    /// Blends linearly from the first to the second color along each unit
+    /// of x
+    Gradient(Color, Color),
+    /// Alternates between two colors in unit wide rings around the y axis
+    Ring(Color, Color),
+    /// Alternates between two colors in unit cells, chosen by the mode
+    Checkers(Color, Color, CheckerMode),
+    /// Two-dimensional pattern projected onto the surface
+    Texture(TextureMap),
+    /// Separate two-dimensional pattern on each face of a cube centered at
+    /// the origin, indexed in the order of `CubeFace::ALL`
+    CubeMap(Box<[UvPattern; 6]>),
+}
+
+/// Procedural color pattern applied to the surface of an object
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pattern {
+    /// Kind of pattern
+    pub kind: PatternKind,
+    /// Transformation of the pattern relative to the object it is applied to
+    pub transform: Matrix,
+}
+
+impl Pattern {
+    /// Returns a new pattern of the given kind with an identity transform
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - kind of pattern
+    pub fn new(kind: PatternKind) -> Pattern {
+        Pattern {
+            kind,
+            transform: Matrix::identity(4),
+        }
+    }
+
+    /// Returns a new pattern with the same color everywhere
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - color of the pattern
+    pub fn solid(color: Color) -> Pattern {
+        Pattern::new(PatternKind::Solid(color))
+    }
+
+    /// Returns a new test pattern, coloring each point by its coordinates in
+    /// pattern space
+    pub fn test() -> Pattern {
+        Pattern::new(PatternKind::Test)
+    }
+
+    /// Returns a new stripe pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - color of stripes at even x
+    /// * `b` - color of stripes at odd x
+    pub fn stripe(a: Color, b: Color) -> Pattern {
+        Pattern::new(PatternKind::Stripe(a, b))
+    }
+
+    /// Returns a new gradient pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - color at the start of each unit
+    /// * `b` - color approached at the end of each unit
+    pub fn gradient(a: Color, b: Color) -> Pattern {
+        Pattern::new(PatternKind::Gradient(a, b))
+    }
+
+    /// Returns a new ring pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - color of rings at even distances
+    /// * `b` - color of rings at odd distances
+    pub fn ring(a: Color, b: Color) -> Pattern {
+        Pattern::new(PatternKind::Ring(a, b))
+    }
+
+    /// Returns a new checkers pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - color of cubes with an even coordinate sum
+    /// * `b` - color of cubes with an odd coordinate sum
+    pub fn checkers(a: Color, b: Color) -> Pattern {
+        Pattern::checkers_with_mode(a, b, CheckerMode::Solid)
+    }
+
+    /// Returns a new checkers pattern using the given mode to find the cell
+    /// a point lies in
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - color of cells with an even coordinate sum
+    /// * `b` - color of cells with an odd coordinate sum
+    /// * `mode` - way of finding the cell of a point
+    pub fn checkers_with_mode(a: Color, b: Color, mode: CheckerMode) -> Pattern {
+        Pattern::new(PatternKind::Checkers(a, b, mode))
+    }
+
+    /// Returns a new pattern projecting a two-dimensional pattern onto the
+    /// surface, repeating it without scale or offset
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - two-dimensional pattern to project
+    /// * `mapping` - projection from points to texture coordinates
+    pub fn texture(pattern: UvPattern, mapping: UvMapping) -> Pattern {
+        Pattern::new(PatternKind::Texture(TextureMap::new(pattern, mapping)))
+    }
+
+    /// Returns a new cube map pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `faces` - pattern of each face, in the order of `CubeFace::ALL`:
+    ///   left, front, right, back, up and down
+    pub fn cube_map(faces: [UvPattern; 6]) -> Pattern {
+        Pattern::new(PatternKind::CubeMap(Box::new(faces)))
+    }
+
+    /// Sets the transformation matrix of the pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - transformation matrix to set for pattern
+    pub fn transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Returns the color of the pattern at the given point in pattern space
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn pattern_at(&self, point: Tuple) -> Color {
+        match &self.kind {
+            PatternKind::Solid(color) => *color,
+            PatternKind::Test => Color::new(point.x, point.y, point.z),
+            PatternKind::Stripe(a, b) => {
+                if is_even(point.x) {
+                    *a
+                } else {
+                    *b
+                }
+            }
+            PatternKind::Gradient(a, b) => *a + (*b - *a) * (point.x - point.x.floor()),
+            PatternKind::Ring(a, b) => {
+                if is_even((point.x * point.x + point.z * point.z).sqrt()) {
+                    *a
+                } else {
+                    *b
+                }
+            }
+            PatternKind::Checkers(a, b, mode) => {
+                let sum = match mode {
+                    CheckerMode::Solid => point.x.floor() + point.y.floor() + point.z.floor(),
+                    CheckerMode::Nudged => {
+                        (point.x + EPSILON).floor()
+                            + (point.y + EPSILON).floor()
+                            + (point.z + EPSILON).floor()
+                    }
+                    CheckerMode::Planar => point.x.floor() + point.z.floor(),
+                };
+
+                if is_even(sum) {
+                    *a
+                } else {
+                    *b
+                }
+            }
+            PatternKind::Texture(texture) => texture.color_at(point),
+            PatternKind::CubeMap(faces) => {
+                let face = CubeFace::of(point);
+                let uv = face.uv(point);
+
+                faces[face.index()].uv_at(uv.x, uv.y)
+            }
+        }
+    }
+
+    /// Returns the color of the pattern at the given point on the object,
+    /// converting the point from world space to object space and then to
+    /// pattern space
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - object the pattern is applied to
+    /// * `world_point` - point in world space
+    pub fn pattern_at_shape(&self, object: &dyn Shape, world_point: Tuple) -> Color {
+        let object_point = &object.transformation().inverse() * &world_point;
+        let pattern_point = &self.transform.inverse() * &object_point;
+
+        self.pattern_at(pattern_point)
+    }
+}
+
+/// Returns the color of the given pattern at the given point on the object
+///
+/// # Arguments
+///
+/// * `pattern` - pattern to evaluate
+/// * `object` - object the pattern is applied to
+/// * `world_point` - point in world space
+pub fn pattern_at_shape(pattern: &Pattern, object: &dyn Shape, world_point: Tuple) -> Color {
+    pattern.pattern_at_shape(object, world_point)
+}
+
+/// Returns true if the integer part of the value is even
+fn is_even(value: f64) -> bool {
+    value.floor().rem_euclid(2.0) == 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{scaling, translation, Sphere};
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    #[test]
+    fn stripe_should_alternate_in_x_only() {
+        let p = Pattern::stripe(white(), black());
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.0, 1.0, 2.0)));
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.9, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(1.0, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(-0.1, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(-1.0, 0.0, 0.0)));
+        assert_eq!(white(), p.pattern_at(Tuple::point(-1.1, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn gradient_should_interpolate_between_colors() {
+        let p = Pattern::gradient(white(), black());
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.0, 0.0, 0.0)));
+        assert_eq!(
+            Color::new(0.75, 0.75, 0.75),
+            p.pattern_at(Tuple::point(0.25, 0.0, 0.0))
+        );
+        assert_eq!(
+            Color::new(0.25, 0.25, 0.25),
+            p.pattern_at(Tuple::point(0.75, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn ring_should_extend_in_x_and_z() {
+        let p = Pattern::ring(white(), black());
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.0, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(1.0, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(0.0, 0.0, 1.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(0.708, 0.0, 0.708)));
+    }
+
+    #[test]
+    fn checkers_should_repeat_in_every_dimension() {
+        let p = Pattern::checkers(white(), black());
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.99, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(1.01, 0.0, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(0.0, 1.01, 0.0)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(0.0, 0.0, 1.01)));
+    }
+
+    #[test]
+    fn should_apply_object_transformation() {
+        let mut object = Sphere::new();
+        object.transform(scaling(2.0, 2.0, 2.0));
+        let p = Pattern::stripe(white(), black());
+
+        assert_eq!(
+            white(),
+            pattern_at_shape(&p, &object, Tuple::point(1.5, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn should_apply_pattern_transformation() {
+        let object = Sphere::new();
+        let mut p = Pattern::stripe(white(), black());
+        p.transform(scaling(2.0, 2.0, 2.0));
+
+        assert_eq!(
+            white(),
+            p.pattern_at_shape(&object, Tuple::point(1.5, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn should_apply_both_object_and_pattern_transformation() {
+        let mut object = Sphere::new();
+        object.transform(scaling(2.0, 2.0, 2.0));
+        let mut p = Pattern::stripe(white(), black());
+        p.transform(translation(0.5, 0.0, 0.0));
+
+        assert_eq!(
+            white(),
+            p.pattern_at_shape(&object, Tuple::point(2.5, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn checker_modes_should_avoid_acne_near_integer_heights() {
+        let below = Tuple::point(0.5, -0.000001, 0.5);
+        let above = Tuple::point(0.5, 0.000001, 0.5);
+
+        let solid = Pattern::checkers(white(), black());
+        assert_ne!(solid.pattern_at(below), solid.pattern_at(above));
+
+        for mode in [CheckerMode::Nudged, CheckerMode::Planar] {
+            let p = Pattern::checkers_with_mode(white(), black(), mode);
+
+            assert_eq!(white(), p.pattern_at(below));
+            assert_eq!(white(), p.pattern_at(above));
+        }
+    }
+
+    #[test]
+    fn planar_checkers_should_repeat_in_x_and_z() {
+        let p = Pattern::checkers_with_mode(white(), black(), CheckerMode::Planar);
+
+        assert_eq!(black(), p.pattern_at(Tuple::point(1.5, 0.0, 0.5)));
+        assert_eq!(black(), p.pattern_at(Tuple::point(0.5, 0.0, 1.5)));
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.5, 7.0, 0.5)));
+    }
+
+    #[test]
+    fn solid_should_be_constant() {
+        let p = Pattern::solid(white());
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.0, 0.0, 0.0)));
+        assert_eq!(white(), p.pattern_at(Tuple::point(-3.5, 2.1, 9.9)));
+    }
+
+    #[test]
+    fn test_pattern_should_expose_pattern_space_point() {
+        let mut object = Sphere::new();
+        object.transform(scaling(2.0, 2.0, 2.0));
+        let mut p = Pattern::test();
+        p.transform(translation(0.5, 1.0, 1.5));
+
+        assert_eq!(
+            Color::new(0.75, 0.5, 0.25),
+            p.pattern_at_shape(&object, Tuple::point(2.5, 3.0, 3.5))
+        );
+    }
+
+    #[test]
+    fn texture_should_project_uv_pattern() {
+        let checkers = UvPattern::Checkers {
+            width: 16.0,
+            height: 8.0,
+            a: black(),
+            b: white(),
+        };
+        let p = Pattern::texture(checkers, UvMapping::Spherical);
+
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.4315, 0.4670, 0.7719)));
+        assert_eq!(
+            black(),
+            p.pattern_at(Tuple::point(-0.9654, 0.2552, -0.0534))
+        );
+        assert_eq!(white(), p.pattern_at(Tuple::point(0.1039, 0.7090, 0.6975)));
+        assert_eq!(
+            black(),
+            p.pattern_at(Tuple::point(-0.4986, -0.7856, -0.3663))
+        );
+    }
+
+    #[test]
+    fn cube_map_should_use_pattern_of_each_face() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let yellow = Color::new(1.0, 1.0, 0.0);
+        let brown = Color::new(1.0, 0.5, 0.0);
+        let green = Color::new(0.0, 1.0, 0.0);
+        let cyan = Color::new(0.0, 1.0, 1.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let purple = Color::new(1.0, 0.0, 1.0);
+        let align = |main, ul, ur, bl, br| UvPattern::AlignCheck {
+            main,
+            ul,
+            ur,
+            bl,
+            br,
+        };
+        let p = Pattern::cube_map([
+            align(yellow, cyan, red, blue, brown),
+            align(cyan, red, yellow, brown, green),
+            align(red, yellow, purple, green, white()),
+            align(green, purple, cyan, white(), blue),
+            align(brown, cyan, purple, red, yellow),
+            align(purple, brown, green, blue, white()),
+        ]);
+        let reference = [
+            (Tuple::point(-1.0, 0.0, 0.0), yellow),
+            (Tuple::point(-1.0, 0.9, -0.9), cyan),
+            (Tuple::point(-1.0, 0.9, 0.9), red),
+            (Tuple::point(0.0, 0.0, 1.0), cyan),
+            (Tuple::point(-0.9, 0.9, 1.0), red),
+            (Tuple::point(1.0, 0.0, 0.0), red),
+            (Tuple::point(1.0, 0.9, 0.9), yellow),
+            (Tuple::point(0.0, 0.0, -1.0), green),
+            (Tuple::point(0.9, 0.9, -1.0), purple),
+            (Tuple::point(0.0, 1.0, 0.0), brown),
+            (Tuple::point(-0.9, 1.0, -0.9), cyan),
+            (Tuple::point(0.0, -1.0, 0.0), purple),
+            (Tuple::point(-0.9, -1.0, 0.9), brown),
+        ];
+
+        for (point, color) in reference {
+            assert_eq!(color, p.pattern_at(point));
+        }
+    }
+}
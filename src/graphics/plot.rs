@@ -0,0 +1,191 @@
+use crate::graphics::{Canvas, Color};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::{Point2, Vec2};
+
+/// Mapping from two-dimensional world coordinates to canvas pixels, used to
+/// draw points and lines onto a canvas
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Canvas, Color, Plot};
+///
+/// let mut c = Canvas::new(10, 10);
+/// let plot = Plot::new();
+///
+/// plot.polyline(&mut c, [(0.0, 0.0), (9.0, 9.0)], Color::new(1.0, 0.0, 0.0));
+///
+/// assert_eq!(Color::new(1.0, 0.0, 0.0), c.pixel_at(0, 9));
+/// assert_eq!(Color::new(1.0, 0.0, 0.0), c.pixel_at(9, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plot {
+    /// Canvas position, in pixels, of the world origin
+    pub origin: Point2,
+    /// Number of pixels per world unit along each axis
+    pub scale: Vec2,
+    /// Whether world y grows upwards, i.e. is measured from the bottom of
+    /// the canvas
+    pub flip_y: bool,
+}
+
+impl Plot {
+    /// Returns a new plot with the world origin at the bottom left pixel, one
+    /// pixel per world unit and y growing upwards
+    pub fn new() -> Plot {
+        Plot {
+            origin: Point2::new(0.0, 0.0),
+            scale: Vec2::new(1.0, 1.0),
+            flip_y: true,
+        }
+    }
+
+    /// Returns the pixel the given world coordinates fall into, or `None` if
+    /// they lie outside of the canvas
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - canvas to map onto
+    /// * `point` - world coordinates
+    pub fn pixel_for(&self, canvas: &Canvas, point: Point2) -> Option<(usize, usize)> {
+        let projected = self.project(canvas, point);
+        let (px, py) = (projected.x.round(), projected.y.round());
+
+        if px < 0.0 || py < 0.0 || px >= canvas.width as f64 || py >= canvas.height as f64 {
+            None
+        } else {
+            Some((px as usize, py as usize))
+        }
+    }
+
+    /// Colors the pixel at the given world coordinates, returning false if
+    /// they lie outside of the canvas
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - canvas to draw onto
+    /// * `point` - world coordinates
+    /// * `color` - color to draw with
+    pub fn point(&self, canvas: &mut Canvas, point: Point2, color: Color) -> bool {
+        match self.pixel_for(canvas, point) {
+            Some((px, py)) => {
+                canvas.write_pixel(px, py, color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Draws straight lines between consecutive world coordinates, skipping
+    /// the parts which fall outside of the canvas
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - canvas to draw onto
+    /// * `points` - world coordinates to connect, in order
+    /// * `color` - color to draw with
+    pub fn polyline<I>(&self, canvas: &mut Canvas, points: I, color: Color)
+    where
+        I: IntoIterator,
+        I::Item: Into<Point2>,
+    {
+        let mut previous: Option<Point2> = None;
+
+        for point in points {
+            let current = self.project(canvas, point.into());
+
+            match previous {
+                Some(start) => line(canvas, start, current, color),
+                None => line(canvas, current, current, color),
+            }
+
+            previous = Some(current);
+        }
+    }
+
+    fn project(&self, canvas: &Canvas, point: Point2) -> Point2 {
+        let px = self.origin.x + point.x * self.scale.x;
+        let py = self.origin.y + point.y * self.scale.y;
+
+        if self.flip_y {
+            Point2::new(px, canvas.height as f64 - 1.0 - py)
+        } else {
+            Point2::new(px, py)
+        }
+    }
+}
+
+impl Default for Plot {
+    fn default() -> Plot {
+        Plot::new()
+    }
+}
+
+/// Draws a line between two positions in pixel space, one pixel per step
+/// along the longer axis, skipping pixels outside of the canvas
+pub(crate) fn line(canvas: &mut Canvas, start: Point2, end: Point2, color: Color) {
+    let delta = end - start;
+    let steps = delta.x.abs().max(delta.y.abs()).ceil().max(1.0);
+
+    for i in 0..=steps as usize {
+        let t = i as f64 / steps;
+        let step = start + delta * t;
+        let (px, py) = (step.x.round(), step.y.round());
+
+        if px >= 0.0 && py >= 0.0 && px < canvas.width as f64 && py < canvas.height as f64 {
+            canvas.write_pixel(px as usize, py as usize, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_world_coordinates_to_pixels() {
+        let c = Canvas::new(100, 50);
+        let mut plot = Plot::new();
+
+        assert_eq!(Some((0, 49)), plot.pixel_for(&c, Point2::new(0.0, 0.0)));
+        assert_eq!(Some((10, 39)), plot.pixel_for(&c, Point2::new(10.0, 10.0)));
+        assert_eq!(None, plot.pixel_for(&c, Point2::new(-1.0, 0.0)));
+        assert_eq!(None, plot.pixel_for(&c, Point2::new(0.0, 50.0)));
+
+        plot.origin = Point2::new(50.0, 25.0);
+        plot.scale = Vec2::new(2.0, 2.0);
+        plot.flip_y = false;
+
+        assert_eq!(Some((54, 19)), plot.pixel_for(&c, Point2::new(2.0, -3.0)));
+    }
+
+    #[test]
+    fn point_should_ignore_coordinates_off_canvas() {
+        let mut c = Canvas::new(5, 5);
+        let red = Color::new(1.0, 0.0, 0.0);
+        let plot = Plot::new();
+
+        assert!(plot.point(&mut c, Point2::new(1.0, 1.0), red));
+        assert!(!plot.point(&mut c, Point2::new(100.0, -3.0), red));
+        assert_eq!(red, c.pixel_at(1, 3));
+    }
+
+    #[test]
+    fn polyline_should_connect_points_and_clip_at_edges() {
+        let mut c = Canvas::new(5, 5);
+        let red = Color::new(1.0, 0.0, 0.0);
+        let plot = Plot::new();
+
+        plot.polyline(&mut c, [(-2.0, 2.0), (2.0, 2.0), (2.0, 10.0)], red);
+
+        for x in 0..=2 {
+            assert_eq!(red, c.pixel_at(x, 2));
+        }
+        for y in 0..=2 {
+            assert_eq!(red, c.pixel_at(2, y));
+        }
+        assert_eq!(Color::default(), c.pixel_at(3, 2));
+        assert_eq!(Color::default(), c.pixel_at(1, 3));
+    }
+}
@@ -0,0 +1,77 @@
+use super::Color;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+/// Operator compressing high dynamic range colors into the displayable
+/// `0.0..=1.0` range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToneMap {
+    /// Clip every component to the displayable range
+    Clamp,
+    /// Reinhard operator `c / (1 + c)` applied to every component, keeping
+    /// detail in highlights at the cost of contrast
+    Reinhard,
+    /// Curve fit of the ACES filmic reference transform by Krzysztof
+    /// Narkowicz, with a toe in the shadows and a soft shoulder
+    Aces,
+}
+
+impl ToneMap {
+    /// Returns the given linear color mapped into the displayable range
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - linear color to map
+    pub fn apply(self, color: Color) -> Color {
+        Color::new(self.map(color.r), self.map(color.g), self.map(color.b))
+    }
+
+    fn map(self, v: f64) -> f64 {
+        let v = v.max(0.0);
+
+        let mapped = match self {
+            ToneMap::Clamp => v,
+            ToneMap::Reinhard => v / (1.0 + v),
+            ToneMap::Aces => (v * (2.51 * v + 0.03)) / (v * (2.43 * v + 0.59) + 0.14),
+        };
+
+        mapped.clamp(0.0, 1.0)
+    }
+}
+
+/// Returns a linear component in `0.0..=1.0` encoded with the sRGB transfer
+/// function and quantized to 8 bits
+pub(crate) fn srgb_encode(v: f64) -> u8 {
+    let encoded = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+
+    (encoded.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_should_map_into_displayable_range() {
+        let bright = Color::new(4.0, 1.0, -1.0);
+
+        assert_eq!(Color::new(1.0, 1.0, 0.0), ToneMap::Clamp.apply(bright));
+        assert_eq!(Color::new(0.8, 0.5, 0.0), ToneMap::Reinhard.apply(bright));
+
+        let aces = ToneMap::Aces.apply(bright);
+        assert!(aces.r > aces.g && aces.r <= 1.0);
+        assert_eq!(0.0, aces.b);
+    }
+
+    #[test]
+    fn srgb_encoding_should_brighten_midtones() {
+        assert_eq!(0, srgb_encode(0.0));
+        assert_eq!(188, srgb_encode(0.5));
+        assert_eq!(255, srgb_encode(1.0));
+    }
+}
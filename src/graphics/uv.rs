@@ -0,0 +1,453 @@
+use crate::graphics::Color;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::{Point2, Tuple, Vec2};
+use core::f64::consts::PI;
+
+/// Two-dimensional pattern defined over texture coordinates `u` and `v`,
+/// both ranging from `0.0` to `1.0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum UvPattern {
+    /// Single color everywhere
+    Solid(Color),
+    /// Alternates between two colors in a grid of squares
+    Checkers {
+        /// Number of squares along u
+        width: f64,
+        /// Number of squares along v
+        height: f64,
+        /// Color of squares with an even index sum
+        a: Color,
+        /// Color of squares with an odd index sum
+        b: Color,
+    },
+    /// Main color with a differently colored square in each corner, used to
+    /// verify the orientation of mapped faces
+    AlignCheck {
+        /// Color of everything but the corners
+        main: Color,
+        /// Color of the upper left corner
+        ul: Color,
+        /// Color of the upper right corner
+        ur: Color,
+        /// Color of the bottom left corner
+        bl: Color,
+        /// Color of the bottom right corner
+        br: Color,
+    },
+}
+
+impl UvPattern {
+    /// Returns the color of the pattern at the given texture coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - horizontal texture coordinate
+    /// * `v` - vertical texture coordinate
+    pub fn uv_at(&self, u: f64, v: f64) -> Color {
+        match *self {
+            UvPattern::Solid(color) => color,
+            UvPattern::Checkers {
+                width,
+                height,
+                a,
+                b,
+            } => {
+                let sum = (u * width).floor() + (v * height).floor();
+
+                if sum.rem_euclid(2.0) == 0.0 {
+                    a
+                } else {
+                    b
+                }
+            }
+            UvPattern::AlignCheck {
+                main,
+                ul,
+                ur,
+                bl,
+                br,
+            } => {
+                if v > 0.8 && u < 0.2 {
+                    ul
+                } else if v > 0.8 && u > 0.8 {
+                    ur
+                } else if v < 0.2 && u < 0.2 {
+                    bl
+                } else if v < 0.2 && u > 0.8 {
+                    br
+                } else {
+                    main
+                }
+            }
+        }
+    }
+}
+
+/// Way texture coordinates outside of `0.0..=1.0` are brought back into range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum UvAddress {
+    /// Tiles the texture by wrapping coordinates around
+    #[default]
+    Repeat,
+    /// Stretches the edges of the texture by clamping coordinates
+    Clamp,
+    /// Tiles the texture, flipping every other tile
+    Mirror,
+}
+
+impl UvAddress {
+    /// Returns the coordinate brought into `0.0..=1.0`
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - texture coordinate
+    pub fn resolve(&self, value: f64) -> f64 {
+        match self {
+            UvAddress::Repeat => value.rem_euclid(1.0),
+            UvAddress::Clamp => value.clamp(0.0, 1.0),
+            UvAddress::Mirror => {
+                let t = value.rem_euclid(2.0);
+
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+/// Two-dimensional pattern projected onto a surface, with control over how
+/// the texture coordinates are scaled, offset and addressed
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextureMap {
+    /// Two-dimensional pattern to project
+    pub pattern: UvPattern,
+    /// Projection from points to texture coordinates
+    pub mapping: UvMapping,
+    /// Way coordinates outside of the texture are brought back into range
+    pub address: UvAddress,
+    /// Factor the u and v coordinates are multiplied by, values above one
+    /// tile the texture more often
+    pub scale: Vec2,
+    /// Amount added to the u and v coordinates after scaling
+    pub offset: Vec2,
+}
+
+impl TextureMap {
+    /// Returns a new texture map repeating the pattern without scale or
+    /// offset
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - two-dimensional pattern to project
+    /// * `mapping` - projection from points to texture coordinates
+    pub fn new(pattern: UvPattern, mapping: UvMapping) -> TextureMap {
+        TextureMap {
+            pattern,
+            mapping,
+            address: UvAddress::Repeat,
+            scale: Vec2::new(1.0, 1.0),
+            offset: Vec2::new(0.0, 0.0),
+        }
+    }
+
+    /// Returns the texture coordinates of the given point after scaling,
+    /// offsetting and addressing
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn uv(&self, point: Tuple) -> Point2 {
+        let uv = self.mapping.map(point);
+
+        Point2::new(
+            self.address.resolve(uv.x * self.scale.x + self.offset.x),
+            self.address.resolve(uv.y * self.scale.y + self.offset.y),
+        )
+    }
+
+    /// Returns the color of the texture at the given point
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn color_at(&self, point: Tuple) -> Color {
+        let uv = self.uv(point);
+
+        self.pattern.uv_at(uv.x, uv.y)
+    }
+}
+
+/// Projection of three-dimensional points onto texture coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum UvMapping {
+    /// Wraps the texture around a sphere centered at the origin
+    Spherical,
+    /// Lays the texture across the xz plane, one unit per texture
+    Planar,
+    /// Wraps the texture around the y axis, one unit of y per texture
+    Cylindrical,
+}
+
+impl UvMapping {
+    /// Returns the texture coordinates of the given point, which planar and
+    /// cylindrical mappings leave unbounded for the addressing mode of a
+    /// texture map to resolve
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn map(&self, point: Tuple) -> Point2 {
+        match self {
+            UvMapping::Spherical => {
+                let theta = point.x.atan2(point.z);
+                let radius = Tuple::vector(point.x, point.y, point.z).magnitude();
+                let phi = (point.y / radius).acos();
+                let raw_u = theta / (2.0 * PI);
+
+                Point2::new(1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+            }
+            UvMapping::Planar => Point2::new(point.x, point.z),
+            UvMapping::Cylindrical => {
+                let theta = point.x.atan2(point.z);
+                let raw_u = theta / (2.0 * PI);
+
+                Point2::new(1.0 - (raw_u + 0.5), point.y)
+            }
+        }
+    }
+}
+
+/// Face of an axis-aligned cube centered at the origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CubeFace {
+    /// Face pointing towards negative x
+    Left,
+    /// Face pointing towards positive z
+    Front,
+    /// Face pointing towards positive x
+    Right,
+    /// Face pointing towards negative z
+    Back,
+    /// Face pointing towards positive y
+    Up,
+    /// Face pointing towards negative y
+    Down,
+}
+
+impl CubeFace {
+    /// Every face, in the order used to index the faces of a cube map
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::Left,
+        CubeFace::Front,
+        CubeFace::Right,
+        CubeFace::Back,
+        CubeFace::Up,
+        CubeFace::Down,
+    ];
+
+    /// Returns the face whose axis the given point is furthest along
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn of(point: Tuple) -> CubeFace {
+        let coord = point.x.abs().max(point.y.abs()).max(point.z.abs());
+
+        if coord == point.x {
+            CubeFace::Right
+        } else if coord == -point.x {
+            CubeFace::Left
+        } else if coord == point.y {
+            CubeFace::Up
+        } else if coord == -point.y {
+            CubeFace::Down
+        } else if coord == point.z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+
+    /// Returns the index of the face in `CubeFace::ALL`
+    pub fn index(&self) -> usize {
+        match self {
+            CubeFace::Left => 0,
+            CubeFace::Front => 1,
+            CubeFace::Right => 2,
+            CubeFace::Back => 3,
+            CubeFace::Up => 4,
+            CubeFace::Down => 5,
+        }
+    }
+
+    /// Returns the texture coordinates of the given point on this face of a
+    /// cube spanning `-1.0..=1.0` on every axis
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in pattern space
+    pub fn uv(&self, point: Tuple) -> Point2 {
+        let wrap = |value: f64| value.rem_euclid(2.0) / 2.0;
+
+        match self {
+            CubeFace::Left => Point2::new(wrap(point.z + 1.0), wrap(point.y + 1.0)),
+            CubeFace::Front => Point2::new(wrap(point.x + 1.0), wrap(point.y + 1.0)),
+            CubeFace::Right => Point2::new(wrap(1.0 - point.z), wrap(point.y + 1.0)),
+            CubeFace::Back => Point2::new(wrap(1.0 - point.x), wrap(point.y + 1.0)),
+            CubeFace::Up => Point2::new(wrap(point.x + 1.0), wrap(1.0 - point.z)),
+            CubeFace::Down => Point2::new(wrap(point.x + 1.0), wrap(point.z + 1.0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn align_check() -> UvPattern {
+        UvPattern::AlignCheck {
+            main: Color::new(1.0, 1.0, 1.0),
+            ul: Color::new(1.0, 0.0, 0.0),
+            ur: Color::new(1.0, 1.0, 0.0),
+            bl: Color::new(0.0, 1.0, 0.0),
+            br: Color::new(0.0, 1.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn uv_checkers_should_alternate_in_grid() {
+        let p = UvPattern::Checkers {
+            width: 2.0,
+            height: 2.0,
+            a: Color::new(0.0, 0.0, 0.0),
+            b: Color::new(1.0, 1.0, 1.0),
+        };
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), p.uv_at(0.0, 0.0));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), p.uv_at(0.5, 0.0));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), p.uv_at(0.0, 0.5));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), p.uv_at(0.5, 0.5));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), p.uv_at(1.0, 1.0));
+    }
+
+    #[test]
+    fn align_check_should_color_each_corner() {
+        let p = align_check();
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), p.uv_at(0.5, 0.5));
+        assert_eq!(Color::new(1.0, 0.0, 0.0), p.uv_at(0.1, 0.9));
+        assert_eq!(Color::new(1.0, 1.0, 0.0), p.uv_at(0.9, 0.9));
+        assert_eq!(Color::new(0.0, 1.0, 0.0), p.uv_at(0.1, 0.1));
+        assert_eq!(Color::new(0.0, 1.0, 1.0), p.uv_at(0.9, 0.1));
+    }
+
+    #[test]
+    fn should_map_points_to_uv() {
+        let spherical = [
+            (Tuple::point(0.0, 0.0, -1.0), Point2::new(0.0, 0.5)),
+            (Tuple::point(1.0, 0.0, 0.0), Point2::new(0.25, 0.5)),
+            (Tuple::point(0.0, 0.0, 1.0), Point2::new(0.5, 0.5)),
+            (Tuple::point(0.0, 1.0, 0.0), Point2::new(0.5, 1.0)),
+            (Tuple::point(0.0, -1.0, 0.0), Point2::new(0.5, 0.0)),
+        ];
+
+        for (point, uv) in spherical {
+            assert_eq!(uv, UvMapping::Spherical.map(point));
+        }
+
+        assert_eq!(
+            Point2::new(0.25, -0.25),
+            UvMapping::Planar.map(Tuple::point(0.25, 3.0, -0.25))
+        );
+        assert_eq!(
+            Point2::new(0.5, 1.25),
+            UvMapping::Cylindrical.map(Tuple::point(0.0, 1.25, 1.0))
+        );
+    }
+
+    #[test]
+    fn should_identify_cube_faces() {
+        let faces = [
+            (Tuple::point(-1.0, 0.5, -0.25), CubeFace::Left),
+            (Tuple::point(1.1, -0.75, 0.8), CubeFace::Right),
+            (Tuple::point(0.1, 0.6, 0.9), CubeFace::Front),
+            (Tuple::point(-0.7, 0.0, -2.0), CubeFace::Back),
+            (Tuple::point(0.5, 1.0, 0.9), CubeFace::Up),
+            (Tuple::point(-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+
+        for (point, face) in faces {
+            assert_eq!(face, CubeFace::of(point));
+            assert_eq!(face, CubeFace::ALL[face.index()]);
+        }
+    }
+
+    #[test]
+    fn should_map_points_on_cube_faces_to_uv() {
+        assert_eq!(
+            Point2::new(0.25, 0.75),
+            CubeFace::Front.uv(Tuple::point(-0.5, 0.5, 1.0))
+        );
+        assert_eq!(
+            Point2::new(0.75, 0.25),
+            CubeFace::Back.uv(Tuple::point(-0.5, -0.5, -1.0))
+        );
+        assert_eq!(
+            Point2::new(0.25, 0.75),
+            CubeFace::Left.uv(Tuple::point(-1.0, 0.5, -0.5))
+        );
+        assert_eq!(
+            Point2::new(0.25, 0.75),
+            CubeFace::Right.uv(Tuple::point(1.0, 0.5, 0.5))
+        );
+        assert_eq!(
+            Point2::new(0.25, 0.25),
+            CubeFace::Up.uv(Tuple::point(-0.5, 1.0, 0.5))
+        );
+        assert_eq!(
+            Point2::new(0.25, 0.75),
+            CubeFace::Down.uv(Tuple::point(-0.5, -1.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn should_resolve_coordinates_by_address_mode() {
+        assert_eq!(0.25, UvAddress::Repeat.resolve(1.25));
+        assert_eq!(0.75, UvAddress::Repeat.resolve(-0.25));
+        assert_eq!(1.0, UvAddress::Clamp.resolve(1.25));
+        assert_eq!(0.0, UvAddress::Clamp.resolve(-0.25));
+        assert_eq!(0.75, UvAddress::Mirror.resolve(1.25));
+        assert_eq!(0.25, UvAddress::Mirror.resolve(-0.25));
+        assert_eq!(0.5, UvAddress::Mirror.resolve(2.5));
+    }
+
+    #[test]
+    fn texture_map_should_scale_offset_and_address_coordinates() {
+        let mut t = TextureMap::new(align_check(), UvMapping::Planar);
+
+        assert_eq!(Point2::new(0.5, 0.75), t.uv(Tuple::point(2.5, 0.0, -0.25)));
+
+        t.scale = Vec2::new(0.25, 0.25);
+        t.offset = Vec2::new(0.5, 0.0);
+        assert_eq!(Point2::new(0.75, 0.5), t.uv(Tuple::point(1.0, 0.0, 2.0)));
+
+        t.address = UvAddress::Clamp;
+        assert_eq!(Point2::new(1.0, 0.0), t.uv(Tuple::point(4.0, 0.0, -1.0)));
+        assert_eq!(
+            Color::new(0.0, 1.0, 1.0),
+            t.color_at(Tuple::point(4.0, 0.0, -1.0))
+        );
+    }
+}
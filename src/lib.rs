@@ -1,9 +1,21 @@
-//! # raylib
+//! # libray
 //!
-//! raylib is a ray tracing library.
+//! libray is a ray tracing library.
 //!
 //! This is an implementation of The Ray Tracer Challenge by Jamis Buck.
+//!
+//! The default `std` feature can be disabled to build the math, shading and
+//! rendering core under `no_std` with `alloc`. File output, scripting, the
+//! C API and multithreaded rendering require `std`.
+//!
+//! The `tracing` feature emits spans around script loading, world reloads
+//! and the render phases through the `tracing` crate, for consumers to
+//! observe long renders with a subscriber of their choice.
+//!
+//! The `rayon` feature lets applications render on a rayon thread pool they
+//! already own.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(
     missing_docs,
     missing_debug_implementations,
@@ -16,5 +28,17 @@
     unused_qualifications
 )]
 
+extern crate alloc;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod graphics;
 pub mod math;
+pub mod physics;
+pub mod scene;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod testing;
+
+pub use physics::{Environment, Projectile};
+pub use scene::{Camera, Computations, RenderSettings, ShapeId, World};
@@ -2,14 +2,53 @@
 //!
 //! Contains fundamental math type and methods.
 
+mod angle;
+mod animation;
+mod cylinder;
+mod decomposition;
+#[cfg(not(any(feature = "std", test)))]
+mod float;
 mod intersection;
+mod interval;
 mod matrix;
+mod plane;
+mod sampling;
+mod shape;
+mod solvers;
+mod spline;
+mod transform_stack;
 mod transformations;
 mod tuple;
+mod vec2;
 mod vectors;
 
+/// Tolerance shared by all floating point comparisons, below which
+/// differences and denominators are treated as zero
+///
+/// It is also the default tolerance of rays, within which a ray missing a
+/// curved surface is still treated as grazing it, see `Ray::with_tolerance`.
+pub const EPSILON: f64 = 0.00001;
+
+pub use angle::Angle;
+pub use animation::{Orbit, Oscillation, TransformProvider};
+pub use cylinder::Cylinder;
+pub use decomposition::{Lu, Qr};
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) use float::Float;
 pub use intersection::{Intersection, Ray, Sphere};
-pub use matrix::Matrix;
+pub use interval::Interval;
+pub(crate) use matrix::format_f64;
+pub use matrix::{Matrix, ShapeMismatch};
+pub use plane::Plane;
+pub use sampling::{Halton, Sampler, Sobol};
+#[cfg(feature = "serde")]
+pub(crate) use shape::tagged as tagged_shapes;
+pub use shape::{AnyShape, Shape};
+pub use solvers::{solve_cubic, solve_quadratic, solve_quadratic_within, solve_quartic};
+pub use spline::{catmull_rom, cubic_bezier, Spline, SplineKind};
+pub use transform_stack::{TransformStack, TransformStep};
 pub use transformations::*;
-pub use tuple::Tuple;
+pub(crate) use tuple::quantize;
+pub use tuple::{QuantizedTuple, Tuple};
+pub use vec2::{Point2, Vec2};
 pub use vectors::{normal_at, reflect};
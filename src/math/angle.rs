@@ -0,0 +1,146 @@
+//! Angles with an explicit unit
+
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Angle stored in radians, created from an explicit unit so that degrees
+/// cannot be mistaken for radians
+///
+/// Functions taking an angle accept anything convertible into an `Angle`.
+/// A bare `f64` converts as radians.
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{rotation_z, Angle};
+/// use core::f64::consts::PI;
+///
+/// assert_eq!(rotation_z(PI / 2.0), rotation_z(Angle::from_degrees(90.0)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Angle {
+    radians: f64,
+}
+
+impl Angle {
+    /// Creates a new Angle from a value in degrees
+    ///
+    /// # Arguments
+    ///
+    /// * `degrees` - size of the angle in degrees
+    pub fn from_degrees(degrees: f64) -> Angle {
+        Angle {
+            radians: degrees.to_radians(),
+        }
+    }
+
+    /// Creates a new Angle from a value in radians
+    ///
+    /// # Arguments
+    ///
+    /// * `radians` - size of the angle in radians
+    pub fn from_radians(radians: f64) -> Angle {
+        Angle { radians }
+    }
+
+    /// Returns the size of the angle in degrees
+    pub fn degrees(&self) -> f64 {
+        self.radians.to_degrees()
+    }
+
+    /// Returns the size of the angle in radians
+    pub fn radians(&self) -> f64 {
+        self.radians
+    }
+
+    /// Returns the sine of the angle
+    pub fn sin(&self) -> f64 {
+        self.radians.sin()
+    }
+
+    /// Returns the cosine of the angle
+    pub fn cos(&self) -> f64 {
+        self.radians.cos()
+    }
+
+    /// Returns the tangent of the angle
+    pub fn tan(&self) -> f64 {
+        self.radians.tan()
+    }
+}
+
+impl From<f64> for Angle {
+    fn from(radians: f64) -> Angle {
+        Angle::from_radians(radians)
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, other: Angle) -> Angle {
+        Angle::from_radians(self.radians + other.radians)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        Angle::from_radians(self.radians - other.radians)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle::from_radians(-self.radians)
+    }
+}
+
+impl Mul<f64> for Angle {
+    type Output = Angle;
+
+    fn mul(self, other: f64) -> Angle {
+        Angle::from_radians(self.radians * other)
+    }
+}
+
+impl Div<f64> for Angle {
+    type Output = Angle;
+
+    fn div(self, other: f64) -> Angle {
+        Angle::from_radians(self.radians / other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+    use core::f64::consts::PI;
+
+    #[test]
+    fn should_convert_between_degrees_and_radians() {
+        let a = Angle::from_degrees(180.0);
+
+        assert!((a.radians() - PI).abs() < EPSILON);
+        assert!((Angle::from_radians(PI / 2.0).degrees() - 90.0).abs() < EPSILON);
+        assert_eq!(Angle::from_radians(0.5), Angle::from(0.5));
+    }
+
+    #[test]
+    fn should_apply_arithmetic_in_radians() {
+        let a = Angle::from_radians(1.0);
+        let b = Angle::from_radians(0.25);
+
+        assert_eq!(Angle::from_radians(1.25), a + b);
+        assert_eq!(Angle::from_radians(0.75), a - b);
+        assert_eq!(Angle::from_radians(-1.0), -a);
+        assert_eq!(Angle::from_radians(2.0), a * 2.0);
+        assert_eq!(Angle::from_radians(0.5), a / 2.0);
+    }
+}
@@ -0,0 +1,170 @@
+//! Time dependent transformations
+
+use super::matrix::Matrix;
+use super::transformations::{rotation_y, translation};
+use super::tuple::Tuple;
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use core::f64::consts::PI;
+
+/// Source of a transformation matrix which may change over time
+pub trait TransformProvider {
+    /// Returns the transformation matrix at the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - time to evaluate the transformation at, in seconds
+    fn transform_at(&self, time: f64) -> Matrix;
+}
+
+impl TransformProvider for Matrix {
+    fn transform_at(&self, _time: f64) -> Matrix {
+        self.clone()
+    }
+}
+
+impl<F> TransformProvider for F
+where
+    F: Fn(f64) -> Matrix,
+{
+    fn transform_at(&self, time: f64) -> Matrix {
+        self(time)
+    }
+}
+
+/// Circular motion around a vertical axis, applied after a base
+/// transformation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orbit {
+    /// Point the orbit revolves around
+    pub center: Tuple,
+    /// Distance from the center
+    pub radius: f64,
+    /// Time taken for a full revolution, in seconds
+    pub period: f64,
+    /// Transformation applied before moving onto the orbit
+    pub base: Matrix,
+}
+
+impl Orbit {
+    /// Returns a new orbit with an identity base transformation
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - point the orbit revolves around
+    /// * `radius` - distance from the center
+    /// * `period` - time taken for a full revolution, in seconds
+    pub fn new(center: Tuple, radius: f64, period: f64) -> Orbit {
+        Orbit {
+            center,
+            radius,
+            period,
+            base: Matrix::identity(4),
+        }
+    }
+}
+
+impl TransformProvider for Orbit {
+    fn transform_at(&self, time: f64) -> Matrix {
+        let angle = 2.0 * PI * time / self.period;
+
+        translation(self.center.x, self.center.y, self.center.z)
+            * rotation_y(angle)
+            * translation(self.radius, 0.0, 0.0)
+            * self.base.clone()
+    }
+}
+
+/// Sinusoidal back and forth motion along a direction, applied after a base
+/// transformation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oscillation {
+    /// Direction of the motion, its length being the amplitude
+    pub axis: Tuple,
+    /// Time taken for a full oscillation, in seconds
+    pub period: f64,
+    /// Transformation applied before the oscillation
+    pub base: Matrix,
+}
+
+impl Oscillation {
+    /// Returns a new oscillation with an identity base transformation
+    ///
+    /// # Arguments
+    ///
+    /// * `axis` - direction of the motion, its length being the amplitude
+    /// * `period` - time taken for a full oscillation, in seconds
+    pub fn new(axis: Tuple, period: f64) -> Oscillation {
+        Oscillation {
+            axis,
+            period,
+            base: Matrix::identity(4),
+        }
+    }
+}
+
+impl TransformProvider for Oscillation {
+    fn transform_at(&self, time: f64) -> Matrix {
+        let offset = self.axis * (2.0 * PI * time / self.period).sin();
+
+        translation(offset.x, offset.y, offset.z) * self.base.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::scaling;
+
+    #[test]
+    fn static_matrices_should_not_change_over_time() {
+        let m = scaling(2.0, 2.0, 2.0);
+
+        assert_eq!(m, m.transform_at(0.0));
+        assert_eq!(m, m.transform_at(12.5));
+    }
+
+    #[test]
+    fn closures_should_provide_transforms() {
+        let provider = |t: f64| translation(t, 0.0, 0.0);
+
+        assert_eq!(translation(3.0, 0.0, 0.0), provider.transform_at(3.0));
+    }
+
+    #[test]
+    fn orbit_should_revolve_around_center() {
+        let orbit = Orbit::new(Tuple::point(0.0, 1.0, 0.0), 2.0, 4.0);
+        let origin = Tuple::point(0.0, 0.0, 0.0);
+
+        assert_eq!(
+            Tuple::point(2.0, 1.0, 0.0),
+            &orbit.transform_at(0.0) * &origin
+        );
+        assert_eq!(
+            Tuple::point(0.0, 1.0, -2.0),
+            &orbit.transform_at(1.0) * &origin
+        );
+        assert_eq!(
+            Tuple::point(-2.0, 1.0, 0.0),
+            &orbit.transform_at(2.0) * &origin
+        );
+    }
+
+    #[test]
+    fn oscillation_should_move_along_axis() {
+        let oscillation = Oscillation::new(Tuple::vector(0.0, 0.5, 0.0), 2.0);
+        let origin = Tuple::point(0.0, 0.0, 0.0);
+
+        assert_eq!(origin, &oscillation.transform_at(0.0) * &origin);
+        assert_eq!(
+            Tuple::point(0.0, 0.5, 0.0),
+            &oscillation.transform_at(0.5) * &origin
+        );
+        assert_eq!(
+            Tuple::point(0.0, -0.5, 0.0),
+            &oscillation.transform_at(1.5) * &origin
+        );
+    }
+}
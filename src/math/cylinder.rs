@@ -0,0 +1,352 @@
+use super::{solve_quadratic_within, Intersection, Matrix, Ray, Shape, Tuple, EPSILON};
+use crate::graphics::Material;
+use alloc::{vec, vec::Vec};
+
+/// Cylinder of radius one around the y axis, optionally truncated and
+/// closed by caps at both ends
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{Cylinder, Ray, Tuple};
+///
+/// let mut cylinder = Cylinder::new();
+/// cylinder.minimum = 1.0;
+/// cylinder.maximum = 2.0;
+/// cylinder.closed = true;
+///
+/// // straight down through both caps
+/// let ray = Ray::new(Tuple::point(0.0, 3.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+/// let times: Vec<f64> = ray.intersect(&cylinder).iter().map(|i| i.t).collect();
+///
+/// assert_eq!(vec![1.0, 2.0], times);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cylinder {
+    /// Transformation matrix of the cylinder
+    pub transform: Matrix,
+    /// Material of the cylinder
+    pub material: Material,
+    /// Height below which the cylinder is cut off, exclusive
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "bound::serialize", deserialize_with = "bound::lower")
+    )]
+    pub minimum: f64,
+    /// Height above which the cylinder is cut off, exclusive
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "bound::serialize", deserialize_with = "bound::upper")
+    )]
+    pub maximum: f64,
+    /// Whether the cut off ends are closed by caps
+    pub closed: bool,
+}
+
+impl Cylinder {
+    /// Returns a new open cylinder of infinite length
+    pub fn new() -> Cylinder {
+        Cylinder {
+            transform: Matrix::identity(4),
+            material: Material::new(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    /// Sets the transformation matrix of cylinder to given transformation
+    /// matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - transformation matrix to set for cylinder
+    pub fn transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Returns the intersections of the ray with the caps of the cylinder,
+    /// none if it is open
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        if !self.closed || ray.direction.y == 0.0 {
+            return;
+        }
+
+        let origin = ray.position(0.0);
+
+        for height in [self.minimum, self.maximum] {
+            let t = (height - origin.y) / ray.direction.y;
+            let p = ray.position(t);
+
+            if p.x * p.x + p.z * p.z <= 1.0 {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+impl Shape for Cylinder {
+    fn transformation(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transformation(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let origin = ray.position(0.0);
+        let direction = ray.direction;
+        let a = direction.x * direction.x + direction.z * direction.z;
+        let mut intersections = vec![];
+
+        // rays parallel to the y axis can only hit the caps
+        if a != 0.0 {
+            let b = 2.0 * (origin.x * direction.x + origin.z * direction.z);
+            let c = origin.x * origin.x + origin.z * origin.z - 1.0;
+
+            if let Some((first, second)) = solve_quadratic_within(a, b, c, ray.tolerance()) {
+                for t in [first, second] {
+                    let y = origin.y + t * direction.y;
+
+                    if self.minimum < y && y < self.maximum {
+                        intersections.push(Intersection::new(t, self));
+                    }
+                }
+            }
+        }
+
+        self.intersect_caps(ray, &mut intersections);
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        intersections
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let distance = point.x * point.x + point.z * point.z;
+
+        if distance < 1.0 && point.y >= self.maximum - EPSILON {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else if distance < 1.0 && point.y <= self.minimum + EPSILON {
+            Tuple::vector(0.0, -1.0, 0.0)
+        } else {
+            Tuple::vector(point.x, 0.0, point.z)
+        }
+    }
+
+    fn local_bounds(&self) -> (Tuple, Tuple) {
+        (
+            Tuple::point(-1.0, self.minimum, -1.0),
+            Tuple::point(1.0, self.maximum, 1.0),
+        )
+    }
+}
+
+impl Default for Cylinder {
+    fn default() -> Cylinder {
+        Cylinder::new()
+    }
+}
+
+/// Serialization of the cut off heights, writing infinite ones as `None`
+/// since formats like JSON have no infinity
+#[cfg(feature = "serde")]
+mod bound {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(height: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if height.is_finite() {
+            serializer.serialize_some(height)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+
+    pub(super) fn lower<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::deserialize(deserializer)?.unwrap_or(f64::NEG_INFINITY))
+    }
+
+    pub(super) fn upper<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn times(cylinder: &Cylinder, origin: Tuple, direction: Tuple) -> Vec<f64> {
+        cylinder
+            .local_intersect(&Ray::new(origin, direction.normalize()))
+            .iter()
+            .map(|i| i.t)
+            .collect()
+    }
+
+    fn truncated(closed: bool) -> Cylinder {
+        Cylinder {
+            minimum: 1.0,
+            maximum: 2.0,
+            closed,
+            ..Cylinder::new()
+        }
+    }
+
+    #[test]
+    fn default_cylinder_should_be_open_and_infinite() {
+        let c = Cylinder::default();
+
+        assert_eq!(f64::NEG_INFINITY, c.minimum);
+        assert_eq!(f64::INFINITY, c.maximum);
+        assert!(!c.closed);
+    }
+
+    #[test]
+    fn ray_should_miss_cylinder() {
+        let c = Cylinder::new();
+
+        for (origin, direction) in [
+            (Tuple::point(1.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(1.0, 1.0, 1.0)),
+        ] {
+            assert!(times(&c, origin, direction).is_empty());
+        }
+    }
+
+    #[test]
+    fn ray_should_strike_cylinder() {
+        let c = Cylinder::new();
+        let hits = |origin, direction| times(&c, origin, direction);
+
+        assert_eq!(
+            vec![5.0, 5.0],
+            hits(Tuple::point(1.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0))
+        );
+        assert_eq!(
+            vec![4.0, 6.0],
+            hits(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0))
+        );
+
+        let slanted = hits(Tuple::point(0.5, 0.0, -5.0), Tuple::vector(0.1, 1.0, 1.0));
+        assert!((slanted[0] - 6.80798).abs() < EPSILON);
+        assert!((slanted[1] - 7.08872).abs() < EPSILON);
+    }
+
+    #[test]
+    fn large_cylinder_should_be_hit() {
+        let mut c = truncated(true);
+        c.transform(crate::math::scaling(1000.0, 1000.0, 1000.0));
+        let side = Ray::new(
+            Tuple::point(0.0, 1500.0, -5000.0),
+            Tuple::vector(0.0, 0.0, 1.0),
+        );
+        let caps = Ray::new(
+            Tuple::point(0.0, 3000.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+        );
+        let times = |ray: Ray| -> Vec<f64> { ray.intersect(&c).iter().map(|i| i.t).collect() };
+
+        assert_eq!(vec![4000.0, 6000.0], times(side));
+        assert_eq!(vec![1000.0, 2000.0], times(caps));
+    }
+
+    #[test]
+    fn normal_should_point_away_from_axis() {
+        let c = Cylinder::new();
+
+        assert_eq!(
+            Tuple::vector(1.0, 0.0, 0.0),
+            c.local_normal_at(Tuple::point(1.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            Tuple::vector(0.0, 0.0, -1.0),
+            c.local_normal_at(Tuple::point(0.0, 5.0, -1.0))
+        );
+        assert_eq!(
+            Tuple::vector(-1.0, 0.0, 0.0),
+            c.local_normal_at(Tuple::point(-1.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn truncated_cylinder_should_only_be_hit_between_its_ends() {
+        let c = truncated(false);
+        let counts: Vec<usize> = [
+            (Tuple::point(0.0, 1.5, 0.0), Tuple::vector(0.1, 1.0, 0.0)),
+            (Tuple::point(0.0, 3.0, -5.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.0, 2.0, -5.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.0, 1.0, -5.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.0, 1.5, -2.0), Tuple::vector(0.0, 0.0, 1.0)),
+        ]
+        .into_iter()
+        .map(|(origin, direction)| times(&c, origin, direction).len())
+        .collect();
+
+        assert_eq!(vec![0, 0, 0, 0, 0, 2], counts);
+    }
+
+    #[test]
+    fn closed_cylinder_should_be_hit_on_its_caps() {
+        let c = truncated(true);
+        let counts: Vec<usize> = [
+            (Tuple::point(0.0, 3.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 3.0, -2.0), Tuple::vector(0.0, -1.0, 2.0)),
+            (Tuple::point(0.0, 4.0, -2.0), Tuple::vector(0.0, -1.0, 1.0)),
+            (Tuple::point(0.0, 0.0, -2.0), Tuple::vector(0.0, 1.0, 2.0)),
+            (Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 1.0)),
+        ]
+        .into_iter()
+        .map(|(origin, direction)| times(&c, origin, direction).len())
+        .collect();
+
+        assert_eq!(vec![2, 2, 2, 2, 2], counts);
+    }
+
+    #[test]
+    fn normal_on_caps_should_point_along_axis() {
+        let c = truncated(true);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        for (point, normal) in [
+            (Tuple::point(0.0, 1.0, 0.0), -up),
+            (Tuple::point(0.5, 1.0, 0.0), -up),
+            (Tuple::point(0.0, 1.0, 0.5), -up),
+            (Tuple::point(0.0, 2.0, 0.0), up),
+            (Tuple::point(0.5, 2.0, 0.0), up),
+            (Tuple::point(0.0, 2.0, 0.5), up),
+        ] {
+            assert_eq!(normal, c.local_normal_at(point));
+        }
+    }
+
+    #[test]
+    fn bounds_should_span_truncated_length() {
+        let corners = truncated(true).bounding_corners();
+
+        assert_eq!(Tuple::point(-1.0, 1.0, -1.0), corners[0]);
+        assert_eq!(Tuple::point(1.0, 2.0, 1.0), corners[7]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn unbounded_cylinder_should_round_trip_through_json() {
+        let open = Cylinder::new();
+        let json = serde_json::to_string(&open).unwrap();
+
+        assert_eq!(open, serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            truncated(true),
+            serde_json::from_str(&serde_json::to_string(&truncated(true)).unwrap()).unwrap()
+        );
+    }
+}
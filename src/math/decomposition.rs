@@ -0,0 +1,354 @@
+//! Matrix decompositions
+
+use super::matrix::Matrix;
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::EPSILON;
+use alloc::{vec, vec::Vec};
+
+/// LU decomposition with partial pivoting of a square matrix, such that
+/// the rows of the matrix reordered by `permutation` equal `l * u`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu {
+    /// Lower triangular matrix with ones on its main diagonal
+    pub l: Matrix,
+    /// Upper triangular matrix
+    pub u: Matrix,
+    /// Index of the original row placed at each row of the decomposition
+    pub permutation: Vec<usize>,
+    /// Whether an odd number of rows were swapped
+    odd: bool,
+}
+
+/// QR decomposition of a matrix with at least as many rows as columns,
+/// such that the matrix equals `q * r`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qr {
+    /// Square orthogonal matrix
+    pub q: Matrix,
+    /// Upper triangular matrix of the same size as the decomposed one
+    pub r: Matrix,
+}
+
+impl Matrix {
+    /// Returns the LU decomposition of the Matrix, or `None` if it is not
+    /// square or is singular
+    ///
+    /// A pivot counts as zero when it is within `EPSILON` times the norm of
+    /// the Matrix, so that scaling the Matrix doesn't change whether it is
+    /// found singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Matrix;
+    ///
+    /// let a = Matrix::from(vec![vec![0.0, 2.0], vec![3.0, 4.0]]);
+    /// let lu = a.lu().unwrap();
+    ///
+    /// assert_eq!(vec![1.0, 1.0], lu.solve(&[2.0, 7.0]));
+    /// assert_eq!(-6.0, lu.determinant());
+    /// ```
+    pub fn lu(&self) -> Option<Lu> {
+        let (n, cols) = self.dimensions();
+
+        if n != cols {
+            return None;
+        }
+
+        let tolerance = EPSILON * self.norm();
+        let mut u = self.clone();
+        let mut l = Matrix::identity(n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut odd = false;
+
+        for k in 0..n {
+            let pivot = (k..n).max_by(|&a, &b| u[(a, k)].abs().total_cmp(&u[(b, k)].abs()))?;
+
+            if u[(pivot, k)].abs() <= tolerance {
+                return None;
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    let tmp = u[(k, j)];
+                    u[(k, j)] = u[(pivot, j)];
+                    u[(pivot, j)] = tmp;
+                }
+                for j in 0..k {
+                    let tmp = l[(k, j)];
+                    l[(k, j)] = l[(pivot, j)];
+                    l[(pivot, j)] = tmp;
+                }
+                permutation.swap(k, pivot);
+                odd = !odd;
+            }
+
+            for i in k + 1..n {
+                let factor = u[(i, k)] / u[(k, k)];
+                l[(i, k)] = factor;
+
+                for j in k..n {
+                    u[(i, j)] -= factor * u[(k, j)];
+                }
+            }
+        }
+
+        Some(Lu {
+            l,
+            u,
+            permutation,
+            odd,
+        })
+    }
+
+    /// Returns the QR decomposition of the Matrix computed with Householder
+    /// reflections, or `None` if it has fewer rows than columns
+    pub fn qr(&self) -> Option<Qr> {
+        let (m, n) = self.dimensions();
+
+        if m < n {
+            return None;
+        }
+
+        let tolerance = EPSILON * self.norm();
+        let mut r = self.clone();
+        let mut q = Matrix::identity(m);
+
+        for k in 0..n.min(m - 1) {
+            let norm = (k..m).map(|i| r[(i, k)] * r[(i, k)]).sum::<f64>().sqrt();
+
+            if norm <= tolerance {
+                continue;
+            }
+
+            let alpha = -norm.copysign(r[(k, k)]);
+            let mut v: Vec<f64> = (k..m).map(|i| r[(i, k)]).collect();
+            v[0] -= alpha;
+            let length = v.iter().map(|x| x * x).sum::<f64>();
+
+            if length <= tolerance * tolerance {
+                continue;
+            }
+
+            for j in 0..n {
+                let dot: f64 = (k..m).map(|i| v[i - k] * r[(i, j)]).sum();
+                for i in k..m {
+                    r[(i, j)] -= 2.0 * v[i - k] * dot / length;
+                }
+            }
+            for i in 0..m {
+                let dot: f64 = (k..m).map(|j| q[(i, j)] * v[j - k]).sum();
+                for j in k..m {
+                    q[(i, j)] -= 2.0 * dot * v[j - k] / length;
+                }
+            }
+        }
+
+        Some(Qr { q, r })
+    }
+}
+
+impl Lu {
+    /// Returns the solution `x` of `a * x = b`, where `a` is the decomposed
+    /// matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - right hand side, one value per row
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` does not have one value per row
+    pub fn solve(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.permutation.len();
+        assert_eq!(n, b.len(), "expected {} values, got {}", n, b.len());
+
+        let mut y = vec![0.0; n];
+        for i in 0..n {
+            y[i] = b[self.permutation[i]] - (0..i).map(|j| self.l[(i, j)] * y[j]).sum::<f64>();
+        }
+
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|j| self.u[(i, j)] * x[j]).sum();
+            x[i] = (y[i] - sum) / self.u[(i, i)];
+        }
+
+        x
+    }
+
+    /// Returns the determinant of the decomposed matrix
+    pub fn determinant(&self) -> f64 {
+        let product: f64 = (0..self.permutation.len())
+            .map(|i| self.u[(i, i)])
+            .product();
+
+        if self.odd {
+            -product
+        } else {
+            product
+        }
+    }
+}
+
+impl Qr {
+    /// Returns the least squares solution `x` minimizing `|a * x - b|`,
+    /// where `a` is the decomposed matrix, or `None` if its columns are
+    /// linearly dependent
+    ///
+    /// Columns count as dependent when a diagonal element of `r` is within
+    /// `EPSILON` times the norm of `r`, which equals the norm of `a`.
+    ///
+    /// # Arguments
+    ///
+    /// * `b` - right hand side, one value per row
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` does not have one value per row
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Matrix;
+    ///
+    /// // fit a line y = c + m * x through three points
+    /// let a = Matrix::from(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+    /// let x = a.qr().unwrap().least_squares(&[1.0, 3.0, 5.0]).unwrap();
+    ///
+    /// assert!((x[0] - 1.0).abs() < 1e-9 && (x[1] - 2.0).abs() < 1e-9);
+    /// ```
+    pub fn least_squares(&self, b: &[f64]) -> Option<Vec<f64>> {
+        let (m, n) = self.r.dimensions();
+        assert_eq!(m, b.len(), "expected {} values, got {}", m, b.len());
+
+        let qtb: Vec<f64> = (0..n)
+            .map(|i| (0..m).map(|j| self.q[(j, i)] * b[j]).sum())
+            .collect();
+        let tolerance = EPSILON * self.r.norm();
+        let mut x = vec![0.0; n];
+
+        for i in (0..n).rev() {
+            if self.r[(i, i)].abs() <= tolerance {
+                return None;
+            }
+
+            let sum: f64 = (i + 1..n).map(|j| self.r[(i, j)] * x[j]).sum();
+            x[i] = (qtb[i] - sum) / self.r[(i, i)];
+        }
+
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Matrix {
+        Matrix::from(vec![
+            vec![-2.0, -8.0, 3.0, 5.0],
+            vec![-3.0, 1.0, 7.0, 3.0],
+            vec![1.0, 2.0, -9.0, 6.0],
+            vec![-6.0, 7.0, 7.0, -9.0],
+        ])
+    }
+
+    #[test]
+    fn lu_should_reconstruct_permuted_matrix() {
+        let a = sample();
+        let lu = a.lu().unwrap();
+        let permuted = Matrix::from(
+            lu.permutation
+                .iter()
+                .map(|&i| a.row(i).to_vec())
+                .collect::<Vec<_>>(),
+        );
+
+        assert_eq!(permuted, &lu.l * &lu.u);
+        assert!((lu.determinant() + 4071.0).abs() < EPSILON);
+        assert!((lu.determinant() - a.determinant()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn lu_should_solve_linear_systems() {
+        let a = sample();
+        let x = [1.0, -2.0, 0.5, 3.0];
+        let b: Vec<f64> = (0..4)
+            .map(|i| (0..4).map(|j| a[(i, j)] * x[j]).sum())
+            .collect();
+        let solved = a.lu().unwrap().solve(&b);
+
+        for (e, s) in x.iter().zip(&solved) {
+            assert!((e - s).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn lu_should_reject_singular_and_non_square_matrices() {
+        let singular = Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+
+        assert_eq!(None, singular.lu());
+        assert_eq!(None, Matrix::zeros(2, 3).lu());
+    }
+
+    #[test]
+    fn qr_should_factor_into_orthogonal_and_triangular() {
+        let a = Matrix::from(vec![
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 2.0, 3.0],
+        ]);
+        let qr = a.qr().unwrap();
+
+        assert!(qr.q.is_orthogonal());
+        assert_eq!(a, &qr.q * &qr.r);
+
+        for i in 0..4 {
+            for j in 0..i.min(3) {
+                assert!(qr.r[(i, j)].abs() < EPSILON);
+            }
+        }
+        assert_eq!(None, Matrix::zeros(2, 3).qr());
+    }
+
+    #[test]
+    fn least_squares_should_reject_dependent_columns() {
+        let a = Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+
+        assert_eq!(None, a.qr().unwrap().least_squares(&[1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn singularity_should_not_depend_on_matrix_scale() {
+        for s in [1e-6, 1e6] {
+            let a = sample().map(|x| x * s);
+            let singular =
+                Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0 + 1e-12]]).map(|x| x * s);
+            let line =
+                Matrix::from(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]).map(|x| x * s);
+            let dependent =
+                Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0 + 1e-12]])
+                    .map(|x| x * s);
+
+            assert!((a.lu().unwrap().determinant() + 4071.0 * s.powi(4)).abs() < 1e-6 * s.powi(4));
+            assert_eq!(None, singular.lu());
+
+            let x = line
+                .qr()
+                .unwrap()
+                .least_squares(&[s, 3.0 * s, 5.0 * s])
+                .unwrap();
+            assert!((x[0] - 1.0).abs() < 1e-9 && (x[1] - 2.0).abs() < 1e-9);
+            assert_eq!(
+                None,
+                dependent
+                    .qr()
+                    .unwrap()
+                    .least_squares(&[s, 2.0 * s, 3.0 * s])
+            );
+        }
+    }
+}
@@ -1,18 +1,31 @@
 use super::Matrix;
 use super::Tuple;
-use crate::graphics::Material;
+use super::{solve_quadratic_within, Shape, EPSILON};
+use crate::graphics::{LightLinking, Material};
+use alloc::{vec, vec::Vec};
 
 /// Represents an individual ray
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ray {
     /// Point of origin of the ray
     origin: Tuple,
     /// Direction vector of the ray
     pub direction: Tuple,
+    /// Largest squared distance, in units of the ray parameter, by which the
+    /// ray may miss a curved surface and still graze it
+    #[cfg_attr(feature = "serde", serde(default = "default_tolerance"))]
+    tolerance: f64,
+}
+
+#[cfg(feature = "serde")]
+fn default_tolerance() -> f64 {
+    EPSILON
 }
 
 /// Represents a sphere object
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sphere {
     /// Origin point of the sphere
     origin: Tuple,
@@ -22,27 +35,78 @@ pub struct Sphere {
     pub transform: Matrix,
     /// Material of the sphere
     pub material: Material,
+    /// Lights illuminating the sphere
+    pub light_linking: LightLinking,
+    /// Whether the sphere is a holdout, rendered black and left out of the
+    /// coverage mask so it cuts a hole in the image for compositing
+    pub holdout: bool,
 }
 
 /// Aggregation of time and object that was intersected
-#[derive(Debug, PartialEq, Clone)]
+///
+/// Two intersections are equal when they occur at the same time on the very
+/// same object, not merely on objects with equal properties.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct Intersection<'a> {
     /// Time where an object was
     pub t: f64,
     /// Reference to intersected object
-    pub sphere: &'a Sphere,
-    _private: ()
+    pub object: &'a dyn Shape,
+    /// Horizontal surface coordinate of the intersection, if the object
+    /// provides one
+    pub u: Option<f64>,
+    /// Vertical surface coordinate of the intersection, if the object
+    /// provides one
+    pub v: Option<f64>,
 }
 
 impl Ray {
-    /// Returns a new ray with an origin and a direction
+    /// Returns a new ray with an origin and a direction, grazing curved
+    /// surfaces within `EPSILON`
     ///
     /// # Arguments
     ///
     /// * `origin` - point of origin of the ray
     /// * `direction` - direction vector of the ray
     pub fn new(origin: Tuple, direction: Tuple) -> Ray {
-        Ray { origin, direction }
+        Ray {
+            origin,
+            direction,
+            tolerance: EPSILON,
+        }
+    }
+
+    /// Returns the ray with the given grazing tolerance
+    ///
+    /// Quadratic intersections compare the tolerance against the squared
+    /// imaginary part of their roots, so it measures how far the ray may
+    /// pass outside a surface rather than the size of the equation
+    /// coefficients, and holds for shapes of any size. Worlds set it from
+    /// `World::intersection_epsilon` for every ray they cast.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - squared distance in units of the ray parameter
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::{Ray, Sphere, Tuple};
+    ///
+    /// // passes 0.0001 units over the top of the sphere
+    /// let ray = Ray::new(Tuple::point(0.0, 1.0001, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+    ///
+    /// assert!(ray.intersect(&Sphere::new()).is_empty());
+    /// assert_eq!(2, ray.with_tolerance(1e-3).intersect(&Sphere::new()).len());
+    /// ```
+    pub fn with_tolerance(self, tolerance: f64) -> Ray {
+        Ray { tolerance, ..self }
+    }
+
+    /// Returns the grazing tolerance of the ray, see `with_tolerance`
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
     }
 
     /// Returns the position as a point tuple of ray at time t
@@ -55,27 +119,13 @@ impl Ray {
     }
 
     /// Calculates and returns the points at which the ray intersects a given
-    /// sphere
+    /// shape
     ///
     /// # Arguments
     ///
-    /// * `s` - sphere to calculate intersections for
-    pub fn intersect<'a>(&self, s: &'a Sphere) -> Vec<Intersection<'a>> {
-        let r = self.transform(s.transform.inverse());
-        let v = r.origin - s.origin;
-        let a = Tuple::dot(&r.direction, &r.direction);
-        let b = 2.0 * Tuple::dot(&r.direction, &v);
-        let c = Tuple::dot(&v, &v) - 1.0;
-        let discriminant = b.powf(2.0) - 4.0 * a * c;
-
-        if discriminant < 0.0 {
-            return vec![];
-        }
-
-        let first = (-b - discriminant.sqrt()) / (2.0 * a);
-        let second = (-b + discriminant.sqrt()) / (2.0 * a);
-
-        vec![Intersection::new(first, &s), Intersection::new(second, &s)]
+    /// * `shape` - shape to calculate intersections for
+    pub fn intersect<'a>(&self, shape: &'a dyn Shape) -> Vec<Intersection<'a>> {
+        shape.local_intersect(&self.transform(shape.transformation().inverse()))
     }
 
     /// Returns a new ray transformed by the given transformation matrix
@@ -85,8 +135,9 @@ impl Ray {
     /// * `matrix` - transformation matrix to affect ray by
     pub fn transform(&self, matrix: Matrix) -> Ray {
         Ray {
-            origin: matrix.clone() * self.origin.clone(),
-            direction: matrix * self.direction.clone(),
+            origin: &matrix * &self.origin,
+            direction: matrix * self.direction,
+            tolerance: self.tolerance,
         }
     }
 }
@@ -101,8 +152,10 @@ impl Sphere {
         Sphere {
             origin: Tuple::point(0.0, 0.0, 0.0),
             radius: 1.0,
-            transform: Matrix::new(4, 4),
+            transform: Matrix::identity(4),
             material: Material::new(),
+            light_linking: LightLinking::All,
+            holdout: false,
         }
     }
 
@@ -114,6 +167,77 @@ impl Sphere {
     pub fn transform(&mut self, transform: Matrix) {
         self.transform = transform;
     }
+
+    /// Returns the normal vector of the sphere at given point
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point tuple in world space to calculate normal vector at
+    pub fn normal_at(&self, point: Tuple) -> Tuple {
+        Shape::normal_at(self, point)
+    }
+}
+
+impl Shape for Sphere {
+    fn transformation(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transformation(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn light_linking(&self) -> &LightLinking {
+        &self.light_linking
+    }
+
+    fn is_holdout(&self) -> bool {
+        self.holdout
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let v = ray.origin - self.origin;
+        let a = Tuple::dot(&ray.direction, &ray.direction);
+        let b = 2.0 * Tuple::dot(&ray.direction, &v);
+        let c = Tuple::dot(&v, &v) - 1.0;
+
+        if a == 0.0 {
+            return vec![];
+        }
+
+        let Some((first, second)) = solve_quadratic_within(a, b, c, ray.tolerance()) else {
+            return vec![];
+        };
+
+        vec![
+            Intersection::new(first, self),
+            Intersection::new(second, self),
+        ]
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        point - self.origin
+    }
+
+    fn local_bounds(&self) -> (Tuple, Tuple) {
+        let extent = Tuple::vector(self.radius, self.radius, self.radius);
+
+        (self.origin - extent, self.origin + extent)
+    }
+}
+
+impl Default for Sphere {
+    fn default() -> Sphere {
+        Sphere::new()
+    }
 }
 
 impl<'a> Intersection<'a> {
@@ -121,48 +245,78 @@ impl<'a> Intersection<'a> {
     ///
     /// # Arguments
     ///
-    /// * `sphere` - reference to intersected object
-    pub fn new(t: f64, sphere: &'a Sphere) -> Intersection {
-        Intersection { t, sphere, _private: ()}
+    /// * `t` - time of the intersection
+    /// * `object` - reference to intersected object
+    pub fn new(t: f64, object: &'a dyn Shape) -> Intersection<'a> {
+        Intersection {
+            t,
+            object,
+            u: None,
+            v: None,
+        }
     }
 
-    /// Returns the first nonnegative intersection as a hit
+    /// Returns a new intersection carrying the surface coordinates of the
+    /// point that was hit
     ///
     /// # Arguments
     ///
-    /// * `intersections` - vector of intersections to sort from
-    pub fn hit<'b>(intersections: &'b Vec<Intersection>) -> Option<Intersection<'b>> {
-        let tmp: Vec<Intersection> = intersections
-            .clone()
-            .into_iter()
-            .filter(|i| i.t >= 0.0)
-            .collect();
-
-        if tmp.len() == 0 {
-            return None;
+    /// * `t` - time of the intersection
+    /// * `object` - reference to intersected object
+    /// * `u` - horizontal surface coordinate
+    /// * `v` - vertical surface coordinate
+    pub fn with_uv(t: f64, object: &'a dyn Shape, u: f64, v: f64) -> Intersection<'a> {
+        Intersection {
+            t,
+            object,
+            u: Some(u),
+            v: Some(v),
         }
+    }
 
-        let mut min = tmp[0].clone();
+    /// Returns true if both intersections refer to the same object instance
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - intersection to compare with
+    pub fn is_same_object(&self, other: &Intersection) -> bool {
+        core::ptr::addr_eq(self.object, other.object)
+    }
 
-        for i in tmp {
-            if i.t < min.t {
-                min = i;
-            }
-        }
+    /// Returns the first nonnegative intersection as a hit
+    ///
+    /// # Arguments
+    ///
+    /// * `intersections` - vector of intersections to sort from
+    pub fn hit<'b>(intersections: &[Intersection<'b>]) -> Option<Intersection<'b>> {
+        intersections
+            .iter()
+            .filter(|i| i.t >= 0.0)
+            .min_by(|a, b| a.t.total_cmp(&b.t))
+            .copied()
+    }
+}
 
-        Some(min)
+impl PartialEq for Intersection<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.t == other.t && self.u == other.u && self.v == other.v && self.is_same_object(other)
     }
 }
 
+/// Orders intersections by time, leaving distinct intersections at the same
+/// time unordered so that the order agrees with `PartialEq`
 impl PartialOrd for Intersection<'_> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.t.partial_cmp(&other.t)
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        match self.t.partial_cmp(&other.t)? {
+            core::cmp::Ordering::Equal if self != other => None,
+            ordering => Some(ordering),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::transformations;
+    use super::super::{transformations, EPSILON};
     use super::*;
 
     #[test]
@@ -181,10 +335,15 @@ mod tests {
 
         assert_eq!(Tuple::point(0.0, 0.0, 0.0), s.origin);
         assert_eq!(1.0, s.radius);
-        assert_eq!(Matrix::new(4, 4), s.transform);
+        assert_eq!(Matrix::identity(4), s.transform);
         assert_eq!(Material::new(), s.material);
     }
 
+    #[test]
+    fn default_sphere_should_be_a_new_sphere() {
+        assert_eq!(Sphere::new(), Sphere::default());
+    }
+
     #[test]
     fn position_should_return_correct_position_at_certain_times() {
         let ray = Ray::new(Tuple::point(2.0, 3.0, 4.0), Tuple::vector(1.0, 0.0, 0.0));
@@ -254,7 +413,31 @@ mod tests {
         let i = Intersection::new(3.5, &s);
 
         assert_eq!(3.5, i.t);
-        assert_eq!(&s, i.sphere);
+        assert!(core::ptr::addr_eq(&s, i.object));
+    }
+
+    #[test]
+    fn intersection_should_carry_surface_coordinates() {
+        let s = Sphere::new();
+        let i = Intersection::with_uv(3.5, &s, 0.2, 0.4);
+
+        assert_eq!(3.5, i.t);
+        assert_eq!(Some(0.2), i.u);
+        assert_eq!(Some(0.4), i.v);
+        assert_eq!(None, Intersection::new(3.5, &s).u);
+        assert_eq!(None, Intersection::new(3.5, &s).v);
+    }
+
+    #[test]
+    fn intersections_should_compare_object_identity() {
+        let a = Sphere::new();
+        let b = Sphere::new();
+
+        assert_eq!(a, b);
+        assert_eq!(Intersection::new(1.0, &a), Intersection::new(1.0, &a));
+        assert_ne!(Intersection::new(1.0, &a), Intersection::new(1.0, &b));
+        assert!(Intersection::new(1.0, &a).is_same_object(&Intersection::new(2.0, &a)));
+        assert!(!Intersection::new(1.0, &a).is_same_object(&Intersection::new(1.0, &b)));
     }
 
     #[test]
@@ -262,7 +445,7 @@ mod tests {
         let s = Sphere::new();
         let a = Intersection::new(1.0, &s);
         let b = Intersection::new(2.0, &s);
-        let intersections = vec![a, b];
+        let intersections = [a, b];
 
         assert_eq!(2, intersections.len());
         assert_eq!(1.0, intersections[0].t);
@@ -276,8 +459,27 @@ mod tests {
         let intersections = r.intersect(&s);
 
         assert_eq!(2, intersections.len());
-        assert_eq!(&s, intersections[0].sphere);
-        assert_eq!(&s, intersections[1].sphere);
+        assert!(core::ptr::addr_eq(&s, intersections[0].object));
+        assert!(core::ptr::addr_eq(&s, intersections[1].object));
+    }
+
+    #[test]
+    fn ordering_should_agree_with_equality() {
+        use core::cmp::Ordering;
+
+        let (s, other) = (Sphere::new(), Sphere::new());
+        let a = Intersection::new(1.0, &s);
+
+        assert!(a < Intersection::new(2.0, &s));
+        assert_eq!(
+            Some(Ordering::Equal),
+            a.partial_cmp(&Intersection::new(1.0, &s))
+        );
+        assert_eq!(None, a.partial_cmp(&Intersection::new(1.0, &other)));
+        assert_eq!(
+            None,
+            a.partial_cmp(&Intersection::with_uv(1.0, &s, 0.5, 0.5))
+        );
     }
 
     #[test]
@@ -285,21 +487,21 @@ mod tests {
         let s = Sphere::new();
         let a = Intersection::new(1.0, &s);
         let b = Intersection::new(2.0, &s);
-        let intersections = vec![a.clone(), b.clone()];
+        let intersections = vec![a, b];
         let hit = Intersection::hit(&intersections);
 
         assert_eq!(Some(a), hit);
 
         let a = Intersection::new(-1.0, &s);
         let b = Intersection::new(1.0, &s);
-        let intersections = vec![a.clone(), b.clone()];
+        let intersections = vec![a, b];
         let hit = Intersection::hit(&intersections);
 
         assert_eq!(Some(b), hit);
 
         let a = Intersection::new(-2.0, &s);
         let b = Intersection::new(-1.0, &s);
-        let intersections = vec![a.clone(), b.clone()];
+        let intersections = vec![a, b];
         let hit = Intersection::hit(&intersections);
 
         assert_eq!(None, hit);
@@ -308,7 +510,7 @@ mod tests {
         let b = Intersection::new(7.0, &s);
         let c = Intersection::new(-3.0, &s);
         let d = Intersection::new(2.0, &s);
-        let intersections = vec![a.clone(), b.clone(), c.clone(), d.clone()];
+        let intersections = vec![a, b, c, d];
         let hit = Intersection::hit(&intersections);
 
         assert_eq!(Some(d), hit);
@@ -337,7 +539,7 @@ mod tests {
     #[test]
     fn sphere_should_contain_correct_transformation() {
         let s = Sphere::new();
-        assert_eq!(s.transform, Matrix::new(4, 4));
+        assert_eq!(s.transform, Matrix::identity(4));
 
         let mut s = Sphere::new();
         let t = transformations::translation(2.0, 3.0, 4.0);
@@ -357,6 +559,16 @@ mod tests {
         assert_eq!(7.0, intersections[1].t);
     }
 
+    #[test]
+    fn should_compute_normal_without_consuming_the_sphere() {
+        let mut s = Sphere::new();
+        s.transform(transformations::scaling(2.0, 2.0, 2.0));
+        let n = s.normal_at(Tuple::point(0.0, 0.0, -2.0));
+
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), n);
+        assert_eq!(transformations::scaling(2.0, 2.0, 2.0), s.transform);
+    }
+
     #[test]
     fn should_intersect_a_translated_sphere_correctly() {
         let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
@@ -366,4 +578,60 @@ mod tests {
 
         assert_eq!(0, intersections.len());
     }
+
+    #[test]
+    fn bounding_corners_should_follow_transform() {
+        let mut s = Sphere::new();
+        s.transform(
+            transformations::translation(1.0, 0.0, 0.0) * transformations::scaling(2.0, 1.0, 1.0),
+        );
+        let corners = s.bounding_corners();
+
+        assert_eq!(Tuple::point(-1.0, -1.0, -1.0), corners[0]);
+        assert_eq!(Tuple::point(3.0, -1.0, -1.0), corners[1]);
+        assert_eq!(Tuple::point(3.0, 1.0, 1.0), corners[7]);
+    }
+
+    #[test]
+    fn should_treat_nearly_tangent_rays_as_grazing() {
+        let s = Sphere::new();
+        let below = Ray::new(
+            Tuple::point(0.0, 1.0 + 1e-7, -5.0),
+            Tuple::vector(0.0, 0.0, 1.0),
+        );
+        let xs = below.intersect(&s);
+
+        assert_eq!(2, xs.len());
+        assert!((xs[0].t - 5.0).abs() < EPSILON);
+        assert!((xs[1].t - 5.0).abs() < EPSILON);
+
+        let above = Ray::new(Tuple::point(0.0, 1.01, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(above.intersect(&s).is_empty());
+    }
+
+    #[test]
+    fn should_intersect_large_spheres() {
+        for radius in [400.0, 1000.0, 1.0e6] {
+            let mut s = Sphere::new();
+            s.transform(transformations::scaling(radius, radius, radius));
+            let r = Ray::new(
+                Tuple::point(0.0, 0.0, -5.0 * radius),
+                Tuple::vector(0.0, 0.0, 1.0),
+            );
+            let xs = r.intersect(&s);
+
+            assert_eq!(2, xs.len());
+            assert!((xs[0].t / radius - 4.0).abs() < EPSILON);
+            assert!((xs[1].t / radius - 6.0).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn should_miss_with_degenerate_direction() {
+        let s = Sphere::new();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 0.0));
+
+        assert!(r.intersect(&s).is_empty());
+    }
 }
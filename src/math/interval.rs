@@ -0,0 +1,135 @@
+//! Intervals of ray times
+
+/// Closed range of values, used to bound the times along a ray at which
+/// intersections are accepted
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    /// Smallest value in the interval
+    pub min: f64,
+    /// Largest value in the interval
+    pub max: f64,
+}
+
+impl Interval {
+    /// Interval containing no values
+    pub const EMPTY: Interval = Interval {
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+
+    /// Interval containing all values
+    pub const UNIVERSE: Interval = Interval {
+        min: f64::NEG_INFINITY,
+        max: f64::INFINITY,
+    };
+
+    /// Interval of all times in front of a ray origin
+    pub const FORWARD: Interval = Interval {
+        min: 0.0,
+        max: f64::INFINITY,
+    };
+
+    /// Returns a new interval between the given bounds
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - smallest value in the interval
+    /// * `max` - largest value in the interval
+    pub fn new(min: f64, max: f64) -> Interval {
+        Interval { min, max }
+    }
+
+    /// Returns true if the interval contains no values
+    pub fn is_empty(&self) -> bool {
+        self.min > self.max
+    }
+
+    /// Returns the distance between the bounds, zero for empty intervals
+    pub fn size(&self) -> f64 {
+        (self.max - self.min).max(0.0)
+    }
+
+    /// Returns true if the value lies within the interval, bounds included
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - value to test
+    pub fn contains(&self, x: f64) -> bool {
+        self.min <= x && x <= self.max
+    }
+
+    /// Returns true if the value lies strictly within the interval
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - value to test
+    pub fn surrounds(&self, x: f64) -> bool {
+        self.min < x && x < self.max
+    }
+
+    /// Returns the value moved into the interval
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - value to clamp
+    pub fn clamp(&self, x: f64) -> f64 {
+        x.max(self.min).min(self.max)
+    }
+
+    /// Returns the values contained in both intervals
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - interval to intersect with
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Interval::new(self.min.max(other.min), self.max.min(other.max))
+    }
+
+    /// Returns the smallest interval containing both intervals
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - interval to enclose
+    pub fn union(&self, other: &Interval) -> Interval {
+        Interval::new(self.min.min(other.min), self.max.max(other.max))
+    }
+}
+
+impl Default for Interval {
+    fn default() -> Interval {
+        Interval::EMPTY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_test_membership() {
+        let i = Interval::new(1.0, 3.0);
+
+        assert!(i.contains(1.0) && i.contains(3.0));
+        assert!(!i.surrounds(1.0) && i.surrounds(2.0));
+        assert!(!i.contains(3.5));
+        assert_eq!(2.0, i.size());
+        assert!(Interval::EMPTY.is_empty());
+        assert!(!Interval::EMPTY.contains(0.0));
+        assert!(Interval::UNIVERSE.contains(-1e300));
+        assert!(!Interval::FORWARD.contains(-0.1));
+    }
+
+    #[test]
+    fn should_clamp_and_combine() {
+        let a = Interval::new(1.0, 3.0);
+        let b = Interval::new(2.0, 5.0);
+
+        assert_eq!(1.0, a.clamp(-4.0));
+        assert_eq!(3.0, a.clamp(4.0));
+        assert_eq!(Interval::new(2.0, 3.0), a.intersection(&b));
+        assert_eq!(Interval::new(1.0, 5.0), a.union(&b));
+        assert!(a.intersection(&Interval::new(4.0, 5.0)).is_empty());
+        assert_eq!(0.0, Interval::EMPTY.size());
+    }
+}
@@ -1,13 +1,17 @@
-use crate::math::Tuple;
-use std::convert::From;
-use std::ops::{Index, IndexMut, Mul};
-
-const EPSILON: f64 = 0.00001;
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::EPSILON;
+use crate::math::{shearing, Angle, Tuple};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::convert::From;
+use core::fmt;
+use core::ops::{Index, IndexMut, Mul};
 
 /// Matrix representation
 ///
 /// This struct can be multiplied
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix {
     /// No. rows in the Matrix
     rows: usize,
@@ -15,31 +19,293 @@ pub struct Matrix {
     cols: usize,
     /// Internal matrix representation
     matrix: Vec<Vec<f64>>,
-    _private: (),
+}
+
+/// Error returned when multiplying matrices whose shapes do not fit
+/// together, i.e. the columns of the left matrix differ from the rows of the
+/// right one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShapeMismatch {
+    /// Rows and columns of the left matrix
+    pub left: (usize, usize),
+    /// Rows and columns of the right matrix
+    pub right: (usize, usize),
 }
 
 impl Matrix {
-    /// Returns a new identity Matrix with given size
+    /// Returns a new Matrix with given size, ones on its main diagonal and
+    /// zeros everywhere else
     ///
     /// # Arguemnts:
     ///
     /// * `rows` - no. rows in the Matrix
     /// * `cols` - no. cols in the Matrix
+    #[deprecated(note = "use `Matrix::identity` or `Matrix::zeros` instead")]
     pub fn new(rows: usize, cols: usize) -> Matrix {
-        let mut matrix = vec![vec![0.0; cols]; rows];
+        let mut m = Matrix::zeros(rows, cols);
 
-        for i in 0..rows {
-            matrix[i][i] = 1.0;
+        for i in 0..rows.min(cols) {
+            m[(i, i)] = 1.0;
         }
 
+        m
+    }
+
+    /// Returns a new square identity Matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - no. rows and cols in the Matrix
+    pub fn identity(size: usize) -> Matrix {
+        Matrix::from_diagonal(&vec![1.0; size])
+    }
+
+    /// Returns a new Matrix with given size filled with zeros
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - no. rows in the Matrix
+    /// * `cols` - no. cols in the Matrix
+    pub fn zeros(rows: usize, cols: usize) -> Matrix {
         Matrix {
             rows,
             cols,
+            matrix: vec![vec![0.0; cols]; rows],
+        }
+    }
+
+    /// Returns a new square Matrix with the given values on its main
+    /// diagonal and zeros everywhere else
+    ///
+    /// # Arguments
+    ///
+    /// * `diagonal` - values of the main diagonal, from the top left
+    pub fn from_diagonal(diagonal: &[f64]) -> Matrix {
+        let mut m = Matrix::zeros(diagonal.len(), diagonal.len());
+
+        for (i, &value) in diagonal.iter().enumerate() {
+            m[(i, i)] = value;
+        }
+
+        m
+    }
+
+    /// Returns a new Matrix built from the given columns
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - columns of the Matrix, from left to right, all of the
+    ///   same length
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns differ in length
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Matrix;
+    ///
+    /// let m = Matrix::from_columns(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    ///
+    /// assert_eq!(Matrix::from(vec![vec![1.0, 3.0], vec![2.0, 4.0]]), m);
+    /// ```
+    pub fn from_columns(columns: Vec<Vec<f64>>) -> Matrix {
+        let rows = columns.first().map_or(0, Vec::len);
+
+        assert!(
+            columns.iter().all(|column| column.len() == rows),
+            "matrix columns differ in length"
+        );
+
+        let mut m = Matrix::zeros(rows, columns.len());
+
+        for (j, column) in columns.iter().enumerate() {
+            for (i, &value) in column.iter().enumerate() {
+                m[(i, j)] = value;
+            }
+        }
+
+        m
+    }
+
+    /// Returns the product of the matrix with another one, or an error if
+    /// the number of columns of this matrix differs from the number of rows
+    /// of the other one
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - matrix to multiply with from the right
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Matrix;
+    ///
+    /// let a = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    /// let b = Matrix::from(vec![vec![1.0], vec![0.0], vec![2.0]]);
+    ///
+    /// assert_eq!(Ok(Matrix::from(vec![vec![7.0], vec![16.0]])), a.checked_mul(&b));
+    /// assert_eq!((3, 1), b.checked_mul(&a).unwrap_err().left);
+    /// ```
+    pub fn checked_mul(&self, rhs: &Matrix) -> Result<Matrix, ShapeMismatch> {
+        if self.cols != rhs.rows {
+            return Err(ShapeMismatch {
+                left: self.dimensions(),
+                right: rhs.dimensions(),
+            });
+        }
+
+        let matrix = (0..self.rows)
+            .map(|i| {
+                (0..rhs.cols)
+                    .map(|j| (0..self.cols).map(|k| self[(i, k)] * rhs[(k, j)]).sum())
+                    .collect()
+            })
+            .collect();
+
+        Ok(Matrix {
+            rows: self.rows,
+            cols: rhs.cols,
             matrix,
-            _private: (),
+        })
+    }
+
+    /// Returns the no. rows and cols in the Matrix
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Returns the elements of the given row
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - index of the row
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is out of bounds
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.matrix[i]
+    }
+
+    /// Returns the elements of the given column, from top to bottom
+    ///
+    /// # Arguments
+    ///
+    /// * `j` - index of the column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is out of bounds
+    pub fn col(&self, j: usize) -> Vec<f64> {
+        assert!(j < self.cols, "column {} out of bounds", j);
+
+        self.matrix.iter().map(|row| row[j]).collect()
+    }
+
+    /// Returns an iterator over the rows of the Matrix, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        self.matrix.iter().map(Vec::as_slice)
+    }
+
+    /// Returns an iterator over all elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &f64> {
+        self.matrix.iter().flatten()
+    }
+
+    /// Returns an iterator over mutable references to all elements in
+    /// row-major order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f64> {
+        self.matrix.iter_mut().flatten()
+    }
+
+    /// Returns a new Matrix of the same size with the given function applied
+    /// to every element
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - function to apply to each element
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Matrix;
+    ///
+    /// let m = Matrix::identity(2).map(|x| x * 3.0 + 1.0);
+    ///
+    /// assert_eq!(Matrix::from(vec![vec![4.0, 1.0], vec![1.0, 4.0]]), m);
+    /// ```
+    pub fn map<F: Fn(f64) -> f64>(&self, f: F) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            matrix: self
+                .matrix
+                .iter()
+                .map(|row| row.iter().map(|&x| f(x)).collect())
+                .collect(),
         }
     }
 
+    /// Returns the sum of the elements on the main diagonal
+    pub fn trace(&self) -> f64 {
+        (0..self.rows.min(self.cols)).map(|i| self[(i, i)]).sum()
+    }
+
+    /// Returns the Frobenius norm, the square root of the sum of all
+    /// squared elements
+    pub fn norm(&self) -> f64 {
+        self.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    /// Returns true if the Matrix is square with ones on its main diagonal
+    /// and zeros everywhere else, within `EPSILON`
+    pub fn is_identity(&self) -> bool {
+        self.approx_eq(&Matrix::identity(self.rows))
+    }
+
+    /// Returns true if the Matrix is square and equals its transpose within
+    /// `EPSILON`
+    pub fn is_symmetric(&self) -> bool {
+        self.rows == self.cols && self.approx_eq(&self.transpose())
+    }
+
+    /// Returns true if the Matrix is square and its rows are orthonormal
+    /// within `EPSILON`, i.e. it only rotates and reflects
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::{rotation_y, scaling};
+    ///
+    /// assert!(rotation_y(0.3).is_orthogonal());
+    /// assert!(!scaling(2.0, 1.0, 1.0).is_orthogonal());
+    /// ```
+    pub fn is_orthogonal(&self) -> bool {
+        self.rows == self.cols && (self * &self.transpose()).is_identity()
+    }
+
+    /// Returns true if both matrices have the same size and all elements
+    /// differ by less than `EPSILON`
+    fn approx_eq(&self, other: &Matrix) -> bool {
+        self.dimensions() == other.dimensions()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| (a - b).abs() < EPSILON)
+    }
+
+    /// Returns the number of bytes allocated on the heap for the elements
+    pub(crate) fn heap_size(&self) -> usize {
+        self.matrix.capacity() * size_of::<Vec<f64>>()
+            + self
+                .matrix
+                .iter()
+                .map(|row| row.capacity() * size_of::<f64>())
+                .sum::<usize>()
+    }
+
     /// Transposes Matrix and returns a new Matrix
     ///
     /// # Example
@@ -70,16 +336,18 @@ impl Matrix {
 
         for i in 0..self.rows {
             for j in 0..self.cols {
-                new[i][j] = self[(j, i)]
+                new[j][i] = self[(i, j)]
             }
         }
 
         Matrix::from(new)
     }
 
-    /// Returns the determinant of the Matrix
+    /// Returns the determinant of the Matrix, one for an empty one
     pub fn determinant(&self) -> f64 {
         match self.rows {
+            0 => 1.0,
+            1 => self[(0, 0)],
             2 => self[(0, 0)] * self[(1, 1)] - self[(0, 1)] * self[(1, 0)],
             _ => {
                 let mut sum = 0.0;
@@ -99,8 +367,8 @@ impl Matrix {
 
         matrix.remove(row);
 
-        for i in 0..matrix.len() {
-            matrix[i].remove(column);
+        for row in matrix.iter_mut() {
+            row.remove(column);
         }
 
         Matrix::from(matrix)
@@ -131,11 +399,12 @@ impl Matrix {
             panic!("Matrix is not invertible");
         }
 
+        let determinant = self.determinant();
         let mut matrix = vec![vec![0.0; self.rows]; self.cols];
 
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                matrix[col][row] = self.cofactor(row, col) / self.determinant();
+        for (col, new_row) in matrix.iter_mut().enumerate() {
+            for (row, value) in new_row.iter_mut().enumerate() {
+                *value = self.cofactor(row, col) / determinant;
             }
         }
 
@@ -150,7 +419,7 @@ impl Matrix {
     /// * `y` - units in y axis
     /// * `z` - units in z axis
     pub fn translate(&self, x: f64, y: f64, z: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
+        let mut m = Matrix::identity(4);
 
         m[(0, 3)] = x;
         m[(1, 3)] = y;
@@ -167,7 +436,7 @@ impl Matrix {
     /// * `y` - scaling in y axis
     /// * `z` - scaling in z axis
     pub fn scale(&self, x: f64, y: f64, z: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
+        let mut m = Matrix::identity(4);
 
         m[(0, 0)] = x;
         m[(1, 1)] = y;
@@ -180,14 +449,15 @@ impl Matrix {
     ///
     /// # Arguments:
     ///
-    /// * `rads` - radians to rotate
-    pub fn rotate_x(&self, rads: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_x(&self, angle: impl Into<Angle>) -> Matrix {
+        let angle = angle.into();
+        let mut m = Matrix::identity(4);
 
-        m[(1, 1)] = rads.cos();
-        m[(2, 1)] = rads.sin();
-        m[(1, 2)] = -rads.sin();
-        m[(2, 2)] = rads.cos();
+        m[(1, 1)] = angle.cos();
+        m[(2, 1)] = angle.sin();
+        m[(1, 2)] = -angle.sin();
+        m[(2, 2)] = angle.cos();
 
         self * m
     }
@@ -196,14 +466,15 @@ impl Matrix {
     ///
     /// # Arguments:
     ///
-    /// * `rads` - radians to rotate
-    pub fn rotate_y(&self, rads: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_y(&self, angle: impl Into<Angle>) -> Matrix {
+        let angle = angle.into();
+        let mut m = Matrix::identity(4);
 
-        m[(0, 0)] = rads.cos();
-        m[(2, 0)] = -rads.sin();
-        m[(0, 2)] = rads.sin();
-        m[(2, 2)] = rads.cos();
+        m[(0, 0)] = angle.cos();
+        m[(2, 0)] = -angle.sin();
+        m[(0, 2)] = angle.sin();
+        m[(2, 2)] = angle.cos();
 
         self * m
     }
@@ -212,19 +483,24 @@ impl Matrix {
     ///
     /// # Arguments:
     ///
-    /// * `rads` - radians to rotate
-    pub fn rotate_z(&self, rads: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_z(&self, angle: impl Into<Angle>) -> Matrix {
+        let angle = angle.into();
+        let mut m = Matrix::identity(4);
 
-        m[(0, 0)] = rads.cos();
-        m[(1, 0)] = rads.sin();
-        m[(0, 1)] = -rads.sin();
-        m[(1, 1)] = rads.cos();
+        m[(0, 0)] = angle.cos();
+        m[(1, 0)] = angle.sin();
+        m[(0, 1)] = -angle.sin();
+        m[(1, 1)] = angle.cos();
 
         self * m
     }
 
-    /// Skews the matrix
+    /// Returns the matrix preceded by a shearing, moving each component in
+    /// proportion to the other two
+    ///
+    /// Points are sheared first and then transformed by the matrix, as with
+    /// `translate`, `scale` and the rotations.
     ///
     /// # Arguments:
     ///
@@ -234,40 +510,51 @@ impl Matrix {
     /// * `yz` - y in proportion to z
     /// * `zx` - z in proportion to x
     /// * `zy` - z in proportion to y
-    pub fn skew(&self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
-        let mut m = Matrix::new(4, 4);
-
-        m[(1, 0)] = yx;
-        m[(2, 0)] = zx;
-        m[(0, 1)] = xy;
-        m[(2, 1)] = zy;
-        m[(0, 2)] = xz;
-        m[(1, 2)] = yz;
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::{Matrix, Tuple};
+    ///
+    /// let transform = Matrix::identity(4)
+    ///     .translate(0.0, 1.0, 0.0)
+    ///     .shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    ///
+    /// // sheared to (5, 3, 4), then moved up
+    /// assert_eq!(Tuple::point(5.0, 4.0, 4.0), transform * Tuple::point(2.0, 3.0, 4.0));
+    /// ```
+    pub fn shear(&self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+        self * shearing(xy, xz, yx, yz, zx, zy)
+    }
 
-        self * m
+    /// Returns the matrix preceded by a shearing, see `shear`
+    ///
+    /// # Arguments:
+    ///
+    /// * `xy` - x in proportion to y
+    /// * `xz` - x in proportion to z
+    /// * `yx` - y in proportion to x
+    /// * `yz` - y in proportion to z
+    /// * `zx` - z in proportion to x
+    /// * `zy` - z in proportion to y
+    #[deprecated(note = "renamed to `shear`, as in The Ray Tracer Challenge")]
+    pub fn skew(&self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+        self.shear(xy, xz, yx, yz, zx, zy)
     }
 }
 
 impl Mul<&Matrix> for &Matrix {
     type Output = Matrix;
 
+    /// # Panics
+    ///
+    /// Panics if the number of columns of the left matrix differs from the
+    /// number of rows of the right one
     fn mul(self, rhs: &Matrix) -> Matrix {
-        if self.matrix.len() != 4 && self.matrix[0].len() != 4 {
-            panic!("Incorrect matrix shape");
-        }
-
-        let mut m = Matrix::new(self.matrix.len(), self.matrix[0].len());
-
-        for i in 0..4 {
-            for j in 0..4 {
-                m[(i, j)] = self[(i, 0)] * rhs[(0, j)]
-                    + self[(i, 1)] * rhs[(1, j)]
-                    + self[(i, 2)] * rhs[(2, j)]
-                    + self[(i, 3)] * rhs[(3, j)];
-            }
+        match self.checked_mul(rhs) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
         }
-
-        m
     }
 }
 
@@ -275,22 +562,7 @@ impl Mul for Matrix {
     type Output = Matrix;
 
     fn mul(self, rhs: Matrix) -> Matrix {
-        if self.matrix.len() != 4 && self.matrix[0].len() != 4 {
-            panic!("Incorrect matrix shape");
-        }
-
-        let mut m = Matrix::new(self.matrix.len(), self.matrix[0].len());
-
-        for i in 0..4 {
-            for j in 0..4 {
-                m[(i, j)] = self[(i, 0)] * rhs[(0, j)]
-                    + self[(i, 1)] * rhs[(1, j)]
-                    + self[(i, 2)] * rhs[(2, j)]
-                    + self[(i, 3)] * rhs[(3, j)];
-            }
-        }
-
-        m
+        &self * &rhs
     }
 }
 
@@ -298,22 +570,7 @@ impl Mul<Matrix> for &Matrix {
     type Output = Matrix;
 
     fn mul(self, rhs: Matrix) -> Matrix {
-        if self.matrix.len() != 4 && self.matrix[0].len() != 4 {
-            panic!("Incorrect matrix shape");
-        }
-
-        let mut m = Matrix::new(self.matrix.len(), self.matrix[0].len());
-
-        for i in 0..4 {
-            for j in 0..4 {
-                m[(i, j)] = self[(i, 0)] * rhs[(0, j)]
-                    + self[(i, 1)] * rhs[(1, j)]
-                    + self[(i, 2)] * rhs[(2, j)]
-                    + self[(i, 3)] * rhs[(3, j)];
-            }
-        }
-
-        m
+        self * &rhs
     }
 }
 
@@ -379,9 +636,8 @@ impl From<Vec<Vec<f64>>> for Matrix {
     fn from(matrix: Vec<Vec<f64>>) -> Matrix {
         Matrix {
             rows: matrix.len(),
-            cols: matrix[0].len(),
+            cols: matrix.first().map_or(0, Vec::len),
             matrix,
-            _private: (),
         }
     }
 }
@@ -392,34 +648,95 @@ impl From<Vec<f64>> for Matrix {
             rows: 1,
             cols: vec.len(),
             matrix: vec![vec],
-            _private: (),
         }
     }
 }
 
+impl fmt::Display for Matrix {
+    /// Formats the matrix row by row with aligned columns, rounded to the
+    /// formatter precision or five decimals
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(5);
+        let cells: Vec<Vec<String>> = self
+            .matrix
+            .iter()
+            .map(|row| row.iter().map(|v| format_f64(*v, precision)).collect())
+            .collect();
+        let widths: Vec<usize> = (0..self.cols)
+            .map(|j| cells.iter().map(|row| row[j].len()).max().unwrap_or(0))
+            .collect();
+
+        for (i, row) in cells.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "|")?;
+
+            for (cell, width) in row.iter().zip(&widths) {
+                write!(f, " {:>width$} |", cell, width = width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a float rounded to the given number of decimals, without printing
+/// negative zero
+pub(crate) fn format_f64(value: f64, precision: usize) -> String {
+    let factor = 10.0_f64.powi(precision as i32);
+    let rounded = (value * factor).round() / factor + 0.0;
+
+    format!("{:.*}", precision, rounded)
+}
+
 impl PartialEq for Matrix {
     fn eq(&self, other: &Matrix) -> bool {
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                if (self[(i, j)] - other[(i, j)]).abs() < EPSILON {
-                    continue;
-                } else {
-                    eprintln!(
-                        "{} - {} = {} < {}",
-                        self[(i, j)],
-                        other[(i, j)],
-                        self[(i, j)] - other[(i, j)],
-                        f64::EPSILON
-                    );
-                    return false;
-                }
-            }
+        if self.dimensions() != other.dimensions() {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                left = ?self.dimensions(),
+                right = ?other.dimensions(),
+                "matrices differ in shape"
+            );
+            return false;
         }
 
-        true
+        let equal = self.approx_eq(other);
+
+        #[cfg(feature = "tracing")]
+        if !equal {
+            let (i, j) = (0..self.rows)
+                .flat_map(|i| (0..self.cols).map(move |j| (i, j)))
+                .find(|&(i, j)| (self[(i, j)] - other[(i, j)]).abs() >= EPSILON)
+                .unwrap_or_default();
+
+            tracing::trace!(
+                row = i,
+                column = j,
+                left = self[(i, j)],
+                right = other[(i, j)],
+                "matrices differ"
+            );
+        }
+
+        equal
+    }
+}
+
+impl fmt::Display for ShapeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot multiply a {}x{} matrix by a {}x{} matrix",
+            self.left.0, self.left.1, self.right.0, self.right.1
+        )
     }
 }
 
+impl core::error::Error for ShapeMismatch {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -545,7 +862,7 @@ mod tests {
         ];
         let reference = Matrix::from(a.clone());
         let a = Matrix::from(a);
-        let identity = Matrix::new(4, 4);
+        let identity = Matrix::identity(4);
 
         assert_eq!(reference, a * identity);
     }
@@ -570,11 +887,30 @@ mod tests {
 
         assert_eq!(reference, a.transpose());
 
-        let identity = Matrix::new(4, 4);
+        let identity = Matrix::identity(4);
 
         assert_eq!(identity, identity.transpose());
     }
 
+    #[test]
+    fn should_invert_single_element_matrices() {
+        let a = Matrix::from(vec![vec![4.0]]);
+
+        assert_eq!(4.0, a.determinant());
+        assert_eq!(1.0, a.cofactor(0, 0));
+        assert_eq!(Matrix::from(vec![vec![0.25]]), a.inverse());
+        assert_eq!(Matrix::identity(1), Matrix::identity(1).inverse());
+    }
+
+    #[test]
+    fn should_transpose_non_square_matrices() {
+        let a = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let reference = Matrix::from(vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]);
+
+        assert_eq!(reference, a.transpose());
+        assert_eq!(a, a.transpose().transpose());
+    }
+
     #[test]
     fn should_compute_correct_determinant_for_2_by_2() {
         let a = vec![vec![1.0, 5.0], vec![-3.0, 2.0]];
@@ -650,9 +986,9 @@ mod tests {
         ];
         let mut b = vec![vec![0.0; 4]; 4];
 
-        for i in 0..4 {
-            for j in 0..4 {
-                b[i][j] = a.cofactor(i, j);
+        for (i, row) in b.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = a.cofactor(i, j);
             }
         }
 
@@ -699,7 +1035,7 @@ mod tests {
         let a = Matrix::from(a);
 
         assert_eq!(-2120.0, a.determinant());
-        assert_eq!(true, a.is_invertible());
+        assert!(a.is_invertible());
 
         let a = vec![
             vec![-4.0, 2.0, -2.0, -3.0],
@@ -710,7 +1046,7 @@ mod tests {
         let a = Matrix::from(a);
 
         assert_eq!(0.0, a.determinant());
-        assert_eq!(false, a.is_invertible());
+        assert!(!a.is_invertible());
     }
 
     #[test]
@@ -761,4 +1097,152 @@ mod tests {
 
         assert_eq!(c * (b.inverse()), a);
     }
+
+    #[test]
+    fn should_display_aligned_rounded_rows() {
+        let a = Matrix::from(vec![vec![1.0, -2.5, 0.333333], vec![-0.000001, 10.0, 4.0]]);
+
+        assert_eq!(
+            "| 1.00000 | -2.50000 | 0.33333 |\n| 0.00000 | 10.00000 | 4.00000 |",
+            format!("{}", a)
+        );
+        assert_eq!(
+            "| 1.0 | -2.5 | 0.3 |\n| 0.0 | 10.0 | 4.0 |",
+            format!("{:.1}", a)
+        );
+    }
+
+    #[test]
+    fn should_multiply_non_square_matrices() {
+        let a = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        let b = Matrix::from(vec![vec![1.0, 0.0, 2.0], vec![0.0, 1.0, 1.0]]);
+        let reference = Matrix::from(vec![
+            vec![1.0, 2.0, 4.0],
+            vec![3.0, 4.0, 10.0],
+            vec![5.0, 6.0, 16.0],
+        ]);
+
+        assert_eq!(reference, &a * &b);
+        assert_eq!(Matrix::from(vec![vec![11.0, 14.0], vec![8.0, 10.0]]), b * a);
+    }
+
+    #[test]
+    fn should_multiply_small_square_matrices() {
+        let a = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+        assert_eq!(a, &a * &Matrix::identity(2));
+        assert_eq!(
+            Matrix::from(vec![vec![7.0, 10.0], vec![15.0, 22.0]]),
+            &a * &a
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot multiply a 2x2 matrix by a 4x4 matrix")]
+    fn should_panic_when_multiplying_mismatched_shapes() {
+        let _ = Matrix::identity(2) * Matrix::identity(4);
+    }
+
+    #[test]
+    fn checked_mul_should_report_mismatched_shapes() {
+        let err = Matrix::zeros(2, 3).checked_mul(&Matrix::identity(2));
+
+        assert_eq!(
+            Err(ShapeMismatch {
+                left: (2, 3),
+                right: (2, 2)
+            }),
+            err
+        );
+    }
+
+    #[test]
+    fn matrices_of_different_shapes_should_not_be_equal() {
+        assert_ne!(Matrix::identity(2), Matrix::identity(4));
+        assert_ne!(Matrix::identity(4), Matrix::identity(2));
+        assert_ne!(Matrix::zeros(2, 3), Matrix::zeros(3, 2));
+    }
+
+    #[test]
+    fn should_construct_special_matrices() {
+        assert_eq!(
+            Matrix::from(vec![vec![1.0, 0.0], vec![0.0, 1.0]]),
+            Matrix::identity(2)
+        );
+        assert_eq!(Matrix::from(vec![vec![0.0; 3]; 2]), Matrix::zeros(2, 3));
+        assert_eq!(
+            Matrix::from(vec![vec![2.0, 0.0], vec![0.0, 3.0]]),
+            Matrix::from_diagonal(&[2.0, 3.0])
+        );
+        assert_eq!(
+            Matrix::from(vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]),
+            Matrix::from_columns(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]])
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_constructor_should_keep_its_behavior() {
+        assert_eq!(Matrix::identity(4), Matrix::new(4, 4));
+        assert_eq!(
+            Matrix::from(vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]]),
+            Matrix::new(2, 3)
+        );
+    }
+
+    #[test]
+    fn should_access_rows_and_columns() {
+        let m = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        assert_eq!((2, 3), m.dimensions());
+        assert_eq!(&[4.0, 5.0, 6.0], m.row(1));
+        assert_eq!(vec![3.0, 6.0], m.col(2));
+        assert_eq!(
+            vec![&[1.0, 2.0, 3.0][..], &[4.0, 5.0, 6.0][..]],
+            m.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(21.0, m.iter().sum::<f64>());
+    }
+
+    #[test]
+    fn should_mutate_elements_in_row_major_order() {
+        let mut m = Matrix::zeros(2, 2);
+
+        for (i, x) in m.iter_mut().enumerate() {
+            *x = i as f64;
+        }
+
+        assert_eq!(Matrix::from(vec![vec![0.0, 1.0], vec![2.0, 3.0]]), m);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 2 out of bounds")]
+    fn should_panic_when_column_out_of_bounds() {
+        Matrix::identity(2).col(2);
+    }
+
+    #[test]
+    fn should_compute_trace_and_norm() {
+        let m = Matrix::from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+        assert_eq!(5.0, m.trace());
+        assert_eq!(30.0_f64.sqrt(), m.norm());
+        assert_eq!(2.0, Matrix::identity(4).norm());
+    }
+
+    #[test]
+    fn should_classify_matrices() {
+        let m = Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        let rotation = Matrix::from(vec![vec![0.0, -1.0], vec![1.0, 0.0]]);
+
+        assert!(Matrix::identity(3).is_identity());
+        assert!(!m.is_identity());
+        assert!(!Matrix::zeros(2, 3).is_identity());
+        assert!(m.is_symmetric());
+        assert!(!rotation.is_symmetric());
+        assert!(rotation.is_orthogonal());
+        assert!(!m.is_orthogonal());
+        assert!(!Matrix::zeros(2, 3).is_orthogonal());
+        assert!(!Matrix::zeros(2, 3).is_symmetric());
+    }
 }
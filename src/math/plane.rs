@@ -0,0 +1,138 @@
+use super::{Intersection, Matrix, Ray, Shape, Tuple};
+use crate::graphics::Material;
+use alloc::{vec, vec::Vec};
+
+/// Infinite plane spanned by the x and z axes, facing up along the y axis
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{translation, Plane, Ray, Tuple};
+///
+/// let mut plane = Plane::new();
+/// plane.transform(translation(0.0, -1.0, 0.0));
+///
+/// // straight down from above, and parallel to the plane
+/// let down = Ray::new(Tuple::point(3.0, 1.0, -7.0), Tuple::vector(0.0, -1.0, 0.0));
+/// let along = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(1.0, 0.0, 0.0));
+///
+/// assert_eq!(2.0, down.intersect(&plane)[0].t);
+/// assert!(along.intersect(&plane).is_empty());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    /// Transformation matrix of the plane
+    pub transform: Matrix,
+    /// Material of the plane
+    pub material: Material,
+}
+
+impl Plane {
+    /// Returns a new plane through the origin
+    pub fn new() -> Plane {
+        Plane {
+            transform: Matrix::identity(4),
+            material: Material::new(),
+        }
+    }
+
+    /// Sets the transformation matrix of plane to given transformation matrix
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - transformation matrix to set for plane
+    pub fn transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+}
+
+impl Shape for Plane {
+    fn transformation(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn set_transformation(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        // rays parallel to the plane never meet it, not even those within it
+        if ray.direction.y == 0.0 {
+            return vec![];
+        }
+
+        vec![Intersection::new(
+            -ray.position(0.0).y / ray.direction.y,
+            self,
+        )]
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        Tuple::vector(0.0, 1.0, 0.0)
+    }
+
+    fn local_bounds(&self) -> (Tuple, Tuple) {
+        (
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Plane {
+        Plane::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_should_be_constant_everywhere() {
+        let p = Plane::new();
+
+        for point in [
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::point(10.0, 0.0, -10.0),
+            Tuple::point(-5.0, 0.0, 150.0),
+        ] {
+            assert_eq!(Tuple::vector(0.0, 1.0, 0.0), p.local_normal_at(point));
+        }
+    }
+
+    #[test]
+    fn ray_should_miss_plane_when_parallel_or_coplanar() {
+        let p = Plane::new();
+        let parallel = Ray::new(Tuple::point(0.0, 10.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let coplanar = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(p.local_intersect(&parallel).is_empty());
+        assert!(p.local_intersect(&coplanar).is_empty());
+    }
+
+    #[test]
+    fn ray_should_intersect_plane_from_above_and_below() {
+        let p = Plane::new();
+        let above = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        let below = Ray::new(Tuple::point(0.0, -1.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        for ray in [above, below] {
+            let xs = p.local_intersect(&ray);
+
+            assert_eq!(1, xs.len());
+            assert_eq!(1.0, xs[0].t);
+            assert!(core::ptr::addr_eq(xs[0].object, &p));
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Low-discrepancy sample sequences
+
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::Point2;
+use alloc::{vec, vec::Vec};
+
+/// Source of sample values in `[0, 1)`, addressed by sample index and
+/// dimension so that every dimension of a sample is drawn from its own
+/// sequence
+pub trait Sampler {
+    /// Returns the value of the given sample in the given dimension, in
+    /// `[0, 1)`
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the sample
+    /// * `dimension` - dimension of the sample
+    fn sample(&self, index: u32, dimension: usize) -> f64;
+
+    /// Returns the values of the given sample in two consecutive dimensions
+    /// as a point
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - index of the sample
+    /// * `dimension` - first of the two dimensions
+    fn sample_2d(&self, index: u32, dimension: usize) -> Point2 {
+        Point2::new(
+            self.sample(index, dimension),
+            self.sample(index, dimension + 1),
+        )
+    }
+}
+
+const PRIMES: [u32; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+/// Halton sequence, the radical inverse of the sample index in a different
+/// prime base for every dimension, optionally Owen scrambled
+///
+/// Dimensions beyond the sixteenth reuse the bases of the first ones, and
+/// should only be used with scrambling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Halton {
+    /// Seed of the scrambling, or `None` for the plain sequence
+    pub scramble: Option<u32>,
+}
+
+impl Halton {
+    /// Returns the plain Halton sequence
+    pub fn new() -> Halton {
+        Halton { scramble: None }
+    }
+
+    /// Returns the Halton sequence Owen scrambled with the given seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - seed of the scrambling
+    pub fn scrambled(seed: u32) -> Halton {
+        Halton {
+            scramble: Some(seed),
+        }
+    }
+}
+
+impl Sampler for Halton {
+    fn sample(&self, index: u32, dimension: usize) -> f64 {
+        let base = PRIMES[dimension % PRIMES.len()];
+        let digits = (53.0 / (base as f64).log2()).ceil() as usize;
+        let mut remaining = index;
+        let mut prefix = 0u64;
+        let mut numerator = 0u64;
+        let mut denominator = 1u64;
+
+        for position in 0..digits {
+            if remaining == 0 && self.scramble.is_none() {
+                break;
+            }
+
+            let mut digit = remaining % base;
+            remaining /= base;
+
+            if let Some(seed) = self.scramble {
+                let key = [seed as u64, dimension as u64, position as u64, prefix];
+                prefix = prefix.wrapping_mul(base as u64) + digit as u64;
+                digit = permute(digit, base, hash(&key));
+            }
+
+            numerator = numerator * base as u64 + digit as u64;
+            denominator *= base as u64;
+        }
+
+        let value = numerator as f64 / denominator as f64;
+
+        value.min(1.0 - f64::EPSILON)
+    }
+}
+
+/// Sobol sequence in base two, optionally Owen scrambled
+///
+/// Direction numbers are provided for eight dimensions. Dimensions beyond
+/// those reuse the first ones, and should only be used with scrambling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sobol {
+    /// Seed of the scrambling, or `None` for the plain sequence
+    pub scramble: Option<u32>,
+    directions: Vec<[u32; 32]>,
+}
+
+/// Degree, polynomial coefficients and initial direction numbers of the
+/// dimensions following the first one, after Joe and Kuo
+const SOBOL_PARAMETERS: [(u32, u32, &[u32]); 7] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+];
+
+impl Sobol {
+    /// Returns the plain Sobol sequence
+    pub fn new() -> Sobol {
+        let mut directions = vec![core::array::from_fn(|k| 1 << (31 - k))];
+
+        for (degree, coefficients, initial) in SOBOL_PARAMETERS {
+            let s = degree as usize;
+            let mut m = [0u32; 32];
+            m[..s].copy_from_slice(initial);
+
+            for k in s..32 {
+                let mut next = m[k - s] ^ (m[k - s] << s);
+
+                for j in 1..s {
+                    if (coefficients >> (s - 1 - j)) & 1 == 1 {
+                        next ^= m[k - j] << j;
+                    }
+                }
+
+                m[k] = next;
+            }
+
+            directions.push(core::array::from_fn(|k| m[k] << (31 - k)));
+        }
+
+        Sobol {
+            scramble: None,
+            directions,
+        }
+    }
+
+    /// Returns the Sobol sequence Owen scrambled with the given seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - seed of the scrambling
+    pub fn scrambled(seed: u32) -> Sobol {
+        Sobol {
+            scramble: Some(seed),
+            ..Sobol::new()
+        }
+    }
+}
+
+impl Default for Sobol {
+    fn default() -> Sobol {
+        Sobol::new()
+    }
+}
+
+impl Sampler for Sobol {
+    fn sample(&self, index: u32, dimension: usize) -> f64 {
+        let directions = &self.directions[dimension % self.directions.len()];
+        let mut bits = 0u32;
+        let mut remaining = index;
+
+        for direction in directions {
+            if remaining == 0 {
+                break;
+            }
+            if remaining & 1 == 1 {
+                bits ^= direction;
+            }
+            remaining >>= 1;
+        }
+
+        if let Some(seed) = self.scramble {
+            let key = hash(&[seed as u64, dimension as u64]) as u32;
+            bits = nested_uniform_scramble(bits, key);
+        }
+
+        bits as f64 / 4294967296.0
+    }
+}
+
+/// Owen scrambles the bits of a base two sample, flipping every bit
+/// depending on the bits above it, after Burley
+fn nested_uniform_scramble(bits: u32, seed: u32) -> u32 {
+    let mut x = bits.reverse_bits();
+
+    x ^= x.wrapping_mul(0x3d20adea);
+    x = x.wrapping_add(seed);
+    x = x.wrapping_mul((seed >> 16) | 1);
+    x ^= x.wrapping_mul(0x05526c56);
+    x ^= x.wrapping_mul(0x53a22864);
+
+    x.reverse_bits()
+}
+
+/// Returns the image of the digit under a random permutation of the digits
+/// in the given base chosen by the hash
+fn permute(digit: u32, base: u32, hash: u64) -> u32 {
+    let mut digits: Vec<u32> = (0..base).collect();
+    let mut state = hash;
+
+    for i in (1..base as usize).rev() {
+        state = mix(state);
+        digits.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+
+    digits[digit as usize]
+}
+
+fn hash(values: &[u64]) -> u64 {
+    values
+        .iter()
+        .fold(0x9e3779b97f4a7c15, |state, value| mix(state ^ value))
+}
+
+/// Finalizer of the SplitMix64 generator
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that each of the first `n` values falls into its own one of
+    /// `n` equally sized intervals
+    fn assert_stratified<S: Sampler>(sampler: &S, dimension: usize, n: u32) {
+        let mut seen = vec![false; n as usize];
+
+        for i in 0..n {
+            let value = sampler.sample(i, dimension);
+
+            assert!((0.0..1.0).contains(&value));
+
+            let stratum = (value * n as f64) as usize;
+
+            assert!(!seen[stratum], "two samples in stratum {}", stratum);
+            seen[stratum] = true;
+        }
+    }
+
+    #[test]
+    fn halton_should_compute_radical_inverses() {
+        let halton = Halton::new();
+
+        assert_eq!(
+            vec![0.0, 0.5, 0.25, 0.75, 0.125],
+            (0..5).map(|i| halton.sample(i, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(1.0 / 3.0, halton.sample(1, 1));
+        assert_eq!(1.0 / 9.0, halton.sample(3, 1));
+        assert_eq!(Point2::new(0.5, 1.0 / 3.0), halton.sample_2d(1, 0));
+    }
+
+    #[test]
+    fn sobol_should_match_reference_points() {
+        let sobol = Sobol::new();
+        let points: Vec<_> = (0..4).map(|i| sobol.sample_2d(i, 0)).collect();
+
+        assert_eq!(
+            vec![(0.0, 0.0), (0.5, 0.5), (0.25, 0.75), (0.75, 0.25)]
+                .into_iter()
+                .map(Point2::from)
+                .collect::<Vec<_>>(),
+            points
+        );
+    }
+
+    #[test]
+    fn unscrambled_sequences_should_be_stratified() {
+        for dimension in 0..8 {
+            assert_stratified(&Sobol::new(), dimension, 64);
+        }
+        assert_stratified(&Halton::new(), 1, 81);
+        assert_stratified(&Halton::new(), 2, 125);
+    }
+
+    #[test]
+    fn scrambling_should_preserve_stratification() {
+        for seed in [1, 7, 12345] {
+            for dimension in 0..10 {
+                assert_stratified(&Sobol::scrambled(seed), dimension, 128);
+            }
+            assert_stratified(&Halton::scrambled(seed), 0, 64);
+            assert_stratified(&Halton::scrambled(seed), 1, 81);
+            assert_stratified(&Halton::scrambled(seed), 3, 49);
+        }
+    }
+
+    #[test]
+    fn scrambling_should_depend_on_seed() {
+        let values =
+            |sampler: &dyn Sampler| (0..8).map(|i| sampler.sample(i, 2)).collect::<Vec<_>>();
+
+        assert_ne!(values(&Sobol::scrambled(1)), values(&Sobol::scrambled(2)));
+        assert_ne!(values(&Halton::scrambled(1)), values(&Halton::scrambled(2)));
+        assert_ne!(values(&Sobol::new()), values(&Sobol::scrambled(1)));
+    }
+}
@@ -0,0 +1,427 @@
+use super::{Intersection, Matrix, Ray, Tuple};
+use crate::graphics::{LightLinking, Material};
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+use core::fmt;
+
+/// Geometric primitive which rays can be intersected with
+///
+/// Shapes are defined in their own object space and placed in the world by
+/// their transformation. Implementations only handle rays and points in
+/// object space, `Ray::intersect` and `normal_at` take care of transforming
+/// them from and to world space.
+///
+/// Every shape which can be cloned and compared is also an `AnyShape`, so
+/// that worlds can hold any mix of shapes as `Box<dyn Shape>`.
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::Material;
+/// use libray::math::{Intersection, Matrix, Ray, Shape, Tuple, EPSILON};
+///
+/// /// The xz plane, a bare version of `Plane`
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Ground {
+///     transform: Matrix,
+///     material: Material,
+/// }
+///
+/// impl Shape for Ground {
+///     fn transformation(&self) -> &Matrix {
+///         &self.transform
+///     }
+///
+///     fn set_transformation(&mut self, transform: Matrix) {
+///         self.transform = transform;
+///     }
+///
+///     fn material(&self) -> &Material {
+///         &self.material
+///     }
+///
+///     fn material_mut(&mut self) -> &mut Material {
+///         &mut self.material
+///     }
+///
+///     fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+///         if ray.direction.y.abs() < EPSILON {
+///             return vec![];
+///         }
+///
+///         vec![Intersection::new(-ray.position(0.0).y / ray.direction.y, self)]
+///     }
+///
+///     fn local_normal_at(&self, _point: Tuple) -> Tuple {
+///         Tuple::vector(0.0, 1.0, 0.0)
+///     }
+/// }
+///
+/// let ground = Ground {
+///     transform: Matrix::identity(4),
+///     material: Material::new(),
+/// };
+/// let ray = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+///
+/// assert_eq!(1.0, ray.intersect(&ground)[0].t);
+/// ```
+pub trait Shape: AnyShape + fmt::Debug + Send + Sync {
+    /// Returns the transformation matrix placing the shape in the world
+    fn transformation(&self) -> &Matrix;
+
+    /// Sets the transformation matrix placing the shape in the world
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - new transformation matrix of the shape
+    fn set_transformation(&mut self, transform: Matrix);
+
+    /// Returns the material of the shape
+    fn material(&self) -> &Material;
+
+    /// Returns a mutable reference to the material of the shape
+    fn material_mut(&mut self) -> &mut Material;
+
+    /// Returns the lights illuminating the shape, every light by default
+    fn light_linking(&self) -> &LightLinking {
+        &LightLinking::All
+    }
+
+    /// Returns whether the shape is a holdout, rendered black and left out
+    /// of the coverage mask, which it is not by default
+    fn is_holdout(&self) -> bool {
+        false
+    }
+
+    /// Returns the intersections of the shape with a ray in object space
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray transformed into the object space of the shape
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
+
+    /// Returns the normal vector of the shape at a point in object space,
+    /// which does not need to be normalized
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point on the shape in object space
+    fn local_normal_at(&self, point: Tuple) -> Tuple;
+
+    /// Returns the normalized normal vector of the shape at a point in
+    /// world space
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point on the shape in world space
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let inverse = self.transformation().inverse();
+        let local_normal = self.local_normal_at(&inverse * &point);
+        let mut world_normal = inverse.transpose() * local_normal;
+        world_normal.w = 0.0;
+
+        world_normal.normalize()
+    }
+
+    /// Returns the smallest and largest corner of the box bounding the
+    /// shape in object space, unbounded in every direction by default
+    fn local_bounds(&self) -> (Tuple, Tuple) {
+        (
+            Tuple::point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        )
+    }
+
+    /// Returns the corners of the box bounding the shape in object space,
+    /// transformed into world space
+    ///
+    /// Corners are ordered by their object space coordinates, x changing
+    /// fastest, so that corners `i` and `i ^ 1`, `i ^ 2` and `i ^ 4` share
+    /// an edge. Unbounded shapes have corners which are not finite.
+    fn bounding_corners(&self) -> [Tuple; 8] {
+        let (min, max) = self.local_bounds();
+
+        core::array::from_fn(|i| {
+            let pick = |bit: usize, low: f64, high: f64| if i & bit == 0 { low } else { high };
+            let corner = Tuple::point(
+                pick(1, min.x, max.x),
+                pick(2, min.y, max.y),
+                pick(4, min.z, max.z),
+            );
+
+            self.transformation() * &corner
+        })
+    }
+}
+
+/// Object safe operations on shapes which can be cloned and compared,
+/// implemented for every such shape
+pub trait AnyShape {
+    /// Returns a boxed copy of the shape
+    fn clone_box(&self) -> Box<dyn Shape>;
+
+    /// Returns true if the other shape is of the same type and equal
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - shape to compare with
+    fn eq_shape(&self, other: &dyn Shape) -> bool;
+
+    /// Returns the shape as `Any`, to downcast it to its concrete type
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the shape as mutable `Any`, to downcast it to its concrete
+    /// type
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Shape + Clone + PartialEq + 'static> AnyShape for T {
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn eq_shape(&self, other: &dyn Shape) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl dyn Shape {
+    /// Returns the shape as its concrete type, if it is of that type
+    pub fn downcast_ref<T: Shape + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns the shape as its concrete mutable type, if it is of that type
+    pub fn downcast_mut<T: Shape + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+impl Clone for Box<dyn Shape> {
+    fn clone(&self) -> Box<dyn Shape> {
+        self.clone_box()
+    }
+}
+
+impl<'a> PartialEq for dyn Shape + 'a {
+    fn eq(&self, other: &dyn Shape) -> bool {
+        self.eq_shape(other)
+    }
+}
+
+/// Serialization of boxed shapes, tagged with the name of their type
+#[cfg(feature = "serde")]
+pub(crate) mod tagged {
+    use super::Shape;
+    use crate::math::{Cylinder, Plane, Sphere};
+    use alloc::{boxed::Box, vec::Vec};
+    use serde::{ser, ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    enum ShapeRef<'a> {
+        Sphere(&'a Sphere),
+        Cylinder(&'a Cylinder),
+        Plane(&'a Plane),
+    }
+
+    #[derive(Deserialize)]
+    enum ShapeData {
+        Sphere(Sphere),
+        Cylinder(Cylinder),
+        Plane(Plane),
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        shapes: &[Box<dyn Shape>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(shapes.len()))?;
+
+        for shape in shapes {
+            let tagged = if let Some(sphere) = shape.downcast_ref::<Sphere>() {
+                ShapeRef::Sphere(sphere)
+            } else if let Some(cylinder) = shape.downcast_ref::<Cylinder>() {
+                ShapeRef::Cylinder(cylinder)
+            } else if let Some(plane) = shape.downcast_ref::<Plane>() {
+                ShapeRef::Plane(plane)
+            } else {
+                return Err(ser::Error::custom(
+                    "only spheres, cylinders and planes can be serialized",
+                ));
+            };
+
+            seq.serialize_element(&tagged)?;
+        }
+
+        seq.end()
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Box<dyn Shape>>, D::Error> {
+        let shapes = Vec::<ShapeData>::deserialize(deserializer)?;
+
+        Ok(shapes
+            .into_iter()
+            .map(|shape| -> Box<dyn Shape> {
+                match shape {
+                    ShapeData::Sphere(sphere) => Box::new(sphere),
+                    ShapeData::Cylinder(cylinder) => Box::new(cylinder),
+                    ShapeData::Plane(plane) => Box::new(plane),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::{Color, Light};
+    use crate::math::{scaling, translation, Sphere};
+    use alloc::vec;
+    use core::f64::consts::FRAC_1_SQRT_2;
+    use std::sync::Mutex;
+
+    /// Shape recording the last ray it was intersected with
+    #[derive(Debug)]
+    struct TestShape {
+        transform: Matrix,
+        material: Material,
+        saved_ray: Mutex<Option<Ray>>,
+    }
+
+    impl Clone for TestShape {
+        fn clone(&self) -> TestShape {
+            TestShape {
+                transform: self.transform.clone(),
+                material: self.material.clone(),
+                saved_ray: Mutex::new(*self.saved_ray.lock().unwrap()),
+            }
+        }
+    }
+
+    impl PartialEq for TestShape {
+        fn eq(&self, other: &TestShape) -> bool {
+            self.transform == other.transform && self.material == other.material
+        }
+    }
+
+    impl TestShape {
+        fn new(transform: Matrix) -> TestShape {
+            TestShape {
+                transform,
+                material: Material::new(),
+                saved_ray: Mutex::new(None),
+            }
+        }
+    }
+
+    impl Shape for TestShape {
+        fn transformation(&self) -> &Matrix {
+            &self.transform
+        }
+
+        fn set_transformation(&mut self, transform: Matrix) {
+            self.transform = transform;
+        }
+
+        fn material(&self) -> &Material {
+            &self.material
+        }
+
+        fn material_mut(&mut self) -> &mut Material {
+            &mut self.material
+        }
+
+        fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+            *self.saved_ray.lock().unwrap() = Some(*ray);
+            vec![]
+        }
+
+        fn local_normal_at(&self, point: Tuple) -> Tuple {
+            Tuple::vector(point.x, point.y, point.z)
+        }
+    }
+
+    #[test]
+    fn intersect_should_transform_ray_into_object_space() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let scaled = TestShape::new(scaling(2.0, 2.0, 2.0));
+        let moved = TestShape::new(translation(5.0, 0.0, 0.0));
+
+        r.intersect(&scaled);
+        r.intersect(&moved);
+
+        assert_eq!(
+            Some(Ray::new(
+                Tuple::point(0.0, 0.0, -2.5),
+                Tuple::vector(0.0, 0.0, 0.5)
+            )),
+            *scaled.saved_ray.lock().unwrap()
+        );
+        assert_eq!(
+            Some(Ray::new(
+                Tuple::point(-5.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0)
+            )),
+            *moved.saved_ray.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn normal_at_should_transform_local_normal_into_world_space() {
+        let s = TestShape::new(translation(0.0, 1.0, 0.0));
+        let n = s.normal_at(Tuple::point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+
+        assert_eq!(Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2), n);
+        assert!(s.light_linking().is_lit_by(&Light::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0)
+        )));
+        assert!(!s.is_holdout());
+    }
+
+    #[test]
+    fn shapes_should_mix_behind_trait_objects() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let sphere = Sphere::new();
+        let test = TestShape::new(Matrix::identity(4));
+        let shapes: [&dyn Shape; 2] = [&test, &sphere];
+
+        let hits: Vec<_> = shapes.iter().flat_map(|s| r.intersect(*s)).collect();
+
+        assert_eq!(2, hits.len());
+        assert!(hits[0].is_same_object(&Intersection::new(0.0, &sphere)));
+    }
+
+    #[test]
+    fn boxed_shapes_should_clone_compare_and_downcast() {
+        let mut boxed: Box<dyn Shape> = Box::new(Sphere::new());
+        let copy = boxed.clone();
+        let other: Box<dyn Shape> = Box::new(TestShape::new(Matrix::identity(4)));
+
+        assert!(*boxed == *copy);
+        assert!(*boxed != *other);
+
+        boxed.material_mut().ambient = 0.5;
+        boxed.set_transformation(scaling(2.0, 2.0, 2.0));
+
+        assert!(*boxed != *copy);
+        assert_eq!(
+            0.5,
+            boxed.downcast_ref::<Sphere>().unwrap().material.ambient
+        );
+        assert!(boxed.downcast_mut::<TestShape>().is_none());
+        assert_eq!(Tuple::point(-2.0, -2.0, -2.0), boxed.bounding_corners()[0]);
+        assert!(!other.bounding_corners()[0].is_finite());
+    }
+}
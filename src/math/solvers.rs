@@ -0,0 +1,408 @@
+//! Polynomial root solvers
+//!
+//! Closed form solvers for the polynomials arising in ray-shape
+//! intersections, formulated to avoid catastrophic cancellation. All
+//! solvers return the real roots in ascending order, repeating roots of
+//! higher multiplicity where the closed form yields them more than once.
+
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::EPSILON;
+use alloc::{vec, vec::Vec};
+use core::f64::consts::PI;
+
+/// Returns the real roots of `a * x^2 + b * x + c`, or `None` if there are
+/// none
+///
+/// Complex roots whose imaginary part squared is within `EPSILON` are
+/// treated as a double root, see `solve_quadratic_within`.
+///
+/// # Arguments
+///
+/// * `a` - quadratic coefficient
+/// * `b` - linear coefficient
+/// * `c` - constant coefficient
+///
+/// # Example
+///
+/// ```
+/// use libray::math::solve_quadratic;
+///
+/// assert_eq!(Some((-3.0, 2.0)), solve_quadratic(1.0, 1.0, -6.0));
+/// assert_eq!(None, solve_quadratic(1.0, 0.0, 1.0));
+/// ```
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    solve_quadratic_within(a, b, c, EPSILON)
+}
+
+/// Returns the real roots of `a * x^2 + b * x + c`, or `None` if there are
+/// none, treating complex roots whose imaginary part squared is within the
+/// given tolerance as a double root
+///
+/// This way a nearly tangent ray yields a double root instead of missing.
+/// The tolerance bounds the roots rather than the coefficients, keeping it
+/// meaningful however large or small they are. A linear equation, with `a`
+/// exactly zero, yields its single root twice.
+///
+/// # Arguments
+///
+/// * `a` - quadratic coefficient
+/// * `b` - linear coefficient
+/// * `c` - constant coefficient
+/// * `tolerance` - largest squared imaginary part of roots treated as real
+///
+/// # Example
+///
+/// ```
+/// use libray::math::solve_quadratic_within;
+///
+/// // a ray passing 0.0001 units over the top of a unit sphere, five units away
+/// assert_eq!(None, solve_quadratic_within(1.0, -10.0, 25.00020001, 1e-5));
+/// assert_eq!(
+///     Some((5.0, 5.0)),
+///     solve_quadratic_within(1.0, -10.0, 25.00020001, 1e-3)
+/// );
+/// ```
+pub fn solve_quadratic_within(a: f64, b: f64, c: f64, tolerance: f64) -> Option<(f64, f64)> {
+    if a == 0.0 {
+        if b == 0.0 {
+            return None;
+        }
+
+        let x = -c / b;
+        return Some((x, x));
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < -4.0 * a * a * tolerance {
+        return None;
+    }
+
+    if discriminant <= 0.0 {
+        let x = -b / (2.0 * a);
+        return Some((x, x));
+    }
+
+    let root = discriminant.sqrt();
+    let q = -0.5 * (b + root.copysign(b));
+
+    if q == 0.0 {
+        return Some((0.0, 0.0));
+    }
+
+    let (x0, x1) = (q / a, c / q);
+
+    Some((x0.min(x1), x0.max(x1)))
+}
+
+/// Returns the real roots of `a * x^3 + b * x^2 + c * x + d`
+///
+/// The equation is treated as quadratic when `a` is negligible next to the
+/// other coefficients, and repeated roots are detected relative to the size
+/// of the roots, so scaling all coefficients alike, or the roots, doesn't
+/// change which roots are found.
+///
+/// # Arguments
+///
+/// * `a` - cubic coefficient
+/// * `b` - quadratic coefficient
+/// * `c` - linear coefficient
+/// * `d` - constant coefficient
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if is_negligible(a, &[b, c, d]) {
+        return solve_quadratic(b, c, d).map_or(vec![], |(x0, x1)| vec![x0, x1]);
+    }
+
+    let (a2, a1, a0) = (b / a, c / a, d / a);
+    let size = root_size(a, &[b, c, d]);
+    let shift = a2 / 3.0;
+    let p = (3.0 * a1 - a2 * a2) / 9.0;
+    let q = (2.0 * a2 * a2 * a2 - 9.0 * a2 * a1 + 27.0 * a0) / 54.0;
+    let discriminant = q * q + p * p * p;
+
+    let mut roots = if discriminant.abs() <= EPSILON * EPSILON * size.powi(6) {
+        if q.abs() <= EPSILON * EPSILON * size.powi(3) {
+            vec![0.0]
+        } else {
+            let u = (-q).cbrt();
+            vec![2.0 * u, -u]
+        }
+    } else if discriminant < 0.0 {
+        let phi = (-q / (-p * p * p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let t = 2.0 * (-p).sqrt();
+
+        vec![
+            t * phi.cos(),
+            -t * (phi + PI / 3.0).cos(),
+            -t * (phi - PI / 3.0).cos(),
+        ]
+    } else {
+        let root = discriminant.sqrt();
+        vec![(root - q).cbrt() - (root + q).cbrt()]
+    };
+
+    for x in roots.iter_mut() {
+        *x = polish(&[a, b, c, d], *x - shift);
+    }
+
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// Returns the real roots of `a * x^4 + b * x^3 + c * x^2 + d * x + e`,
+/// found with Ferrari's method and refined with Newton's method
+///
+/// As with `solve_cubic`, the tolerances are relative to the coefficients,
+/// so scaling all of them alike, or the roots, doesn't change which roots
+/// are found.
+///
+/// # Arguments
+///
+/// * `a` - quartic coefficient
+/// * `b` - cubic coefficient
+/// * `c` - quadratic coefficient
+/// * `d` - linear coefficient
+/// * `e` - constant coefficient
+///
+/// # Example
+///
+/// ```
+/// use libray::math::solve_quartic;
+///
+/// // (x - 1)(x - 2)(x - 3)(x - 4)
+/// let roots = solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0);
+///
+/// assert_eq!(4, roots.len());
+/// assert!((roots[3] - 4.0).abs() < 1e-9);
+/// ```
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if is_negligible(a, &[b, c, d, e]) {
+        return solve_cubic(b, c, d, e);
+    }
+
+    let (a3, a2, a1, a0) = (b / a, c / a, d / a, e / a);
+    let size = root_size(a, &[b, c, d, e]);
+    let shift = a3 / 4.0;
+    let sq = a3 * a3;
+    let p = -3.0 / 8.0 * sq + a2;
+    let q = sq * a3 / 8.0 - a3 * a2 / 2.0 + a1;
+    let r = -3.0 / 256.0 * sq * sq + sq * a2 / 16.0 - a3 * a1 / 4.0 + a0;
+
+    let mut roots = if r.abs() <= EPSILON * EPSILON * size.powi(4) {
+        let mut roots = solve_cubic(1.0, 0.0, p, q);
+        roots.push(0.0);
+        roots
+    } else {
+        let resolvent = solve_cubic(1.0, -p / 2.0, -r, r * p / 2.0 - q * q / 8.0);
+        let z = resolvent[resolvent.len() - 1];
+        let u = z * z - r;
+        let v = 2.0 * z - p;
+
+        if u < -EPSILON * size.powi(4) || v < -EPSILON * size * size {
+            return vec![];
+        }
+
+        let u = u.max(0.0).sqrt();
+        let v = v.max(0.0).sqrt().copysign(q);
+
+        [
+            solve_quadratic(1.0, v, z - u),
+            solve_quadratic(1.0, -v, z + u),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|(x0, x1)| [x0, x1])
+        .collect()
+    };
+
+    for x in roots.iter_mut() {
+        *x = polish(&[a, b, c, d, e], *x - shift);
+    }
+
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// Returns true if the leading coefficient of a polynomial is negligible,
+/// the polynomial without it having roots so much smaller than the one the
+/// leading term adds that it barely changes them
+///
+/// # Arguments
+///
+/// * `leading` - leading coefficient
+/// * `others` - remaining coefficients, highest degree first
+fn is_negligible(leading: f64, others: &[f64]) -> bool {
+    match others.split_first() {
+        _ if leading == 0.0 => true,
+        Some((&next, rest)) if next != 0.0 => {
+            let size = root_size(next, rest);
+            size > 0.0 && leading.abs() * size <= EPSILON * next.abs()
+        }
+        _ => false,
+    }
+}
+
+/// Returns a bound on the magnitude of the roots of a polynomial, the
+/// largest `|k / leading|^(1 / i)` of its `i`-th coefficient `k` below the
+/// leading one
+///
+/// # Arguments
+///
+/// * `leading` - nonzero leading coefficient
+/// * `others` - remaining coefficients, highest degree first
+fn root_size(leading: f64, others: &[f64]) -> f64 {
+    others.iter().zip(1..).fold(0.0, |max, (k, i)| {
+        (k / leading).abs().powf(1.0 / f64::from(i)).max(max)
+    })
+}
+
+/// Refines a root of the polynomial with the given coefficients, highest
+/// degree first, with a few steps of Newton's method
+fn polish(coefficients: &[f64], mut x: f64) -> f64 {
+    for _ in 0..4 {
+        let (value, slope) = coefficients.iter().fold((0.0, 0.0), |(value, slope), &k| {
+            (value * x + k, slope * x + value)
+        });
+
+        if slope.abs() < f64::EPSILON {
+            break;
+        }
+
+        let next = x - value / slope;
+
+        if !next.is_finite() {
+            break;
+        }
+
+        x = next;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(expected: &[f64], actual: &[f64]) {
+        assert_eq!(expected.len(), actual.len(), "roots {:?}", actual);
+
+        for (e, a) in expected.iter().zip(actual) {
+            assert!(
+                (e - a).abs() < 1e-6,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn quadratic_should_avoid_cancellation() {
+        let (x0, x1) = solve_quadratic(1.0, -1.0e8, 1.0).unwrap();
+
+        assert!((x0 - 1.0e-8).abs() < 1.0e-20);
+        assert!((x1 - 1.0e8).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn quadratic_should_handle_edge_cases() {
+        assert_eq!(Some((2.0, 2.0)), solve_quadratic(1.0, -4.0, 4.0));
+        assert_eq!(Some((2.0, 2.0)), solve_quadratic(1.0, -4.0, 4.0 + 1e-7));
+        assert_eq!(None, solve_quadratic(1.0, -4.0, 4.1));
+        assert_eq!(Some((-2.0, -2.0)), solve_quadratic(0.0, 2.0, 4.0));
+        assert_eq!(None, solve_quadratic(0.0, 0.0, 4.0));
+        assert_eq!(Some((0.0, 0.0)), solve_quadratic(3.0, 0.0, 0.0));
+        assert_eq!(Some((-2.0, 0.0)), solve_quadratic(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn quadratic_tolerance_should_not_depend_on_coefficient_size() {
+        let (x0, x1) = solve_quadratic(1e-6, -1e-2, 24.0).unwrap();
+        let (y0, y1) = solve_quadratic(1e-6, -1e-5, 2.4e-5).unwrap();
+
+        assert_roots(&[4000.0, 6000.0], &[x0, x1]);
+        assert_roots(&[4.0, 6.0], &[y0, y1]);
+        assert_eq!(None, solve_quadratic(1e-6, -1e-2, 25.1));
+    }
+
+    #[test]
+    fn cubic_should_find_real_roots() {
+        assert_roots(&[1.0, 2.0, 3.0], &solve_cubic(1.0, -6.0, 11.0, -6.0));
+        assert_roots(&[2.0], &solve_cubic(2.0, -4.0, 2.0, -4.0));
+        assert_roots(&[-1.0, 2.0], &solve_cubic(1.0, -3.0, 0.0, 4.0));
+        assert_roots(&[0.0], &solve_cubic(1.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn cubic_should_not_depend_on_coefficient_scale() {
+        for s in [1e-6, 1e6] {
+            assert_roots(
+                &[1.0, 2.0, 3.0],
+                &solve_cubic(s, -6.0 * s, 11.0 * s, -6.0 * s),
+            );
+            assert_roots(&[1.0, 2.0], &solve_cubic(s, -5.0 * s, 8.0 * s, -4.0 * s));
+        }
+
+        let roots = solve_cubic(1.0, -6.0e3, 11.0e6, -6.0e9);
+        assert_roots(
+            &[1.0, 2.0, 3.0],
+            &roots.iter().map(|x| x / 1e3).collect::<Vec<_>>(),
+        );
+        assert_roots(&[1.0, 2.0], &solve_cubic(1e-20, 1.0, -3.0, 2.0));
+        assert_roots(&[-1.0, 0.0], &solve_cubic(1.0, 1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn quartic_should_find_real_roots() {
+        assert_roots(
+            &[1.0, 2.0, 3.0, 4.0],
+            &solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+        );
+        assert_roots(&[-1.0, 1.0], &solve_quartic(1.0, 0.0, 0.0, 0.0, -1.0));
+        assert_roots(&[], &solve_quartic(1.0, 0.0, 2.0, 0.0, 1.0));
+        assert_roots(
+            &[-3.0, 0.0, 0.0, 3.0],
+            &solve_quartic(2.0, 0.0, -18.0, 0.0, 0.0),
+        );
+        assert_roots(&[1.0, 1.0], &solve_quartic(1.0, -2.0, 2.0, -2.0, 1.0));
+    }
+
+    #[test]
+    fn quartic_should_not_depend_on_coefficient_scale() {
+        for s in [1e-6, 1e6] {
+            assert_roots(
+                &[1.0, 2.0, 3.0, 4.0],
+                &solve_quartic(s, -10.0 * s, 35.0 * s, -50.0 * s, 24.0 * s),
+            );
+            assert_roots(
+                &[1.0, 1.0],
+                &solve_quartic(s, -2.0 * s, 2.0 * s, -2.0 * s, s),
+            );
+        }
+
+        let roots = solve_quartic(1.0, -10.0e3, 35.0e6, -50.0e9, 24.0e12);
+        assert_roots(
+            &[1.0, 2.0, 3.0, 4.0],
+            &roots.iter().map(|x| x / 1e3).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn quartic_should_solve_torus_intersection() {
+        // ray along x through a torus of radii 2 and 0.5 around the y axis
+        let (big, small) = (2.0_f64, 0.5_f64);
+        let (ox, dx) = (-5.0_f64, 1.0_f64);
+        let k = ox * ox - big * big - small * small;
+        let roots = solve_quartic(
+            dx.powi(4),
+            4.0 * ox * dx.powi(3),
+            2.0 * k * dx * dx + 4.0 * ox * ox * dx * dx,
+            4.0 * k * ox * dx,
+            k * k - 4.0 * big * big * small * small,
+        );
+
+        assert_roots(&[2.5, 3.5, 6.5, 7.5], &roots);
+    }
+}
@@ -0,0 +1,217 @@
+//! Smooth curves through and along control points
+
+use super::Tuple;
+use alloc::vec::Vec;
+
+/// Returns the point at `t` on the uniform Catmull-Rom segment running from
+/// `p1` to `p2`, with `p0` and `p3` shaping the tangents at its ends
+///
+/// # Arguments
+///
+/// * `p0` - point before the segment
+/// * `p1` - start of the segment, reached at `t = 0`
+/// * `p2` - end of the segment, reached at `t = 1`
+/// * `p3` - point after the segment
+/// * `t` - position along the segment from `0.0` to `1.0`
+pub fn catmull_rom(p0: Tuple, p1: Tuple, p2: Tuple, p3: Tuple, t: f64) -> Tuple {
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    p0 * (-0.5 * t3 + t2 - 0.5 * t)
+        + p1 * (1.5 * t3 - 2.5 * t2 + 1.0)
+        + p2 * (-1.5 * t3 + 2.0 * t2 + 0.5 * t)
+        + p3 * (0.5 * t3 - 0.5 * t2)
+}
+
+/// Returns the point at `t` on the cubic Bézier curve with the given
+/// control points
+///
+/// # Arguments
+///
+/// * `p0` - start of the curve, reached at `t = 0`
+/// * `p1` - control point pulling the curve away from the start
+/// * `p2` - control point pulling the curve towards the end
+/// * `p3` - end of the curve, reached at `t = 1`
+/// * `t` - position along the curve from `0.0` to `1.0`
+pub fn cubic_bezier(p0: Tuple, p1: Tuple, p2: Tuple, p3: Tuple, t: f64) -> Tuple {
+    let s = 1.0 - t;
+
+    p0 * (s * s * s) + p1 * (3.0 * s * s * t) + p2 * (3.0 * s * t * t) + p3 * (t * t * t)
+}
+
+/// Way a spline interprets its control points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplineKind {
+    /// Passes through every point, with tangents following the neighbouring
+    /// points
+    CatmullRom,
+    /// Chain of cubic Bézier curves sharing their end points, taking the
+    /// points in groups of an end point followed by two control points and
+    /// ignoring points left over after the last full curve
+    Bezier,
+}
+
+/// Piecewise cubic curve through or along a list of points, evaluated by a
+/// parameter running from `0.0` at the first point to `1.0` at the last,
+/// with every segment taking an equal share of it
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{Spline, SplineKind, Tuple};
+///
+/// let spline = Spline::new(
+///     SplineKind::CatmullRom,
+///     vec![
+///         Tuple::point(0.0, 0.0, 0.0),
+///         Tuple::point(1.0, 1.0, 0.0),
+///         Tuple::point(2.0, 0.0, 0.0),
+///     ],
+/// );
+///
+/// assert_eq!(Tuple::point(1.0, 1.0, 0.0), spline.point_at(0.5));
+/// assert_eq!(Tuple::point(2.0, 0.0, 0.0), spline.point_at(1.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spline {
+    /// Interpretation of the points
+    pub kind: SplineKind,
+    /// Control points of the spline
+    pub points: Vec<Tuple>,
+}
+
+impl Spline {
+    /// Creates a new spline
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - interpretation of the points
+    /// * `points` - control points of the spline
+    pub fn new(kind: SplineKind, points: Vec<Tuple>) -> Spline {
+        Spline { kind, points }
+    }
+
+    /// Returns the number of cubic segments of the spline
+    pub fn segments(&self) -> usize {
+        let n = self.points.len();
+
+        match self.kind {
+            SplineKind::CatmullRom => n.saturating_sub(1),
+            SplineKind::Bezier => n.saturating_sub(1) / 3,
+        }
+    }
+
+    /// Returns the point at the given position along the spline, a spline
+    /// without any segment staying at its first point
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - position along the spline, clamped to `0.0..=1.0`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spline has no points.
+    pub fn point_at(&self, u: f64) -> Tuple {
+        let segments = self.segments();
+
+        if segments == 0 {
+            return self.points[0];
+        }
+
+        let position = u.clamp(0.0, 1.0) * segments as f64;
+        let index = (position as usize).min(segments - 1);
+        let t = position - index as f64;
+        let p = &self.points;
+
+        match self.kind {
+            SplineKind::CatmullRom => {
+                // the ends are extended by mirroring their neighbours
+                let before = if index == 0 {
+                    p[0] * 2.0 - p[1]
+                } else {
+                    p[index - 1]
+                };
+                let after = if index + 2 < p.len() {
+                    p[index + 2]
+                } else {
+                    p[index + 1] * 2.0 - p[index]
+                };
+
+                catmull_rom(before, p[index], p[index + 1], after, t)
+            }
+            SplineKind::Bezier => {
+                let start = index * 3;
+
+                cubic_bezier(p[start], p[start + 1], p[start + 2], p[start + 3], t)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catmull_rom_should_pass_through_inner_points() {
+        let p = [
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(1.0, 3.0, 0.0),
+            Tuple::point(2.0, 0.0, 0.0),
+        ];
+
+        assert_eq!(p[1], catmull_rom(p[0], p[1], p[2], p[3], 0.0));
+        assert_eq!(p[2], catmull_rom(p[0], p[1], p[2], p[3], 1.0));
+        assert_eq!(1.0, catmull_rom(p[0], p[1], p[2], p[3], 0.5).w);
+    }
+
+    #[test]
+    fn bezier_should_start_and_end_at_outer_points() {
+        let p = [
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(1.0, 1.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        ];
+
+        assert_eq!(p[0], cubic_bezier(p[0], p[1], p[2], p[3], 0.0));
+        assert_eq!(p[3], cubic_bezier(p[0], p[1], p[2], p[3], 1.0));
+        assert_eq!(
+            Tuple::point(0.5, 0.75, 0.0),
+            cubic_bezier(p[0], p[1], p[2], p[3], 0.5)
+        );
+    }
+
+    #[test]
+    fn spline_should_split_parameter_evenly_between_segments() {
+        let points = vec![
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::point(2.0, 0.0, 0.0),
+            Tuple::point(3.0, 0.0, 0.0),
+            Tuple::point(4.0, 0.0, 0.0),
+            Tuple::point(5.0, 0.0, 0.0),
+            Tuple::point(6.0, 0.0, 0.0),
+            Tuple::point(7.0, 0.0, 0.0),
+        ];
+        let bezier = Spline::new(SplineKind::Bezier, points.clone());
+        let catmull_rom = Spline::new(SplineKind::CatmullRom, points);
+
+        assert_eq!(2, bezier.segments());
+        assert_eq!(Tuple::point(3.0, 0.0, 0.0), bezier.point_at(0.5));
+        assert_eq!(Tuple::point(6.0, 0.0, 0.0), bezier.point_at(2.0));
+        assert_eq!(7, catmull_rom.segments());
+        assert_eq!(Tuple::point(3.5, 0.0, 0.0), catmull_rom.point_at(0.5));
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), catmull_rom.point_at(-1.0));
+    }
+
+    #[test]
+    fn single_point_spline_should_stay_in_place() {
+        let spline = Spline::new(SplineKind::CatmullRom, vec![Tuple::point(1.0, 2.0, 3.0)]);
+
+        assert_eq!(Tuple::point(1.0, 2.0, 3.0), spline.point_at(0.7));
+    }
+}
@@ -0,0 +1,336 @@
+//! Transformations recorded as a sequence of named steps
+
+use super::{
+    format_f64, rotation_x, rotation_y, rotation_z, scaling, shearing, translation, Angle, Matrix,
+    TransformProvider,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Single named step of a `TransformStack`, keeping the parameters it was
+/// created with
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TransformStep {
+    /// Translation by the given units along each axis
+    Translate {
+        /// Units along the x axis
+        x: f64,
+        /// Units along the y axis
+        y: f64,
+        /// Units along the z axis
+        z: f64,
+    },
+    /// Scaling by the given factors along each axis
+    Scale {
+        /// Factor along the x axis
+        x: f64,
+        /// Factor along the y axis
+        y: f64,
+        /// Factor along the z axis
+        z: f64,
+    },
+    /// Rotation around the x axis
+    RotateX(Angle),
+    /// Rotation around the y axis
+    RotateY(Angle),
+    /// Rotation around the z axis
+    RotateZ(Angle),
+    /// Shearing of each component in proportion to the other two
+    Shear {
+        /// x in proportion to y
+        xy: f64,
+        /// x in proportion to z
+        xz: f64,
+        /// y in proportion to x
+        yx: f64,
+        /// y in proportion to z
+        yz: f64,
+        /// z in proportion to x
+        zx: f64,
+        /// z in proportion to y
+        zy: f64,
+    },
+}
+
+impl TransformStep {
+    /// Returns the transformation matrix of the step
+    pub fn matrix(&self) -> Matrix {
+        match *self {
+            TransformStep::Translate { x, y, z } => translation(x, y, z),
+            TransformStep::Scale { x, y, z } => scaling(x, y, z),
+            TransformStep::RotateX(angle) => rotation_x(angle),
+            TransformStep::RotateY(angle) => rotation_y(angle),
+            TransformStep::RotateZ(angle) => rotation_z(angle),
+            TransformStep::Shear {
+                xy,
+                xz,
+                yx,
+                yz,
+                zx,
+                zy,
+            } => shearing(xy, xz, yx, yz, zx, zy),
+        }
+    }
+}
+
+impl fmt::Display for TransformStep {
+    /// Formats the step as a call with its parameters, rounded to the
+    /// formatter precision or five decimals and angles given in degrees
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(5);
+        let n = |value: f64| format_f64(value, precision);
+
+        match *self {
+            TransformStep::Translate { x, y, z } => {
+                write!(f, "translate({}, {}, {})", n(x), n(y), n(z))
+            }
+            TransformStep::Scale { x, y, z } => write!(f, "scale({}, {}, {})", n(x), n(y), n(z)),
+            TransformStep::RotateX(angle) => write!(f, "rotate_x({}°)", n(angle.degrees())),
+            TransformStep::RotateY(angle) => write!(f, "rotate_y({}°)", n(angle.degrees())),
+            TransformStep::RotateZ(angle) => write!(f, "rotate_z({}°)", n(angle.degrees())),
+            TransformStep::Shear {
+                xy,
+                xz,
+                yx,
+                yz,
+                zx,
+                zy,
+            } => write!(
+                f,
+                "shear({}, {}, {}, {}, {}, {})",
+                n(xy),
+                n(xz),
+                n(yx),
+                n(yz),
+                n(zx),
+                n(zy)
+            ),
+        }
+    }
+}
+
+/// Transformation recorded as a sequence of named steps, so that scene files
+/// and debug output show the intent behind a transformation rather than its
+/// sixteen matrix elements
+///
+/// Steps apply to an object in the order they were recorded: the first step
+/// is applied first. This is the reverse of chaining `Matrix` methods,
+/// where the last call is applied first.
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{rotation_y, scaling, translation, Angle, TransformStack};
+///
+/// let stack = TransformStack::new()
+///     .scale(2.0, 2.0, 2.0)
+///     .rotate_y(Angle::from_degrees(90.0))
+///     .translate(0.0, 1.0, 0.0);
+///
+/// assert_eq!(
+///     &(&translation(0.0, 1.0, 0.0) * &rotation_y(Angle::from_degrees(90.0)))
+///         * &scaling(2.0, 2.0, 2.0),
+///     stack.matrix()
+/// );
+/// assert_eq!(
+///     "scale(2.0, 2.0, 2.0) then rotate_y(90.0°) then translate(0.0, 1.0, 0.0)",
+///     format!("{:.1}", stack)
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct TransformStack {
+    /// Steps of the transformation, in the order they are applied
+    pub steps: Vec<TransformStep>,
+}
+
+impl TransformStack {
+    /// Returns a new stack without any steps, i.e. the identity
+    pub fn new() -> TransformStack {
+        TransformStack { steps: Vec::new() }
+    }
+
+    /// Returns the stack with the given step appended
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - step applied after all recorded steps
+    pub fn then(mut self, step: TransformStep) -> TransformStack {
+        self.steps.push(step);
+        self
+    }
+
+    /// Returns the stack with a translation appended
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - units along the x axis
+    /// * `y` - units along the y axis
+    /// * `z` - units along the z axis
+    pub fn translate(self, x: f64, y: f64, z: f64) -> TransformStack {
+        self.then(TransformStep::Translate { x, y, z })
+    }
+
+    /// Returns the stack with a scaling appended
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - factor along the x axis
+    /// * `y` - factor along the y axis
+    /// * `z` - factor along the z axis
+    pub fn scale(self, x: f64, y: f64, z: f64) -> TransformStack {
+        self.then(TransformStep::Scale { x, y, z })
+    }
+
+    /// Returns the stack with a rotation around the x axis appended
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_x(self, angle: impl Into<Angle>) -> TransformStack {
+        self.then(TransformStep::RotateX(angle.into()))
+    }
+
+    /// Returns the stack with a rotation around the y axis appended
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_y(self, angle: impl Into<Angle>) -> TransformStack {
+        self.then(TransformStep::RotateY(angle.into()))
+    }
+
+    /// Returns the stack with a rotation around the z axis appended
+    ///
+    /// # Arguments
+    ///
+    /// * `angle` - angle to rotate by, a bare `f64` being radians
+    pub fn rotate_z(self, angle: impl Into<Angle>) -> TransformStack {
+        self.then(TransformStep::RotateZ(angle.into()))
+    }
+
+    /// Returns the stack with a shearing appended
+    ///
+    /// # Arguments
+    ///
+    /// * `xy` - x in proportion to y
+    /// * `xz` - x in proportion to z
+    /// * `yx` - y in proportion to x
+    /// * `yz` - y in proportion to z
+    /// * `zx` - z in proportion to x
+    /// * `zy` - z in proportion to y
+    pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> TransformStack {
+        self.then(TransformStep::Shear {
+            xy,
+            xz,
+            yx,
+            yz,
+            zx,
+            zy,
+        })
+    }
+
+    /// Returns the transformation matrix composed of all steps
+    pub fn matrix(&self) -> Matrix {
+        self.steps
+            .iter()
+            .fold(Matrix::identity(4), |m, step| &step.matrix() * &m)
+    }
+}
+
+impl From<&TransformStack> for Matrix {
+    fn from(stack: &TransformStack) -> Matrix {
+        stack.matrix()
+    }
+}
+
+impl TransformProvider for TransformStack {
+    fn transform_at(&self, _time: f64) -> Matrix {
+        self.matrix()
+    }
+}
+
+impl fmt::Display for TransformStack {
+    /// Formats the steps in order, or `identity` if there are none
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "identity");
+        }
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " then ")?;
+            }
+            fmt::Display::fmt(step, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Tuple;
+
+    #[test]
+    fn steps_should_apply_in_recorded_order() {
+        let stack = TransformStack::new()
+            .translate(1.0, 0.0, 0.0)
+            .scale(2.0, 2.0, 2.0);
+
+        assert_eq!(
+            Tuple::point(4.0, 2.0, 2.0),
+            stack.matrix() * Tuple::point(1.0, 1.0, 1.0)
+        );
+        assert_eq!(Matrix::identity(4), TransformStack::new().matrix());
+    }
+
+    #[test]
+    fn every_step_should_match_its_transformation_function() {
+        let stack = TransformStack::new()
+            .rotate_x(0.5)
+            .rotate_z(Angle::from_degrees(30.0))
+            .shear(1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        let expected = &(&shearing(1.0, 0.0, 0.0, 0.0, 0.0, 1.0)
+            * &rotation_z(Angle::from_degrees(30.0)))
+            * &rotation_x(0.5);
+
+        assert_eq!(expected, Matrix::from(&stack));
+        assert_eq!(expected, stack.transform_at(3.0));
+    }
+
+    #[test]
+    fn should_display_steps_with_angles_in_degrees() {
+        let stack = TransformStack::new()
+            .rotate_x(Angle::from_degrees(45.0))
+            .shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.5);
+
+        assert_eq!("identity", format!("{}", TransformStack::new()));
+        assert_eq!(
+            "rotate_x(45.00°) then shear(1.00, 0.00, 0.00, 0.00, 0.00, 0.50)",
+            format!("{:.2}", stack)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_json() {
+        let stack = TransformStack::new()
+            .translate(1.0, 2.0, 3.0)
+            .rotate_y(Angle::from_degrees(90.0));
+        let json = serde_json::to_string(&stack).unwrap();
+
+        assert!(json.starts_with(r#"[{"translate":{"x":1.0,"y":2.0,"z":3.0}}"#));
+        assert_eq!(stack, serde_json::from_str(&json).unwrap());
+    }
+}
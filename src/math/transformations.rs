@@ -1,6 +1,9 @@
 //! Matrix tranformation operation
 
+use super::angle::Angle;
 use super::matrix::Matrix;
+use super::tuple::Tuple;
+use alloc::vec;
 
 /// Returns a translation matrix with the given translation units
 ///
@@ -10,7 +13,7 @@ use super::matrix::Matrix;
 /// * `y` - units in y axis
 /// * `z` - units in z axis
 pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+    let mut m = Matrix::identity(4);
 
     m[(0, 3)] = x;
     m[(1, 3)] = y;
@@ -27,7 +30,7 @@ pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
 /// * `y` - scaling in y axis
 /// * `z` - scaling in z axis
 pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+    let mut m = Matrix::identity(4);
 
     m[(0, 0)] = x;
     m[(1, 1)] = y;
@@ -40,14 +43,15 @@ pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
 ///
 /// # Arguments:
 ///
-/// * `rads` - radians to rotate
-pub fn rotation_x(rads: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+/// * `angle` - angle to rotate by, a bare `f64` being radians
+pub fn rotation_x(angle: impl Into<Angle>) -> Matrix {
+    let angle = angle.into();
+    let mut m = Matrix::identity(4);
 
-    m[(1, 1)] = rads.cos();
-    m[(2, 1)] = rads.sin();
-    m[(1, 2)] = -rads.sin();
-    m[(2, 2)] = rads.cos();
+    m[(1, 1)] = angle.cos();
+    m[(2, 1)] = angle.sin();
+    m[(1, 2)] = -angle.sin();
+    m[(2, 2)] = angle.cos();
 
     m
 }
@@ -56,14 +60,15 @@ pub fn rotation_x(rads: f64) -> Matrix {
 ///
 /// # Arguments:
 ///
-/// * `rads` - radians to rotate
-pub fn rotation_y(rads: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+/// * `angle` - angle to rotate by, a bare `f64` being radians
+pub fn rotation_y(angle: impl Into<Angle>) -> Matrix {
+    let angle = angle.into();
+    let mut m = Matrix::identity(4);
 
-    m[(0, 0)] = rads.cos();
-    m[(2, 0)] = -rads.sin();
-    m[(0, 2)] = rads.sin();
-    m[(2, 2)] = rads.cos();
+    m[(0, 0)] = angle.cos();
+    m[(2, 0)] = -angle.sin();
+    m[(0, 2)] = angle.sin();
+    m[(2, 2)] = angle.cos();
 
     m
 }
@@ -72,19 +77,21 @@ pub fn rotation_y(rads: f64) -> Matrix {
 ///
 /// # Arguments:
 ///
-/// * `rads` - radians to rotate
-pub fn rotation_z(rads: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+/// * `angle` - angle to rotate by, a bare `f64` being radians
+pub fn rotation_z(angle: impl Into<Angle>) -> Matrix {
+    let angle = angle.into();
+    let mut m = Matrix::identity(4);
 
-    m[(0, 0)] = rads.cos();
-    m[(1, 0)] = rads.sin();
-    m[(0, 1)] = -rads.sin();
-    m[(1, 1)] = rads.cos();
+    m[(0, 0)] = angle.cos();
+    m[(1, 0)] = angle.sin();
+    m[(0, 1)] = -angle.sin();
+    m[(1, 1)] = angle.cos();
 
     m
 }
 
-/// Returns a skewing matrix
+/// Returns a shearing matrix, moving each component in proportion to the
+/// other two
 ///
 /// # Arguments:
 ///
@@ -94,8 +101,8 @@ pub fn rotation_z(rads: f64) -> Matrix {
 /// * `yz` - y in proportion to z
 /// * `zx` - z in proportion to x
 /// * `zy` - z in proportion to y
-pub fn skewing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
-    let mut m = Matrix::new(4, 4);
+pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+    let mut m = Matrix::identity(4);
 
     m[(1, 0)] = yx;
     m[(2, 0)] = zx;
@@ -107,9 +114,45 @@ pub fn skewing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
     m
 }
 
+/// Returns a shearing matrix, see `shearing`
+///
+/// # Arguments:
+///
+/// * `xy` - x in proportion to y
+/// * `xz` - x in proportion to z
+/// * `yx` - y in proportion to x
+/// * `yz` - y in proportion to z
+/// * `zx` - z in proportion to x
+/// * `zy` - z in proportion to y
+#[deprecated(note = "renamed to `shearing`, as in The Ray Tracer Challenge")]
+pub fn skewing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+    shearing(xy, xz, yx, yz, zx, zy)
+}
+
+/// Returns a view transformation matrix orienting the world relative to an
+/// eye
+///
+/// # Arguments:
+///
+/// * `from` - point where the eye is positioned
+/// * `to` - point the eye is looking at
+/// * `up` - vector pointing approximately upwards
+pub fn view_transform(from: Tuple, to: Tuple, up: Tuple) -> Matrix {
+    let forward = (to - from).normalize();
+    let left = Tuple::cross(&forward, &up.normalize());
+    let true_up = Tuple::cross(&left, &forward);
+    let orientation = Matrix::from(vec![
+        vec![left.x, left.y, left.z, 0.0],
+        vec![true_up.x, true_up.y, true_up.z, 0.0],
+        vec![-forward.x, -forward.y, -forward.z, 0.0],
+        vec![0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    orientation * translation(-from.x, -from.y, -from.z)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::tuple::Tuple;
     use super::*;
 
     #[test]
@@ -119,7 +162,7 @@ mod tests {
 
         assert_eq!(transform * p, Tuple::point(2.0, 1.0, 7.0));
         assert_eq!(
-            Matrix::new(4, 4).translate(5.0, -3.0, 2.0) * p,
+            Matrix::identity(4).translate(5.0, -3.0, 2.0) * p,
             Tuple::point(2.0, 1.0, 7.0)
         );
     }
@@ -131,7 +174,7 @@ mod tests {
 
         assert_eq!(transform.inverse() * p, Tuple::point(-8.0, 7.0, 3.0));
         assert_eq!(
-            Matrix::new(4, 4).translate(5.0, -3.0, 2.0).inverse() * p,
+            Matrix::identity(4).translate(5.0, -3.0, 2.0).inverse() * p,
             Tuple::point(-8.0, 7.0, 3.0)
         );
     }
@@ -143,7 +186,7 @@ mod tests {
 
         assert_eq!(transform * p, Tuple::vector(-3.0, 4.0, 5.0));
         assert_eq!(
-            Matrix::new(4, 4).translate(5.0, -3.0, 2.0) * p,
+            Matrix::identity(4).translate(5.0, -3.0, 2.0) * p,
             Tuple::vector(-3.0, 4.0, 5.0)
         );
     }
@@ -155,7 +198,7 @@ mod tests {
 
         assert_eq!(transform * p, Tuple::point(-8.0, 18.0, 32.0));
         assert_eq!(
-            Matrix::new(4, 4).scale(2.0, 3.0, 4.0) * p,
+            Matrix::identity(4).scale(2.0, 3.0, 4.0) * p,
             Tuple::point(-8.0, 18.0, 32.0)
         );
     }
@@ -167,7 +210,7 @@ mod tests {
 
         assert_eq!(transform * v, Tuple::vector(-8.0, 18.0, 32.0));
         assert_eq!(
-            Matrix::new(4, 4).scale(2.0, 3.0, 4.0) * v,
+            Matrix::identity(4).scale(2.0, 3.0, 4.0) * v,
             Tuple::vector(-8.0, 18.0, 32.0)
         );
     }
@@ -179,7 +222,7 @@ mod tests {
 
         assert_eq!(transform.inverse() * v, Tuple::vector(-2.0, 2.0, 2.0));
         assert_eq!(
-            Matrix::new(4, 4).scale(2.0, 3.0, 4.0).inverse() * v,
+            Matrix::identity(4).scale(2.0, 3.0, 4.0).inverse() * v,
             Tuple::vector(-2.0, 2.0, 2.0)
         );
     }
@@ -191,7 +234,7 @@ mod tests {
 
         assert_eq!(transform.inverse() * v, Tuple::vector(-2.0, 3.0, 4.0));
         assert_eq!(
-            Matrix::new(4, 4).scale(-1.0, 1.0, 1.0).inverse() * v,
+            Matrix::identity(4).scale(-1.0, 1.0, 1.0).inverse() * v,
             Tuple::vector(-2.0, 3.0, 4.0)
         );
     }
@@ -199,8 +242,8 @@ mod tests {
     #[test]
     fn should_rotate_correctly_around_x_axis() {
         let p = Tuple::point(0.0, 1.0, 0.0);
-        let a = rotation_x(std::f64::consts::PI / 4.0);
-        let b = rotation_x(std::f64::consts::PI / 2.0);
+        let a = rotation_x(core::f64::consts::PI / 4.0);
+        let b = rotation_x(core::f64::consts::PI / 2.0);
 
         assert_eq!(
             a * p,
@@ -209,11 +252,11 @@ mod tests {
         assert_eq!(b * p, Tuple::point(0.0, 0.0, 1.0));
 
         assert_eq!(
-            Matrix::new(4, 4).rotate_x(std::f64::consts::PI / 4.0) * p,
+            Matrix::identity(4).rotate_x(core::f64::consts::PI / 4.0) * p,
             Tuple::point(0.0, 2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0)
         );
         assert_eq!(
-            Matrix::new(4, 4).rotate_x(std::f64::consts::PI / 2.0) * p,
+            Matrix::identity(4).rotate_x(core::f64::consts::PI / 2.0) * p,
             Tuple::point(0.0, 0.0, 1.0)
         );
     }
@@ -221,7 +264,7 @@ mod tests {
     #[test]
     fn inverse_of_rotation_should_rotate_in_opposite_direction() {
         let p = Tuple::point(0.0, 1.0, 0.0);
-        let a = rotation_x(std::f64::consts::PI / 4.0);
+        let a = rotation_x(core::f64::consts::PI / 4.0);
 
         assert_eq!(
             a.inverse() * p,
@@ -229,8 +272,8 @@ mod tests {
         );
 
         assert_eq!(
-            Matrix::new(4, 4)
-                .rotate_x(std::f64::consts::PI / 4.0)
+            Matrix::identity(4)
+                .rotate_x(core::f64::consts::PI / 4.0)
                 .inverse()
                 * p,
             Tuple::point(0.0, 2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0)
@@ -240,8 +283,8 @@ mod tests {
     #[test]
     fn should_rotate_correctly_around_y_axis() {
         let p = Tuple::point(0.0, 0.0, 1.0);
-        let a = rotation_y(std::f64::consts::PI / 4.0);
-        let b = rotation_y(std::f64::consts::PI / 2.0);
+        let a = rotation_y(core::f64::consts::PI / 4.0);
+        let b = rotation_y(core::f64::consts::PI / 2.0);
 
         assert_eq!(
             a * p,
@@ -250,11 +293,11 @@ mod tests {
         assert_eq!(b * p, Tuple::point(1.0, 0.0, 0.0));
 
         assert_eq!(
-            Matrix::new(4, 4).rotate_y(std::f64::consts::PI / 4.0) * p,
+            Matrix::identity(4).rotate_y(core::f64::consts::PI / 4.0) * p,
             Tuple::point(2.0_f64.sqrt() / 2.0, 0.0, 2.0_f64.sqrt() / 2.0)
         );
         assert_eq!(
-            Matrix::new(4, 4).rotate_y(std::f64::consts::PI / 2.0) * p,
+            Matrix::identity(4).rotate_y(core::f64::consts::PI / 2.0) * p,
             Tuple::point(1.0, 0.0, 0.0)
         );
     }
@@ -262,8 +305,8 @@ mod tests {
     #[test]
     fn should_rotate_correctly_around_z_axis() {
         let p = Tuple::point(0.0, 1.0, 0.0);
-        let a = rotation_z(std::f64::consts::PI / 4.0);
-        let b = rotation_z(std::f64::consts::PI / 2.0);
+        let a = rotation_z(core::f64::consts::PI / 4.0);
+        let b = rotation_z(core::f64::consts::PI / 2.0);
 
         assert_eq!(
             a * p,
@@ -272,69 +315,123 @@ mod tests {
         assert_eq!(b * p, Tuple::point(-1.0, 0.0, 0.0));
 
         assert_eq!(
-            Matrix::new(4, 4).rotate_z(std::f64::consts::PI / 4.0) * p,
+            Matrix::identity(4).rotate_z(core::f64::consts::PI / 4.0) * p,
             Tuple::point(-2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0.0)
         );
         assert_eq!(
-            Matrix::new(4, 4).rotate_z(std::f64::consts::PI / 2.0) * p,
+            Matrix::identity(4).rotate_z(core::f64::consts::PI / 2.0) * p,
             Tuple::point(-1.0, 0.0, 0.0)
         );
     }
 
     #[test]
-    fn skewing_should_transform_correctly() {
-        let transform = skewing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    fn shearing_should_transform_correctly() {
+        let transform = shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(5.0, 3.0, 4.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * p,
+            Matrix::identity(4).shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.0) * p,
             Tuple::point(5.0, 3.0, 4.0)
         );
 
-        let transform = skewing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0);
+        let transform = shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(6.0, 3.0, 4.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(0.0, 1.0, 0.0, 0.0, 0.0, 0.0) * p,
+            Matrix::identity(4).shear(0.0, 1.0, 0.0, 0.0, 0.0, 0.0) * p,
             Tuple::point(6.0, 3.0, 4.0)
         );
 
-        let transform = skewing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0);
+        let transform = shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(2.0, 5.0, 4.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(0.0, 0.0, 1.0, 0.0, 0.0, 0.0) * p,
+            Matrix::identity(4).shear(0.0, 0.0, 1.0, 0.0, 0.0, 0.0) * p,
             Tuple::point(2.0, 5.0, 4.0)
         );
 
-        let transform = skewing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+        let transform = shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(2.0, 7.0, 4.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(0.0, 0.0, 0.0, 1.0, 0.0, 0.0) * p,
+            Matrix::identity(4).shear(0.0, 0.0, 0.0, 1.0, 0.0, 0.0) * p,
             Tuple::point(2.0, 7.0, 4.0)
         );
 
-        let transform = skewing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0);
+        let transform = shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(2.0, 3.0, 6.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(0.0, 0.0, 0.0, 0.0, 1.0, 0.0) * p,
+            Matrix::identity(4).shear(0.0, 0.0, 0.0, 0.0, 1.0, 0.0) * p,
             Tuple::point(2.0, 3.0, 6.0)
         );
 
-        let transform = skewing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        let transform = shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
         let p = Tuple::point(2.0, 3.0, 4.0);
 
         assert_eq!(transform * p, Tuple::point(2.0, 3.0, 7.0));
         assert_eq!(
-            Matrix::new(4, 4).skew(0.0, 0.0, 0.0, 0.0, 0.0, 1.0) * p,
+            Matrix::identity(4).shear(0.0, 0.0, 0.0, 0.0, 0.0, 1.0) * p,
             Tuple::point(2.0, 3.0, 7.0)
         );
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn skewing_should_remain_an_alias_of_shearing() {
+        let transform = shearing(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+
+        assert_eq!(transform, skewing(1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+        assert_eq!(
+            transform,
+            Matrix::identity(4).skew(1.0, 2.0, 3.0, 4.0, 5.0, 6.0)
+        );
+    }
+
+    #[test]
+    fn view_transform_should_be_identity_for_default_orientation() {
+        let from = Tuple::point(0.0, 0.0, 0.0);
+        let to = Tuple::point(0.0, 0.0, -1.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(Matrix::identity(4), view_transform(from, to, up));
+    }
+
+    #[test]
+    fn view_transform_looking_in_positive_z_should_reflect() {
+        let from = Tuple::point(0.0, 0.0, 0.0);
+        let to = Tuple::point(0.0, 0.0, 1.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(scaling(-1.0, 1.0, -1.0), view_transform(from, to, up));
+    }
+
+    #[test]
+    fn view_transform_should_move_the_world() {
+        let from = Tuple::point(0.0, 0.0, 8.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(translation(0.0, 0.0, -8.0), view_transform(from, to, up));
+    }
+
+    #[test]
+    fn should_compute_arbitrary_view_transform() {
+        let from = Tuple::point(1.0, 3.0, 2.0);
+        let to = Tuple::point(4.0, -2.0, 8.0);
+        let up = Tuple::vector(1.0, 1.0, 0.0);
+        let reference = Matrix::from(vec![
+            vec![-0.50709, 0.50709, 0.67612, -2.36643],
+            vec![0.76772, 0.60609, 0.12122, -2.82843],
+            vec![-0.35857, 0.59761, -0.71714, 0.00000],
+            vec![0.00000, 0.00000, 0.00000, 1.00000],
+        ]);
+
+        assert_eq!(reference, view_transform(from, to, up));
+    }
 }
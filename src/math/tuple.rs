@@ -1,12 +1,18 @@
 //! Matrix representation and operations
 //!
-use std::convert::From;
-use std::ops::{Add, Div, Index, Mul, Neg, Sub};
-
-const EPSILON: f64 = 0.00001;
+use super::format_f64;
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::EPSILON;
+use alloc::vec::Vec;
+use core::convert::From;
+use core::fmt;
+use core::ops::{Add, Div, Index, Mul, Neg, Sub};
 
 /// 3 Dimensional Tuple struct representing points or vectors.
 #[derive(PartialOrd, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Tuple {
     /// x-value of the tuple
     pub x: f64,
@@ -17,7 +23,6 @@ pub struct Tuple {
     /// w-value of the tuple. A non-zero value indicates a point, otherwise a
     /// vector
     pub w: f64,
-    _private: (),
 }
 
 impl Tuple {
@@ -30,13 +35,7 @@ impl Tuple {
     /// * `z` - z value of tuple
     /// * `w` - w value of tuple
     pub fn new(x: f64, y: f64, z: f64, w: f64) -> Tuple {
-        Tuple {
-            x,
-            y,
-            z,
-            w,
-            _private: (),
-        }
+        Tuple { x, y, z, w }
     }
 
     /// Creates a new point Tuple where `w` is `1.0`
@@ -47,13 +46,7 @@ impl Tuple {
     /// * `y` - y value of point
     /// * `z` - z value of point
     pub fn point(x: f64, y: f64, z: f64) -> Tuple {
-        Tuple {
-            x,
-            y,
-            z,
-            w: 1.0,
-            _private: (),
-        }
+        Tuple { x, y, z, w: 1.0 }
     }
 
     /// Creates a new vector Tuple where `w` is `0.0`
@@ -64,13 +57,7 @@ impl Tuple {
     /// * `y` - y value of point
     /// * `z` - z value of point
     pub fn vector(x: f64, y: f64, z: f64) -> Tuple {
-        Tuple {
-            x,
-            y,
-            z,
-            w: 0.0,
-            _private: (),
-        }
+        Tuple { x, y, z, w: 0.0 }
     }
 
     /// Determines if given Tuple is a vector
@@ -83,6 +70,11 @@ impl Tuple {
         self.w != 0.0
     }
 
+    /// Determines if every coordinate of given Tuple is finite
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
     /// Computes the magnitude of given vector Tuple using Pythagoras' theorem
     pub fn magnitude(&self) -> f64 {
         (self.x.powf(2.0) + self.y.powf(2.0) + self.z.powf(2.0) + self.w.powf(2.0)).sqrt()
@@ -116,6 +108,109 @@ impl Tuple {
             a.x * b.y - a.y * b.x,
         )
     }
+
+    /// Returns the x, y and z components of the Tuple as a vector
+    pub fn xyz(&self) -> Tuple {
+        Tuple::vector(self.x, self.y, self.z)
+    }
+
+    /// Returns the Tuple with the absolute value of every component
+    pub fn abs(&self) -> Tuple {
+        Tuple::new(self.x.abs(), self.y.abs(), self.z.abs(), self.w.abs())
+    }
+
+    /// Returns the smallest of the x, y and z components
+    pub fn min_component(&self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Returns the largest of the x, y and z components
+    pub fn max_component(&self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// Computes the element-wise minimum of two given tuples
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - tuple of LHS
+    /// * `b` - tuple of RHS
+    pub fn min(a: &Tuple, b: &Tuple) -> Tuple {
+        Tuple::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z), a.w.min(b.w))
+    }
+
+    /// Computes the element-wise maximum of two given tuples
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - tuple of LHS
+    /// * `b` - tuple of RHS
+    pub fn max(a: &Tuple, b: &Tuple) -> Tuple {
+        Tuple::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z), a.w.max(b.w))
+    }
+
+    /// Restricts every component of the Tuple to lie between the matching
+    /// components of `lower` and `upper`
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - smallest allowed value of each component
+    /// * `upper` - largest allowed value of each component
+    pub fn clamp(&self, lower: &Tuple, upper: &Tuple) -> Tuple {
+        Tuple::min(&Tuple::max(self, lower), upper)
+    }
+}
+
+/// Canonical, hashable form of a `Tuple`
+///
+/// Every component is snapped to a grid of `EPSILON` sized steps so that
+/// tuples can be used as keys in a `HashMap` or `BTreeMap`. Tuples within
+/// `EPSILON` of each other usually, but not always, share the same key since
+/// they may fall on different sides of a grid boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuantizedTuple {
+    x: i64,
+    y: i64,
+    z: i64,
+    w: i64,
+}
+
+impl QuantizedTuple {
+    /// Returns the quantized form of the given tuple
+    ///
+    /// # Arguments:
+    ///
+    /// * `tuple` - tuple to quantize
+    pub fn new(tuple: Tuple) -> QuantizedTuple {
+        QuantizedTuple {
+            x: quantize(tuple.x),
+            y: quantize(tuple.y),
+            z: quantize(tuple.z),
+            w: quantize(tuple.w),
+        }
+    }
+}
+
+impl From<Tuple> for QuantizedTuple {
+    fn from(tuple: Tuple) -> QuantizedTuple {
+        QuantizedTuple::new(tuple)
+    }
+}
+
+impl From<QuantizedTuple> for Tuple {
+    fn from(q: QuantizedTuple) -> Tuple {
+        Tuple::new(
+            q.x as f64 * EPSILON,
+            q.y as f64 * EPSILON,
+            q.z as f64 * EPSILON,
+            q.w as f64 * EPSILON,
+        )
+    }
+}
+
+/// Snaps a value to the closest multiple of `EPSILON`
+pub(crate) fn quantize(v: f64) -> i64 {
+    (v / EPSILON).round() as i64
 }
 
 impl Add for Tuple {
@@ -192,6 +287,26 @@ impl PartialEq for Tuple {
     }
 }
 
+impl fmt::Display for Tuple {
+    /// Formats the tuple as a point, a vector or a plain tuple, rounded to
+    /// the formatter precision or five decimals
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(5);
+        let x = format_f64(self.x, precision);
+        let y = format_f64(self.y, precision);
+        let z = format_f64(self.z, precision);
+
+        if self.w == 1.0 {
+            write!(f, "point({}, {}, {})", x, y, z)
+        } else if self.w == 0.0 {
+            write!(f, "vector({}, {}, {})", x, y, z)
+        } else {
+            let w = format_f64(self.w, precision);
+            write!(f, "tuple({}, {}, {}, {})", x, y, z, w)
+        }
+    }
+}
+
 impl Index<usize> for Tuple {
     type Output = f64;
 
@@ -227,28 +342,28 @@ mod tests {
         assert_eq!(-4.2, t.y);
         assert_eq!(3.1, t.z);
         assert_eq!(1.0, t.w);
-        assert_eq!(true, t.is_point());
-        assert_eq!(false, t.is_vector());
+        assert!(t.is_point());
+        assert!(!t.is_vector());
 
         t = Tuple::new(4.3, -4.2, 3.1, 0.0);
         assert_eq!(4.3, t.x);
         assert_eq!(-4.2, t.y);
         assert_eq!(3.1, t.z);
         assert_eq!(0.0, t.w);
-        assert_eq!(false, t.is_point());
-        assert_eq!(true, t.is_vector());
+        assert!(!t.is_point());
+        assert!(t.is_vector());
     }
 
     #[test]
     fn should_create_correct_types() {
         let mut reference = Tuple::new(4.0, -4.0, 3.0, 1.0);
         let mut t = Tuple::point(4.0, -4.0, 3.0);
-        assert_eq!(true, t.is_point());
+        assert!(t.is_point());
         assert_eq!(reference, t);
 
         reference.w = 0.0;
         t = Tuple::vector(4.0, -4.0, 3.0);
-        assert_eq!(true, t.is_vector());
+        assert!(t.is_vector());
         assert_eq!(reference, t);
     }
 
@@ -388,4 +503,53 @@ mod tests {
 
         assert_eq!(Tuple::cross(&b, &a), reference);
     }
+
+    #[test]
+    fn should_display_points_vectors_and_tuples() {
+        let p = Tuple::point(1.0, -2.0, 0.5);
+        let v = Tuple::vector(1.0 / 3.0, 0.0, -0.0);
+        let t = Tuple::new(1.0, 2.0, 3.0, 2.0);
+
+        assert_eq!("point(1.00000, -2.00000, 0.50000)", format!("{}", p));
+        assert_eq!("vector(0.33, 0.00, 0.00)", format!("{:.2}", v));
+        assert_eq!("tuple(1.0, 2.0, 3.0, 2.0)", format!("{:.1}", t));
+    }
+
+    #[test]
+    fn nearly_equal_tuples_should_share_quantized_key() {
+        use std::collections::HashSet;
+
+        let a = Tuple::point(1.0, 2.0, 3.0);
+        let b = Tuple::point(1.0 + 1e-9, 2.0, 3.0 - 1e-9);
+        let c = Tuple::vector(1.0, 2.0, 3.0);
+        let set: HashSet<QuantizedTuple> = [a, b, c].iter().map(|t| (*t).into()).collect();
+
+        assert_eq!(2, set.len());
+        assert!(set.contains(&QuantizedTuple::new(a)));
+        assert_eq!(a, Tuple::from(QuantizedTuple::new(b)));
+    }
+
+    #[test]
+    fn should_swizzle_and_take_absolute_components() {
+        let t = Tuple::point(-1.0, 2.0, -3.0);
+
+        assert_eq!(Tuple::vector(-1.0, 2.0, -3.0), t.xyz());
+        assert_eq!(Tuple::point(1.0, 2.0, 3.0), t.abs());
+        assert_eq!(-3.0, t.min_component());
+        assert_eq!(2.0, t.max_component());
+    }
+
+    #[test]
+    fn should_compute_element_wise_min_max_and_clamp() {
+        let a = Tuple::point(1.0, 5.0, -2.0);
+        let b = Tuple::point(3.0, -1.0, 0.0);
+
+        assert_eq!(Tuple::point(1.0, -1.0, -2.0), Tuple::min(&a, &b));
+        assert_eq!(Tuple::point(3.0, 5.0, 0.0), Tuple::max(&a, &b));
+
+        let lower = Tuple::point(0.0, 0.0, 0.0);
+        let upper = Tuple::point(2.0, 2.0, 2.0);
+
+        assert_eq!(Tuple::point(1.0, 2.0, 0.0), a.clamp(&lower, &upper));
+    }
 }
@@ -0,0 +1,244 @@
+//! Two dimensional points and vectors
+//!
+#[cfg(not(any(feature = "std", test)))]
+use super::Float;
+use super::EPSILON;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Two dimensional vector, the difference between two `Point2`s
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2 {
+    /// x-value of the vector
+    pub x: f64,
+    /// y-value of the vector
+    pub y: f64,
+}
+
+/// Two dimensional position, such as texture coordinates, a sample or a
+/// location in canvas space
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point2 {
+    /// x-value of the point
+    pub x: f64,
+    /// y-value of the point
+    pub y: f64,
+}
+
+impl Vec2 {
+    /// Creates a new Vec2
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - x value of vector
+    /// * `y` - y value of vector
+    pub fn new(x: f64, y: f64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    /// Computes the length of the vector
+    pub fn magnitude(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Normalizes the vector to a unit vector
+    pub fn normalize(self) -> Vec2 {
+        self / self.magnitude()
+    }
+
+    /// Computes the dot product of two given vectors
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - vector of LHS
+    /// * `b` - vector of RHS
+    pub fn dot(a: &Vec2, b: &Vec2) -> f64 {
+        a.x * b.x + a.y * b.y
+    }
+
+    /// Computes the z-component of the cross product of two given vectors,
+    /// positive if `b` lies counter-clockwise of `a`
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - vector of LHS
+    /// * `b` - vector of RHS
+    pub fn cross(a: &Vec2, b: &Vec2) -> f64 {
+        a.x * b.y - a.y * b.x
+    }
+}
+
+impl Point2 {
+    /// Creates a new Point2
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - x value of point
+    /// * `y` - y value of point
+    pub fn new(x: f64, y: f64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// Computes the distance between two given points
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - first point
+    /// * `b` - second point
+    pub fn distance(a: &Point2, b: &Point2) -> f64 {
+        (*b - *a).magnitude()
+    }
+
+    /// Returns the point interpolated between this point and another one
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - point reached when `t` is one
+    /// * `t` - fraction of the way towards `other`
+    pub fn lerp(&self, other: &Point2, t: f64) -> Point2 {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, other: f64) -> Vec2 {
+        Vec2::new(self.x * other, self.y * other)
+    }
+}
+
+impl Div<f64> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, other: f64) -> Vec2 {
+        Vec2::new(self.x / other, self.y / other)
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Vec2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Vec2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Point2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl PartialEq for Vec2 {
+    fn eq(&self, other: &Vec2) -> bool {
+        (self.x - other.x).abs() < EPSILON && (self.y - other.y).abs() < EPSILON
+    }
+}
+
+impl PartialEq for Point2 {
+    fn eq(&self, other: &Point2) -> bool {
+        (self.x - other.x).abs() < EPSILON && (self.y - other.y).abs() < EPSILON
+    }
+}
+
+impl From<(f64, f64)> for Vec2 {
+    fn from((x, y): (f64, f64)) -> Vec2 {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<(f64, f64)> for Point2 {
+    fn from((x, y): (f64, f64)) -> Point2 {
+        Point2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (f64, f64) {
+    fn from(v: Vec2) -> (f64, f64) {
+        (v.x, v.y)
+    }
+}
+
+impl From<Point2> for (f64, f64) {
+    fn from(p: Point2) -> (f64, f64) {
+        (p.x, p.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_combine_points_and_vectors() {
+        let a = Point2::new(1.0, 2.0);
+        let b = Point2::new(4.0, 6.0);
+        let v = b - a;
+
+        assert_eq!(Vec2::new(3.0, 4.0), v);
+        assert_eq!(b, a + v);
+        assert_eq!(a, b - v);
+        assert_eq!(5.0, v.magnitude());
+        assert_eq!(5.0, Point2::distance(&a, &b));
+        assert_eq!(Point2::new(2.5, 4.0), a.lerp(&b, 0.5));
+    }
+
+    #[test]
+    fn should_apply_vector_operators() {
+        let a = Vec2::new(1.0, -2.0);
+        let b = Vec2::new(3.0, 4.0);
+
+        assert_eq!(Vec2::new(4.0, 2.0), a + b);
+        assert_eq!(Vec2::new(-2.0, -6.0), a - b);
+        assert_eq!(Vec2::new(-1.0, 2.0), -a);
+        assert_eq!(Vec2::new(2.0, -4.0), a * 2.0);
+        assert_eq!(Vec2::new(0.6, 0.8), b.normalize());
+        assert_eq!(-5.0, Vec2::dot(&a, &b));
+        assert_eq!(10.0, Vec2::cross(&a, &b));
+    }
+
+    #[test]
+    fn should_convert_from_and_to_pairs() {
+        let p: Point2 = (1.0, 2.0).into();
+        let pair: (f64, f64) = Vec2::new(3.0, 4.0).into();
+
+        assert_eq!(Point2::new(1.0, 2.0), p);
+        assert_eq!((3.0, 4.0), pair);
+    }
+}
@@ -1,18 +1,13 @@
-use super::{Sphere, Tuple};
+use super::{Shape, Tuple};
 
-/// Returns the normal vector of given sphere at given point
+/// Returns the normal vector of given shape at given point
 ///
 /// # Arguments
 ///
-/// `sphere` - sphere to find normal of
+/// `shape` - shape to find normal of
 /// `point` - point tuple to calculate normal vector at
-pub fn normal_at(sphere: Sphere, point: Tuple) -> Tuple {
-    let obj_point = sphere.transform.inverse() * point;
-    let obj_normal = obj_point - Tuple::point(0.0, 0.0, 0.0);
-    let mut world_normal = sphere.transform.inverse().transpose() * obj_normal;
-    world_normal.w = 0.0;
-
-    world_normal.normalize()
+pub fn normal_at(shape: &dyn Shape, point: Tuple) -> Tuple {
+    shape.normal_at(point)
 }
 
 /// Returns the reflected vector of given vector around a normal
@@ -27,32 +22,33 @@ pub fn reflect(incoming: Tuple, normal: Tuple) -> Tuple {
 
 #[cfg(test)]
 mod tests {
-    use super::super::transformations;
+    use super::super::{transformations, Sphere};
     use super::*;
+    use core::f64::consts::FRAC_1_SQRT_2;
 
     #[test]
     fn should_calculate_the_correct_normal_vector() {
         let s = Sphere::new();
-        let n = normal_at(s, Tuple::point(1.0, 0.0, 0.0));
+        let n = normal_at(&s, Tuple::point(1.0, 0.0, 0.0));
         let reference = Tuple::vector(1.0, 0.0, 0.0);
 
         assert_eq!(reference, n);
 
         let s = Sphere::new();
-        let n = normal_at(s, Tuple::point(0.0, 1.0, 0.0));
+        let n = normal_at(&s, Tuple::point(0.0, 1.0, 0.0));
         let reference = Tuple::vector(0.0, 1.0, 0.0);
 
         assert_eq!(reference, n);
 
         let s = Sphere::new();
-        let n = normal_at(s, Tuple::point(0.0, 0.0, 1.0));
+        let n = normal_at(&s, Tuple::point(0.0, 0.0, 1.0));
         let reference = Tuple::vector(0.0, 0.0, 1.0);
 
         assert_eq!(reference, n);
 
         let s = Sphere::new();
         let n = normal_at(
-            s,
+            &s,
             Tuple::point(
                 3.0_f64.sqrt() / 3.0,
                 3.0_f64.sqrt() / 3.0,
@@ -72,14 +68,14 @@ mod tests {
     fn normal_vector_should_be_normalized() {
         let s = Sphere::new();
         let n = normal_at(
-            s,
+            &s,
             Tuple::point(
                 3.0_f64.sqrt() / 3.0,
                 3.0_f64.sqrt() / 3.0,
                 3.0_f64.sqrt() / 3.0,
             ),
         );
-        let reference = n.clone().normalize();
+        let reference = n.normalize();
 
         assert_eq!(reference, n);
     }
@@ -88,8 +84,8 @@ mod tests {
     fn should_compute_correct_normal_on_translated_sphere() {
         let mut s = Sphere::new();
         s.transform(transformations::translation(0.0, 1.0, 0.0));
-        let n = normal_at(s, Tuple::point(0.0, 1.70711, -0.70711));
-        let reference = Tuple::vector(0.0, 0.70711, -0.70711);
+        let n = normal_at(&s, Tuple::point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        let reference = Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
 
         assert_eq!(reference, n);
     }
@@ -97,10 +93,10 @@ mod tests {
     #[test]
     fn should_compute_correct_normal_on_transformed_sphere() {
         let mut s = Sphere::new();
-        let m = transformations::scaling(1.0, 0.5, 1.0).rotate_z(std::f64::consts::PI / 5.0);
+        let m = transformations::scaling(1.0, 0.5, 1.0).rotate_z(core::f64::consts::PI / 5.0);
         s.transform(m);
         let n = normal_at(
-            s,
+            &s,
             Tuple::point(0.0, 2.0_f64.sqrt() / 2.0, -(2.0_f64.sqrt() / 2.0)),
         );
         let reference = Tuple::vector(0.0, 0.97014, -0.24254);
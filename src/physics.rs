@@ -0,0 +1,11 @@
+//! Physics module
+//!
+//! Contains a simple projectile simulation with configurable integration
+//! and collisions against the objects of a world.
+
+mod collision;
+mod integration;
+mod projectile;
+
+pub use integration::IntegrationMethod;
+pub use projectile::{Environment, Projectile};
@@ -0,0 +1,117 @@
+use super::{Environment, Projectile};
+use crate::math::{Ray, Tuple};
+use crate::scene::World;
+
+impl Environment {
+    /// Advances the projectile by the given time step like `step`, bouncing
+    /// it off the first object of the world crossed by its path
+    ///
+    /// The path is cast as a ray from the start to the end of the step. On a
+    /// hit the projectile stops just above the surface for the rest of the
+    /// step, with the velocity along the normal reversed and scaled by the
+    /// restitution.
+    ///
+    /// # Arguments:
+    ///
+    /// * `projectile` - Projectile to be affected by environment properties
+    /// * `dt` - length of the step, where one is a tick
+    /// * `world` - world holding the objects to collide with
+    pub fn step_in(&self, projectile: Projectile, dt: f64, world: &World) -> Projectile {
+        let next = self.step(projectile, dt);
+        let path = next.position - projectile.position;
+        let distance = path.magnitude();
+
+        if distance == 0.0 {
+            return next;
+        }
+
+        let ray = Ray::new(projectile.position, path / distance);
+        let Some(hit) = world.ray_cast(&ray).filter(|hit| hit.t <= distance) else {
+            return next;
+        };
+
+        let normal = hit.normalv;
+        let along = normal * Tuple::dot(&next.velocity, &normal);
+
+        Projectile {
+            position: hit.over_point,
+            velocity: next.velocity - along - along * self.restitution,
+        }
+    }
+
+    /// Returns an endless iterator over the states of the projectile, one
+    /// tick apart, bouncing off the objects of the world
+    ///
+    /// # Arguments:
+    ///
+    /// * `projectile` - initial state of the projectile
+    /// * `world` - world holding the objects to collide with
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::Color;
+    /// use libray::math::Tuple;
+    /// use libray::physics::{Environment, Projectile};
+    /// use libray::scene::{checkered_floor, World};
+    ///
+    /// let mut world = World::new();
+    /// let floor = checkered_floor(Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0), 1.0);
+    /// world.objects.push(Box::new(floor));
+    ///
+    /// let mut e = Environment::new(Tuple::vector(0.0, -0.1, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+    /// e.restitution = 0.5;
+    /// let p = Projectile::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+    ///
+    /// // the ball keeps bouncing on the floor instead of falling through it
+    /// assert!(e.trajectory_in(p, &world).take(200).all(|p| p.position.y > -0.01));
+    /// ```
+    pub fn trajectory_in<'a>(
+        &'a self,
+        projectile: Projectile,
+        world: &'a World,
+    ) -> impl Iterator<Item = Projectile> + 'a {
+        core::iter::successors(Some(projectile), move |p| {
+            Some(self.step_in(*p, 1.0, world))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{scaling, translation, Sphere};
+    use crate::physics::IntegrationMethod;
+
+    fn floor() -> World {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[0]
+            .set_transformation(&translation(0.0, -10.0, 0.0) * &scaling(10.0, 10.0, 10.0));
+        world
+    }
+
+    #[test]
+    fn step_in_should_bounce_off_objects_with_restitution() {
+        let mut e = Environment::new(Tuple::vector(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+        e.restitution = 0.5;
+        // touches the top of the floor halfway through the step
+        let p = Projectile::new(Tuple::point(-0.5, 1.0, 0.0), Tuple::vector(1.0, -2.0, 0.0));
+
+        let bounced = e.step_in(p, 1.0, &floor());
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), bounced.position);
+        assert!(bounced.position.y > 0.0);
+        assert_eq!(Tuple::vector(1.0, 1.0, 0.0), bounced.velocity);
+    }
+
+    #[test]
+    fn step_in_should_ignore_objects_beyond_the_step() {
+        let mut e = Environment::new(Tuple::vector(0.0, -0.5, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+        e.integration = IntegrationMethod::Rk4;
+        let p = Projectile::new(Tuple::point(0.0, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+
+        assert_eq!(e.step(p, 1.0), e.step_in(p, 1.0, &floor()));
+        assert_eq!(e.step(p, 0.0), e.step_in(p, 0.0, &floor()));
+    }
+}
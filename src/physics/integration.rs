@@ -0,0 +1,112 @@
+use super::Projectile;
+use crate::math::Tuple;
+
+/// Numerical method advancing a projectile through time
+///
+/// With constant forces every method but `Euler` follows the exact
+/// trajectory, they differ once forces depend on the velocity, such as drag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegrationMethod {
+    /// Explicit Euler, moving with the velocity at the start of the step
+    /// before applying the acceleration
+    #[default]
+    Euler,
+    /// Velocity Verlet, moving with the average of the accelerations at
+    /// both ends of the step
+    VelocityVerlet,
+    /// Classic fourth order Runge-Kutta, averaging four evaluations of the
+    /// acceleration across the step
+    Rk4,
+}
+
+impl IntegrationMethod {
+    /// Returns the state of the projectile after the given time step
+    ///
+    /// # Arguments
+    ///
+    /// * `projectile` - state at the start of the step
+    /// * `dt` - length of the step
+    /// * `acceleration` - acceleration of the projectile at a given velocity
+    pub fn integrate<F>(&self, projectile: Projectile, dt: f64, acceleration: F) -> Projectile
+    where
+        F: Fn(Tuple) -> Tuple,
+    {
+        let Projectile { position, velocity } = projectile;
+
+        match self {
+            IntegrationMethod::Euler => Projectile {
+                position: position + velocity * dt,
+                velocity: velocity + acceleration(velocity) * dt,
+            },
+            IntegrationMethod::VelocityVerlet => {
+                let start = acceleration(velocity);
+                let end = acceleration(velocity + start * dt);
+
+                Projectile {
+                    position: position + velocity * dt + start * (0.5 * dt * dt),
+                    velocity: velocity + (start + end) * (0.5 * dt),
+                }
+            }
+            IntegrationMethod::Rk4 => {
+                let v1 = velocity;
+                let a1 = acceleration(v1);
+                let v2 = velocity + a1 * (0.5 * dt);
+                let a2 = acceleration(v2);
+                let v3 = velocity + a2 * (0.5 * dt);
+                let a3 = acceleration(v3);
+                let v4 = velocity + a3 * dt;
+                let a4 = acceleration(v4);
+
+                Projectile {
+                    position: position + (v1 + v2 * 2.0 + v3 * 2.0 + v4) * (dt / 6.0),
+                    velocity: velocity + (a1 + a2 * 2.0 + a3 * 2.0 + a4) * (dt / 6.0),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn falling() -> Projectile {
+        Projectile::new(Tuple::point(0.0, 10.0, 0.0), Tuple::vector(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn higher_order_methods_should_be_exact_under_constant_forces() {
+        let gravity = |_| Tuple::vector(0.0, -2.0, 0.0);
+        let exact = Projectile::new(Tuple::point(1.0, 9.0, 0.0), Tuple::vector(1.0, -2.0, 0.0));
+
+        assert_eq!(
+            exact,
+            IntegrationMethod::VelocityVerlet.integrate(falling(), 1.0, gravity)
+        );
+        assert_eq!(
+            exact,
+            IntegrationMethod::Rk4.integrate(falling(), 1.0, gravity)
+        );
+        assert_eq!(
+            Tuple::point(1.0, 10.0, 0.0),
+            IntegrationMethod::Euler
+                .integrate(falling(), 1.0, gravity)
+                .position
+        );
+    }
+
+    #[test]
+    fn rk4_should_follow_drag_most_closely() {
+        // dv/dt = -v decays exactly as v0 * e^-t
+        let drag = |v: Tuple| -v;
+        let exact = (-1.0f64).exp();
+        let error = |method: IntegrationMethod| {
+            let p = (0..10).fold(falling(), |p, _| method.integrate(p, 0.1, drag));
+            (p.velocity.x - exact).abs()
+        };
+
+        assert!(error(IntegrationMethod::Rk4) < 1e-6);
+        assert!(error(IntegrationMethod::Rk4) < error(IntegrationMethod::VelocityVerlet));
+        assert!(error(IntegrationMethod::VelocityVerlet) < error(IntegrationMethod::Euler));
+    }
+}
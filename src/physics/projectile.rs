@@ -0,0 +1,141 @@
+use super::IntegrationMethod;
+use crate::math::Tuple;
+
+/// Projectile struct
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projectile {
+    /// Tuple representing projectile position
+    pub position: Tuple,
+    /// Tuple representing projectile velocity
+    pub velocity: Tuple,
+}
+
+/// Environment struct
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Environment {
+    /// Tuple representing environment gravity
+    pub gravity: Tuple,
+    /// Tuple representing environment gravity wind
+    pub wind: Tuple,
+    /// Linear drag coefficient, slowing the projectile in proportion to its
+    /// velocity
+    pub drag: f64,
+    /// Method advancing projectiles through time
+    pub integration: IntegrationMethod,
+    /// Fraction of the velocity along the surface normal kept when bouncing
+    /// off an object, from `0.0` for no bounce to `1.0` for a perfectly
+    /// elastic one
+    pub restitution: f64,
+}
+
+impl Projectile {
+    /// Returns a new projectile
+    ///
+    /// # Arguments:
+    ///
+    /// * `position` - point where the projectile starts
+    /// * `velocity` - initial velocity vector of the projectile
+    pub fn new(position: Tuple, velocity: Tuple) -> Projectile {
+        Projectile { position, velocity }
+    }
+}
+
+impl Environment {
+    /// Returns a new environment without drag, integrated with the Euler
+    /// method and bouncing projectiles perfectly elastically
+    ///
+    /// # Arguments:
+    ///
+    /// * `gravity` - gravity vector applied every tick
+    /// * `wind` - wind vector applied every tick
+    pub fn new(gravity: Tuple, wind: Tuple) -> Environment {
+        Environment {
+            gravity,
+            wind,
+            drag: 0.0,
+            integration: IntegrationMethod::Euler,
+            restitution: 1.0,
+        }
+    }
+
+    /// Returns the acceleration of a projectile moving at the given velocity
+    ///
+    /// # Arguments:
+    ///
+    /// * `velocity` - velocity of the projectile
+    pub fn acceleration(&self, velocity: Tuple) -> Tuple {
+        self.gravity + self.wind - velocity * self.drag
+    }
+
+    /// Advances the projectile by the given time step
+    ///
+    /// # Arguments:
+    ///
+    /// * `projectile` - Projectile to be affected by environment properties
+    /// * `dt` - length of the step, where one is a tick
+    pub fn step(&self, projectile: Projectile, dt: f64) -> Projectile {
+        self.integration
+            .integrate(projectile, dt, |velocity| self.acceleration(velocity))
+    }
+
+    /// Tick environment one step
+    ///
+    /// # Arguments:
+    ///
+    /// * `projectile` - Projectile to be affected by environment properties
+    pub fn tick(&self, projectile: Projectile) -> Projectile {
+        self.step(projectile, 1.0)
+    }
+
+    /// Returns an endless iterator over the states of the projectile, one
+    /// tick apart, starting with the given projectile
+    ///
+    /// # Arguments:
+    ///
+    /// * `projectile` - initial state of the projectile
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::Tuple;
+    /// use libray::physics::{Environment, Projectile};
+    ///
+    /// let p = Projectile::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(1.0, 1.0, 0.0));
+    /// let e = Environment::new(Tuple::vector(0.0, -0.1, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+    /// let ticks = e.trajectory(p).take_while(|p| p.position.y > 0.0).count();
+    ///
+    /// assert_eq!(22, ticks);
+    /// ```
+    pub fn trajectory(&self, projectile: Projectile) -> impl Iterator<Item = Projectile> + '_ {
+        core::iter::successors(Some(projectile), move |p| Some(self.tick(*p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_should_move_projectile_and_apply_forces() {
+        let p = Projectile::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(1.0, 1.0, 0.0));
+        let e = Environment::new(
+            Tuple::vector(0.0, -0.1, 0.0),
+            Tuple::vector(-0.01, 0.0, 0.0),
+        );
+        let p = e.tick(p);
+
+        assert_eq!(Tuple::point(1.0, 2.0, 0.0), p.position);
+        assert_eq!(Tuple::vector(0.99, 0.9, 0.0), p.velocity);
+    }
+
+    #[test]
+    fn trajectory_should_start_with_initial_state() {
+        let p = Projectile::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(1.0, 1.0, 0.0));
+        let e = Environment::new(Tuple::vector(0.0, -0.1, 0.0), Tuple::vector(0.0, 0.0, 0.0));
+        let states: Vec<Projectile> = e.trajectory(p).take(3).collect();
+
+        assert_eq!(p, states[0]);
+        assert_eq!(e.tick(p), states[1]);
+        assert_eq!(e.tick(e.tick(p)), states[2]);
+    }
+}
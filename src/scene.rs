@@ -0,0 +1,49 @@
+//! Scene module
+//!
+//! Contains the world, its spatial queries, diffs and an interactive editor,
+//! the camera, its lens and paths, animations, render settings and progress
+//! reporting, material previews, scene scales, memory budgets, the
+//! precomputed state of intersections and ray traces for debugging.
+
+mod animation;
+mod camera;
+mod camera_path;
+mod computations;
+mod diff;
+mod editor;
+mod exposure;
+mod floor;
+mod lens;
+mod memory;
+mod orthographic;
+mod preview;
+mod progress;
+mod query;
+mod scale;
+mod settings;
+mod stereo;
+mod trace;
+mod world;
+
+pub use animation::Animation;
+pub use camera::Camera;
+pub use camera_path::CameraPath;
+pub use computations::Computations;
+pub use diff::WorldDiff;
+pub use editor::SceneEditor;
+pub use exposure::Exposure;
+pub use floor::checkered_floor;
+pub use lens::Lens;
+pub use memory::{BudgetExceeded, MemoryBudget, MemoryUsage};
+pub use orthographic::OrthographicCamera;
+pub use preview::{render_material_preview, shader_ball, shader_ball_camera};
+#[cfg(feature = "std")]
+pub use progress::ProgressBar;
+pub(crate) use progress::Reporter;
+pub use progress::{ProgressSink, RenderProgress};
+pub use scale::SceneScale;
+pub(crate) use settings::SHADOW_BIAS;
+pub use settings::{Integrator, RenderSettings};
+pub use stereo::StereoCamera;
+pub use trace::{RayTrace, TraceEvent};
+pub use world::{ShapeId, World};
@@ -0,0 +1,518 @@
+use super::{Camera, RenderSettings, ShapeId, World};
+use crate::graphics::{Canvas, Color};
+use crate::math::{view_transform, Point2, TransformProvider, Tuple};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+use core::ops::Range;
+
+/// Set of transform providers driving objects of a world and a camera,
+/// evaluated at a given time before rendering each frame
+///
+/// The camera can additionally keep looking at an object and keep an object
+/// in focus, following it wherever its provider moves it.
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{translation, Orbit, Sphere, Tuple};
+/// use libray::scene::{Animation, Camera, ShapeId, World};
+///
+/// let mut world = World::new();
+/// world.objects.push(Box::new(Sphere::new()));
+/// let mut camera = Camera::new(10, 10, 1.0);
+///
+/// let mut animation = Animation::new();
+/// animation.animate(ShapeId(0), Orbit::new(Tuple::point(0.0, 0.0, 0.0), 3.0, 2.0));
+/// animation.animate_camera(|t: f64| translation(0.0, 0.0, -5.0 - t));
+/// animation.apply(&mut world, &mut camera, 0.0);
+///
+/// assert_eq!(translation(3.0, 0.0, 0.0), *world.objects[0].transformation());
+/// assert_eq!(translation(0.0, 0.0, -5.0), camera.transform);
+/// ```
+pub struct Animation {
+    /// Providers of the transformation of objects in the world
+    pub objects: Vec<(ShapeId, Box<dyn TransformProvider + Send + Sync>)>,
+    /// Provider of the view transformation of the camera
+    pub camera: Option<Box<dyn TransformProvider + Send + Sync>>,
+    /// Object the camera turns to look at, keeping the y axis up
+    pub look_at: Option<ShapeId>,
+    /// Object the lens of the camera focuses on
+    pub focus: Option<ShapeId>,
+    /// Number of frames per second
+    pub frame_rate: f64,
+    /// Fraction of each frame the shutter stays open, in degrees, where 360
+    /// keeps it open for the whole frame
+    pub shutter_angle: f64,
+    /// Number of times sampled while the shutter is open, blurring moving
+    /// objects when greater than one
+    pub motion_samples: usize,
+}
+
+impl Animation {
+    /// Returns a new animation without any providers or tracked objects,
+    /// running at 24 frames per second with a 180 degree shutter sampled once
+    /// per frame
+    pub fn new() -> Animation {
+        Animation {
+            objects: vec![],
+            camera: None,
+            look_at: None,
+            focus: None,
+            frame_rate: 24.0,
+            shutter_angle: 180.0,
+            motion_samples: 1,
+        }
+    }
+
+    /// Returns the time at which the given frame starts, in seconds
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - number of the frame
+    pub fn frame_time(&self, frame: usize) -> f64 {
+        frame as f64 / self.frame_rate
+    }
+
+    /// Returns the times at which the shutter opens and closes during the
+    /// given frame, in seconds
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - number of the frame
+    pub fn shutter_interval(&self, frame: usize) -> (f64, f64) {
+        let open = self.frame_time(frame);
+        let exposure = self.shutter_angle.clamp(0.0, 360.0) / 360.0 / self.frame_rate;
+
+        (open, open + exposure)
+    }
+
+    /// Renders the given frame, averaging renders at evenly spread times
+    /// while the shutter is open to blur objects moving during the exposure
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world holding the animated objects
+    /// * `camera` - camera to render from
+    /// * `frame` - number of the frame
+    /// * `settings` - settings to render each sample with
+    pub fn render_frame(
+        &self,
+        world: &World,
+        camera: &Camera,
+        frame: usize,
+        settings: &RenderSettings,
+    ) -> Canvas {
+        let (open, close) = self.shutter_interval(frame);
+        let n = self.motion_samples.max(1);
+        let (mut world, mut camera) = (world.clone(), camera.clone());
+        let mut image = Canvas::new(camera.hsize(), camera.vsize());
+
+        for i in 0..n {
+            let time = open + (close - open) * (i as f64 + 0.5) / n as f64;
+            self.apply(&mut world, &mut camera, time);
+            let sample = camera.render(&world, settings);
+
+            for y in 0..image.height {
+                for x in 0..image.width {
+                    let color = image.pixel_at(x, y) + sample.pixel_at(x, y) / n as f64;
+                    image.write_pixel(x, y, color);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Returns an iterator rendering the given frames one after the other,
+    /// calling `update` before each frame to change the scene freely, e.g.
+    /// to step a physics simulation or grow procedural geometry
+    ///
+    /// Changes made by `update` carry over to later frames, while the
+    /// providers of the animation are applied on top of them for every frame
+    /// rendered. Frames are only rendered as the iterator advances, so long
+    /// animations can be written out without holding every frame in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world at the start of the animation
+    /// * `camera` - camera at the start of the animation
+    /// * `frames` - numbers of the frames to render
+    /// * `settings` - settings to render each frame with
+    /// * `update` - callback receiving the frame number, world and camera
+    ///   before the frame is rendered
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::{Color, Light};
+    /// use libray::math::{translation, view_transform, Sphere, Tuple};
+    /// use libray::scene::{Animation, Camera, RenderSettings, World};
+    ///
+    /// let mut world = World::new();
+    /// world.objects.push(Box::new(Sphere::new()));
+    /// world.lights.push(Light::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0)));
+    /// let mut camera = Camera::new(11, 11, 1.0);
+    /// camera.transform(view_transform(
+    ///     Tuple::point(0.0, 0.0, -5.0),
+    ///     Tuple::point(0.0, 0.0, 0.0),
+    ///     Tuple::vector(0.0, 1.0, 0.0),
+    /// ));
+    ///
+    /// let settings = RenderSettings::default();
+    /// let frames: Vec<_> = Animation::new()
+    ///     .render_animation_with(world, camera, 0..3, &settings, |frame, world, _camera| {
+    ///         // the sphere drops out of view one unit per frame
+    ///         world.objects[0].set_transformation(translation(0.0, -3.0 * frame as f64, 0.0));
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(3, frames.len());
+    /// assert_ne!(Color::new(0.0, 0.0, 0.0), frames[0].1.pixel_at(5, 5));
+    /// assert_eq!(Color::new(0.0, 0.0, 0.0), frames[2].1.pixel_at(5, 5));
+    /// ```
+    pub fn render_animation_with<'a, F>(
+        &'a self,
+        mut world: World,
+        mut camera: Camera,
+        frames: Range<usize>,
+        settings: &'a RenderSettings,
+        mut update: F,
+    ) -> impl Iterator<Item = (usize, Canvas)> + 'a
+    where
+        F: FnMut(usize, &mut World, &mut Camera) + 'a,
+    {
+        frames.map(move |frame| {
+            update(frame, &mut world, &mut camera);
+
+            (frame, self.render_frame(&world, &camera, frame, settings))
+        })
+    }
+
+    /// Drives the transformation of an object with the given provider,
+    /// replacing any previous provider of that object
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to animate
+    /// * `provider` - source of the transformation over time
+    pub fn animate<P>(&mut self, id: ShapeId, provider: P)
+    where
+        P: TransformProvider + Send + Sync + 'static,
+    {
+        self.objects.retain(|(other, _)| *other != id);
+        self.objects.push((id, Box::new(provider)));
+    }
+
+    /// Drives the view transformation of the camera with the given provider
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - source of the view transformation over time
+    pub fn animate_camera<P>(&mut self, provider: P)
+    where
+        P: TransformProvider + Send + Sync + 'static,
+    {
+        self.camera = Some(Box::new(provider));
+    }
+
+    /// Keeps the camera turned towards the center of the given object, on
+    /// top of the view transformation of its provider
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to look at
+    pub fn track(&mut self, id: ShapeId) {
+        self.look_at = Some(id);
+    }
+
+    /// Keeps the center of the given object in focus by pulling the focal
+    /// distance of the camera lens, leaving cameras without a lens untouched
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to focus on
+    pub fn focus_on(&mut self, id: ShapeId) {
+        self.focus = Some(id);
+    }
+
+    /// Sets the transformations of the animated objects and camera to their
+    /// values at the given time, then turns and focuses the camera on the
+    /// tracked objects, skipping objects missing from the world
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world holding the animated objects
+    /// * `camera` - camera to move
+    /// * `time` - time to evaluate the providers at, in seconds
+    pub fn apply(&self, world: &mut World, camera: &mut Camera, time: f64) {
+        for (id, provider) in &self.objects {
+            if let Some(object) = world.object_mut(*id) {
+                object.set_transformation(provider.transform_at(time));
+            }
+        }
+
+        if let Some(provider) = &self.camera {
+            camera.transform(provider.transform_at(time));
+        }
+
+        let center = |id: Option<ShapeId>| {
+            let object = world.object(id?)?;
+
+            Some(object.transformation() * &Tuple::point(0.0, 0.0, 0.0))
+        };
+
+        if let Some(target) = center(self.look_at) {
+            let eye = &camera.transform.inverse() * &Tuple::point(0.0, 0.0, 0.0);
+
+            if target != eye {
+                camera.transform(view_transform(eye, target, Tuple::vector(0.0, 1.0, 0.0)));
+            }
+        }
+
+        if let (Some(target), Some(lens)) = (center(self.focus), &mut camera.lens) {
+            // the plane in focus is measured along the view direction
+            let depth = -(&camera.transform * &target).z;
+
+            if depth > 0.0 {
+                lens.focal_distance = depth;
+            }
+        }
+    }
+
+    /// Renders the screen-space motion of the surface visible through each
+    /// pixel between the given time and a later one, measured in pixels and
+    /// stored in the red and green channels, zero where nothing is visible
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world holding the animated objects
+    /// * `camera` - camera to render from
+    /// * `time` - time of the frame, in seconds
+    /// * `delta` - time elapsed until the motion is measured, e.g. one frame
+    /// * `settings` - settings to render with
+    pub fn motion_vectors(
+        &self,
+        world: &World,
+        camera: &Camera,
+        time: f64,
+        delta: f64,
+        settings: &RenderSettings,
+    ) -> Canvas {
+        let (mut now, mut now_camera) = (world.clone(), camera.clone());
+        let (mut later, mut later_camera) = (world.clone(), camera.clone());
+        self.apply(&mut now, &mut now_camera, time);
+        self.apply(&mut later, &mut later_camera, time + delta);
+
+        let mut image = Canvas::new(camera.hsize(), camera.vsize());
+
+        for y in 0..camera.vsize() {
+            for x in 0..camera.hsize() {
+                let ray = now_camera.ray_for_pixel(x, y);
+                let Some(comps) = now.cast(&ray, settings.shadow_bias) else {
+                    continue;
+                };
+                let Some(id) = now.id_of(comps.object) else {
+                    continue;
+                };
+                let local = &comps.object.transformation().inverse() * &comps.point;
+                let moved = later.objects[id.0].transformation() * &local;
+
+                if let Some(to) = later_camera.project(moved) {
+                    let motion = to - Point2::new(x as f64 + 0.5, y as f64 + 0.5);
+
+                    image.write_pixel(x, y, Color::new(motion.x, motion.y, 0.0));
+                }
+            }
+        }
+
+        image
+    }
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Animation")
+            .field(
+                "objects",
+                &self.objects.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            )
+            .field("camera", &self.camera.is_some())
+            .field("look_at", &self.look_at)
+            .field("focus", &self.focus)
+            .field("frame_rate", &self.frame_rate)
+            .field("shutter_angle", &self.shutter_angle)
+            .field("motion_samples", &self.motion_samples)
+            .finish()
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Animation {
+        Animation::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{scaling, translation, view_transform, Matrix, Sphere, Tuple, EPSILON};
+    use crate::scene::Lens;
+    use core::f64::consts::PI;
+
+    #[test]
+    fn should_replace_previous_provider_of_object() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        let mut camera = Camera::new(10, 10, 1.0);
+        let mut animation = Animation::new();
+
+        animation.animate(ShapeId(0), scaling(2.0, 2.0, 2.0));
+        animation.animate(ShapeId(0), |t: f64| translation(t, 0.0, 0.0));
+        animation.animate(ShapeId(5), scaling(3.0, 3.0, 3.0));
+        animation.apply(&mut world, &mut camera, 2.0);
+
+        assert_eq!(2, animation.objects.len());
+        assert_eq!(
+            translation(2.0, 0.0, 0.0),
+            *world.objects[0].transformation()
+        );
+    }
+
+    #[test]
+    fn motion_vectors_should_follow_moving_objects() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        let mut camera = Camera::new(21, 21, PI / 2.0);
+        camera.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let mut animation = Animation::new();
+        animation.animate(ShapeId(0), |t: f64| translation(t, 0.0, 0.0));
+
+        let motion =
+            animation.motion_vectors(&world, &camera, 0.0, 0.5, &RenderSettings::default());
+        let center = motion.pixel_at(10, 10);
+
+        assert!(center.r > 0.0);
+        assert!(center.g.abs() < EPSILON);
+        assert_eq!(Color::default(), motion.pixel_at(0, 0));
+
+        animation.animate_camera(|t: f64| {
+            view_transform(
+                Tuple::point(t, 0.0, -5.0),
+                Tuple::point(t, 0.0, 0.0),
+                Tuple::vector(0.0, 1.0, 0.0),
+            )
+        });
+        let motion =
+            animation.motion_vectors(&world, &camera, 0.0, 0.5, &RenderSettings::default());
+
+        assert!(motion.pixel_at(10, 10).r.abs() < EPSILON);
+    }
+
+    #[test]
+    fn camera_should_look_at_and_focus_on_tracked_object() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        let mut camera = Camera::new(21, 21, PI / 2.0);
+        camera.lens = Some(Lens::new(0.1, 1.0));
+        let mut animation = Animation::new();
+        animation.animate(ShapeId(0), |t: f64| translation(t, 0.0, 0.0));
+        animation.animate_camera(translation(0.0, 0.0, 5.0));
+        animation.track(ShapeId(0));
+        animation.focus_on(ShapeId(0));
+
+        for time in [0.0, 3.0] {
+            animation.apply(&mut world, &mut camera, time);
+            let target = Tuple::point(time, 0.0, 0.0);
+            let center = camera.project(target).unwrap();
+
+            assert!((center.x - 10.5).abs() < EPSILON);
+            assert!((center.y - 10.5).abs() < EPSILON);
+            assert!(
+                (camera.lens.unwrap().focal_distance - (time * time + 25.0).sqrt()).abs() < EPSILON
+            );
+        }
+
+        animation.focus_on(ShapeId(7));
+        animation.apply(&mut world, &mut camera, 4.0);
+
+        assert!((camera.lens.unwrap().focal_distance - 34f64.sqrt()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn scene_changes_should_carry_over_between_frames() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[0].material_mut().ambient = 0.0;
+        let mut animation = Animation::new();
+        animation.animate(ShapeId(0), |t: f64| translation(t, 0.0, 0.0));
+        let mut seen = vec![];
+
+        let frames: Vec<_> = animation
+            .render_animation_with(
+                world,
+                Camera::new(4, 3, 1.0),
+                2..5,
+                &RenderSettings::default(),
+                |frame, world, _camera| {
+                    world.objects[0].material_mut().ambient += 1.0;
+                    seen.push((
+                        frame,
+                        world.objects[0].material().ambient,
+                        world.objects[0].transformation().clone(),
+                    ));
+                },
+            )
+            .map(|(frame, canvas)| (frame, canvas.width, canvas.height))
+            .collect();
+
+        assert_eq!(vec![(2, 4, 3), (3, 4, 3), (4, 4, 3)], frames);
+        // providers only move the objects of the rendered copy
+        assert_eq!(
+            vec![
+                (2, 1.0, Matrix::identity(4)),
+                (3, 2.0, Matrix::identity(4)),
+                (4, 3.0, Matrix::identity(4))
+            ],
+            seen
+        );
+    }
+
+    #[test]
+    fn should_derive_shutter_interval_from_frame_timing() {
+        let mut animation = Animation::new();
+        animation.frame_rate = 25.0;
+        animation.shutter_angle = 90.0;
+
+        assert_eq!(2.0, animation.frame_time(50));
+        assert_eq!((2.0, 2.01), animation.shutter_interval(50));
+    }
+
+    #[test]
+    fn should_blur_objects_moving_while_shutter_is_open() {
+        let mut world = World::default_world();
+        let mut camera = Camera::new(21, 21, PI / 2.0);
+        camera.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        world.objects.truncate(1);
+        let mut animation = Animation::new();
+        animation.frame_rate = 1.0;
+        animation.shutter_angle = 360.0;
+        animation.animate(ShapeId(0), |t: f64| translation(8.0 * t - 4.0, 0.0, 0.0));
+        let settings = RenderSettings::default();
+
+        let sharp = animation.render_frame(&world, &camera, 0, &settings);
+        animation.motion_samples = 8;
+        let blurred = animation.render_frame(&world, &camera, 0, &settings);
+
+        assert_ne!(Color::default(), sharp.pixel_at(10, 10));
+        assert!(blurred.pixel_at(10, 10).r < sharp.pixel_at(10, 10).r);
+        assert!(blurred.pixel_at(2, 10).r > sharp.pixel_at(2, 10).r);
+    }
+}
@@ -0,0 +1,1096 @@
+use super::{
+    Computations, Exposure, Lens, ProgressSink, RayTrace, RenderSettings, Reporter, ShapeId, World,
+};
+use crate::graphics::{line, Canvas, Color, Framebuffer};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::{Angle, Interval, Matrix, Point2, Ray, Tuple, Vec2};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Virtual camera mapping a canvas onto the world
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "CameraConfig", into = "CameraConfig")
+)]
+pub struct Camera {
+    /// View transformation matrix of the camera
+    pub transform: Matrix,
+    /// Horizontal size of the canvas in pixels
+    hsize: usize,
+    /// Vertical size of the canvas in pixels
+    vsize: usize,
+    /// Angle in radians describing how much the camera can see
+    field_of_view: f64,
+    /// Size of a single pixel in world space units
+    pixel_size: f64,
+    /// Half of the canvas width in world space units
+    half_width: f64,
+    /// Half of the canvas height in world space units
+    half_height: f64,
+    /// Distance from the camera below which objects are not rendered
+    pub near: Option<f64>,
+    /// Distance from the camera beyond which objects are not rendered
+    pub far: Option<f64>,
+    /// Exposure applied to rendered colors, for scenes lit by photometric
+    /// lights, `None` to leave colors untouched
+    pub exposure: Option<Exposure>,
+    /// Lens blurring objects away from its focal distance, `None` for a
+    /// pinhole camera keeping everything in focus
+    pub lens: Option<Lens>,
+}
+
+/// Serialized form of a camera, from which the derived sizes are recomputed
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CameraConfig {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: f64,
+    transform: Matrix,
+    #[serde(default)]
+    near: Option<f64>,
+    #[serde(default)]
+    far: Option<f64>,
+    #[serde(default)]
+    exposure: Option<Exposure>,
+    #[serde(default)]
+    lens: Option<Lens>,
+}
+
+#[cfg(feature = "serde")]
+impl From<CameraConfig> for Camera {
+    fn from(config: CameraConfig) -> Camera {
+        let mut camera = Camera::new(config.hsize, config.vsize, config.field_of_view);
+        camera.transform(config.transform);
+        camera.near = config.near;
+        camera.far = config.far;
+        camera.exposure = config.exposure;
+        camera.lens = config.lens;
+        camera
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Camera> for CameraConfig {
+    fn from(camera: Camera) -> CameraConfig {
+        CameraConfig {
+            hsize: camera.hsize,
+            vsize: camera.vsize,
+            field_of_view: camera.field_of_view,
+            transform: camera.transform,
+            near: camera.near,
+            far: camera.far,
+            exposure: camera.exposure,
+            lens: camera.lens,
+        }
+    }
+}
+
+impl Camera {
+    /// Returns a new pinhole camera with an identity view transformation, no
+    /// clipping distances and no exposure
+    ///
+    /// # Arguments
+    ///
+    /// * `hsize` - horizontal size of the canvas in pixels
+    /// * `vsize` - vertical size of the canvas in pixels
+    /// * `field_of_view` - angle describing how much the camera can see, a
+    ///   bare `f64` being radians
+    pub fn new(hsize: usize, vsize: usize, field_of_view: impl Into<Angle>) -> Camera {
+        let field_of_view = field_of_view.into().radians();
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as f64 / vsize as f64;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+
+        Camera {
+            transform: Matrix::identity(4),
+            hsize,
+            vsize,
+            field_of_view,
+            pixel_size: (half_width * 2.0) / hsize as f64,
+            half_width,
+            half_height,
+            near: None,
+            far: None,
+            exposure: None,
+            lens: None,
+        }
+    }
+
+    /// Sets the view transformation matrix of the camera
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - view transformation matrix to set for the camera
+    pub fn transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Returns the horizontal size of the canvas in pixels
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    /// Returns the vertical size of the canvas in pixels
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    /// Returns the field of view of the camera in radians
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+
+    /// Returns the size of a single pixel in world space units
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    /// Returns a ray starting at the camera and passing through the center of
+    /// the given pixel
+    ///
+    /// # Arguments
+    ///
+    /// * `px` - horizontal pixel coordinate
+    /// * `py` - vertical pixel coordinate
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        self.ray_through(&self.transform.inverse(), px as f64 + 0.5, py as f64 + 0.5)
+    }
+
+    /// Returns the position on the canvas, measured in pixels from its top
+    /// left corner, onto which the given point is projected, or `None` if
+    /// the point lies behind the camera
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in world space
+    pub fn project(&self, point: Tuple) -> Option<Point2> {
+        let p = &self.transform * &point;
+
+        if p.z >= 0.0 {
+            return None;
+        }
+
+        let (world_x, world_y) = (p.x / -p.z, p.y / -p.z);
+
+        Some(Point2::new(
+            (self.half_width - world_x) / self.pixel_size,
+            (self.half_height - world_y) / self.pixel_size,
+        ))
+    }
+
+    /// Draws the edges of the box bounding every object in the world onto
+    /// the canvas as a wireframe, skipping unbounded objects and edges
+    /// reaching behind the camera
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world holding the objects
+    /// * `canvas` - rendered image to draw onto
+    /// * `color` - color of the wireframe
+    pub fn overlay_bounds(&self, world: &World, canvas: &mut Canvas, color: Color) {
+        for object in &world.objects {
+            let corners = object.bounding_corners();
+
+            if corners.iter().any(|c| !c.is_finite()) {
+                continue;
+            }
+
+            let corners = corners.map(|c| self.project(c).map(|p| p - Vec2::new(0.5, 0.5)));
+
+            for i in 0..8 {
+                for bit in [1, 2, 4] {
+                    if i & bit != 0 {
+                        continue;
+                    }
+                    if let (Some(start), Some(end)) = (corners[i], corners[i | bit]) {
+                        line(canvas, start, end, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the object visible through the given pixel together with the
+    /// precomputed state of the hit, or `None` if the pixel shows nothing
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to pick from
+    /// * `px` - horizontal pixel coordinate
+    /// * `py` - vertical pixel coordinate
+    pub fn pick<'a>(
+        &self,
+        world: &'a World,
+        px: usize,
+        py: usize,
+    ) -> Option<(ShapeId, Computations<'a>)> {
+        let comps = world.ray_cast(&self.ray_for_pixel(px, py))?;
+        let id = world.id_of(comps.object)?;
+
+        Some((id, comps))
+    }
+
+    /// Traces the ray through the center of the given pixel, recording every
+    /// step taken to compute its color
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to trace through
+    /// * `px` - horizontal pixel coordinate
+    /// * `py` - vertical pixel coordinate
+    /// * `settings` - settings to render with
+    pub fn trace_pixel(
+        &self,
+        world: &World,
+        px: usize,
+        py: usize,
+        settings: &RenderSettings,
+    ) -> RayTrace {
+        world.trace(&self.ray_for_pixel(px, py), settings)
+    }
+
+    /// Renders the given world onto a new canvas, applying the exposure of
+    /// the camera if it has one
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling, threading and shading
+    pub fn render(&self, world: &World, settings: &RenderSettings) -> Canvas {
+        self.render_by(settings, self.shader(world, settings), None)
+    }
+
+    /// Renders the given world like `render` into a framebuffer provided by
+    /// the caller instead of a newly allocated canvas
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling, threading and shading
+    /// * `target` - framebuffer of the size of the camera to render into
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the framebuffer differs from the camera.
+    pub fn render_into<B>(&self, world: &World, settings: &RenderSettings, target: &mut B)
+    where
+        B: Framebuffer + ?Sized,
+    {
+        assert_eq!(
+            (self.hsize, self.vsize),
+            (target.width(), target.height()),
+            "framebuffer size differs from the camera"
+        );
+
+        self.render_by_into(settings, self.shader(world, settings), None, target);
+    }
+
+    /// Renders the given world like `render` on the threads of an existing
+    /// rayon pool instead of threads of its own, ignoring the thread count
+    /// of the settings
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - thread pool to render on
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling and shading
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::scene::{Camera, RenderSettings, World};
+    ///
+    /// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    /// let camera = Camera::new(8, 6, 1.0);
+    /// let image = camera.render_in(&pool, &World::new(), &RenderSettings::default());
+    ///
+    /// assert_eq!(6, image.height);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn render_in(
+        &self,
+        pool: &rayon::ThreadPool,
+        world: &World,
+        settings: &RenderSettings,
+    ) -> Canvas {
+        use rayon::prelude::*;
+
+        let inverse = self.transform.inverse();
+        let sample = self.shader(world, settings);
+        let rows: Vec<(usize, Vec<Color>)> = pool.install(|| {
+            (0..self.vsize)
+                .into_par_iter()
+                .map(|y| (y, self.render_row(settings, &inverse, y, &sample, None)))
+                .collect()
+        });
+
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        compose(rows, &mut image);
+
+        image
+    }
+
+    /// Renders the given world like `render`, sending the progress to the
+    /// given sink after every finished row
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling, threading and shading
+    /// * `sink` - receiver of the progress updates
+    pub fn render_with_progress(
+        &self,
+        world: &World,
+        settings: &RenderSettings,
+        sink: &dyn ProgressSink,
+    ) -> Canvas {
+        let reporter = Reporter::new(sink, self.vsize, settings.samples_per_pixel());
+
+        self.render_by(settings, self.shader(world, settings), Some(&reporter))
+    }
+
+    /// Renders the given region of the canvas like `render`, overwriting
+    /// only the pixels inside of it
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling and shading
+    /// * `canvas` - canvas of the size of the camera to render onto
+    /// * `columns` - horizontal pixel coordinates to render
+    /// * `rows` - vertical pixel coordinates to render
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(columns = ?columns, rows = ?rows))
+    )]
+    pub(crate) fn render_region(
+        &self,
+        world: &World,
+        settings: &RenderSettings,
+        canvas: &mut Canvas,
+        columns: Range<usize>,
+        rows: Range<usize>,
+    ) {
+        let inverse = self.transform.inverse();
+        let sample = self.shader(world, settings);
+
+        for y in rows {
+            for x in columns.clone() {
+                let color = self.render_pixel(settings, &inverse, x, y, &sample);
+                canvas.write_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Returns the function computing the color seen along a camera ray,
+    /// clipped and exposed by the camera
+    fn shader<'a>(
+        &'a self,
+        world: &'a World,
+        settings: &'a RenderSettings,
+    ) -> impl Fn(&Ray) -> Color + Sync + 'a {
+        let range = self.clip_range();
+        let exposure = self.exposure.map_or(1.0, |e| e.factor());
+
+        move |ray| world.color_within(ray, settings, range) * exposure
+    }
+
+    /// Renders the given world onto a new canvas with a custom integrator,
+    /// reusing the ray generation, antialiasing and threading of `render`
+    ///
+    /// The integrator is called for every camera ray and its colors are
+    /// averaged per pixel as returned, without clipping distances or
+    /// exposure applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling and threading
+    /// * `integrator` - function returning the color seen along a ray
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::graphics::Color;
+    /// use libray::math::{translation, Sphere};
+    /// use libray::scene::{Camera, RenderSettings, World};
+    ///
+    /// let mut world = World::new();
+    /// let mut sphere = Sphere::new();
+    /// sphere.transform(translation(0.0, 0.0, -5.0));
+    /// world.objects.push(Box::new(sphere));
+    /// let camera = Camera::new(4, 4, 1.0);
+    ///
+    /// // white wherever the ray hits anything
+    /// let mask = camera.render_with(&world, &RenderSettings::default(), |ray, world| {
+    ///     match world.ray_cast(ray) {
+    ///         Some(_) => Color::new(1.0, 1.0, 1.0),
+    ///         None => Color::new(0.0, 0.0, 0.0),
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(Color::new(1.0, 1.0, 1.0), mask.pixel_at(2, 2));
+    /// assert_eq!(Color::new(0.0, 0.0, 0.0), mask.pixel_at(0, 0));
+    /// ```
+    pub fn render_with<F>(&self, world: &World, settings: &RenderSettings, integrator: F) -> Canvas
+    where
+        F: Fn(&Ray, &World) -> Color + Sync,
+    {
+        self.render_by(settings, |ray| integrator(ray, world), None)
+    }
+
+    /// Renders the fraction of each pixel covered by objects which are not
+    /// holdouts, white where fully covered, for use as an alpha mask
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling and threading
+    pub fn render_coverage(&self, world: &World, settings: &RenderSettings) -> Canvas {
+        let range = self.clip_range();
+
+        self.render_by(
+            settings,
+            |ray| Color::new(1.0, 1.0, 1.0) * world.coverage_within(ray, settings, range),
+            None,
+        )
+    }
+
+    /// Returns the range of distances along camera rays within which
+    /// objects are rendered
+    fn clip_range(&self) -> Interval {
+        Interval::new(self.near.unwrap_or(0.0), self.far.unwrap_or(f64::INFINITY))
+    }
+
+    fn render_by<F>(
+        &self,
+        settings: &RenderSettings,
+        sample: F,
+        reporter: Option<&Reporter>,
+    ) -> Canvas
+    where
+        F: Fn(&Ray) -> Color + Sync,
+    {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        self.render_by_into(settings, sample, reporter, &mut image);
+
+        image
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            name = "render",
+            skip_all,
+            fields(width = self.hsize, height = self.vsize, samples = settings.samples_per_pixel())
+        )
+    )]
+    fn render_by_into<F, B>(
+        &self,
+        settings: &RenderSettings,
+        sample: F,
+        reporter: Option<&Reporter>,
+        target: &mut B,
+    ) where
+        F: Fn(&Ray) -> Color + Sync,
+        B: Framebuffer + ?Sized,
+    {
+        let inverse = self.transform.inverse();
+
+        #[cfg(not(feature = "std"))]
+        let rows: Vec<(usize, Vec<Color>)> = (0..self.vsize)
+            .map(|y| (y, self.render_row(settings, &inverse, y, &sample, reporter)))
+            .collect();
+
+        #[cfg(feature = "std")]
+        let threads = settings.thread_count().min(self.vsize).max(1);
+        #[cfg(feature = "std")]
+        let rows: Vec<(usize, Vec<Color>)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|offset| {
+                    let inverse = &inverse;
+                    let sample = &sample;
+
+                    scope.spawn(move || {
+                        #[cfg(feature = "tracing")]
+                        let _span = tracing::debug_span!("render_rows", offset, threads).entered();
+
+                        (offset..self.vsize)
+                            .step_by(threads)
+                            .map(|y| (y, self.render_row(settings, inverse, y, sample, reporter)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("render thread panicked"))
+                .collect()
+        });
+
+        compose(rows, target);
+    }
+
+    fn render_row<F>(
+        &self,
+        settings: &RenderSettings,
+        inverse: &Matrix,
+        y: usize,
+        sample: &F,
+        reporter: Option<&Reporter>,
+    ) -> Vec<Color>
+    where
+        F: Fn(&Ray) -> Color,
+    {
+        let row = (0..self.hsize)
+            .map(|x| self.render_pixel(settings, inverse, x, y, sample))
+            .collect();
+
+        if let Some(reporter) = reporter {
+            reporter.tile_done(self.hsize);
+        }
+
+        #[cfg(feature = "std")]
+        if settings.yield_between_rows {
+            std::thread::yield_now();
+        }
+
+        row
+    }
+
+    fn render_pixel<F>(
+        &self,
+        settings: &RenderSettings,
+        inverse: &Matrix,
+        x: usize,
+        y: usize,
+        sample: &F,
+    ) -> Color
+    where
+        F: Fn(&Ray) -> Color,
+    {
+        let n = settings.samples.max(1);
+        let step = 1.0 / n as f64;
+        let mut color = Color::default();
+
+        for sy in 0..n {
+            for sx in 0..n {
+                let ray = self.lens_ray_through(
+                    inverse,
+                    x as f64 + (sx as f64 + 0.5) * step,
+                    y as f64 + (sy as f64 + 0.5) * step,
+                    sy * n + sx,
+                    n * n,
+                );
+                color = color + sample(&ray);
+            }
+        }
+
+        color / settings.samples_per_pixel() as f64
+    }
+
+    /// Returns the ray through the given position on the canvas, measured in
+    /// pixels from its top left corner
+    fn ray_through(&self, inverse: &Matrix, x: f64, y: f64) -> Ray {
+        let world_x = self.half_width - x * self.pixel_size;
+        let world_y = self.half_height - y * self.pixel_size;
+
+        let pixel = inverse * &Tuple::point(world_x, world_y, -1.0);
+        let origin = inverse * &Tuple::point(0.0, 0.0, 0.0);
+
+        Ray::new(origin, (pixel - origin).normalize())
+    }
+
+    /// Returns the ray through the given position on the canvas like
+    /// `ray_through`, starting from the given sample point on the lens and
+    /// aimed at the plane in focus, if the camera has a lens
+    fn lens_ray_through(
+        &self,
+        inverse: &Matrix,
+        x: f64,
+        y: f64,
+        sample: usize,
+        samples: usize,
+    ) -> Ray {
+        let Some(lens) = self.lens else {
+            return self.ray_through(inverse, x, y);
+        };
+
+        let distance = lens.focal_distance;
+        let world_x = self.half_width - x * self.pixel_size;
+        let world_y = self.half_height - y * self.pixel_size;
+        let offset = lens.sample(sample, samples);
+
+        let focus = inverse * &Tuple::point(world_x * distance, world_y * distance, -distance);
+        let origin = inverse * &Tuple::point(offset.x, offset.y, 0.0);
+
+        Ray::new(origin, (focus - origin).normalize())
+    }
+}
+
+/// Writes the given rendered rows into a framebuffer
+fn compose<I, B>(rows: I, target: &mut B)
+where
+    I: IntoIterator<Item = (usize, Vec<Color>)>,
+    B: Framebuffer + ?Sized,
+{
+    for (y, row) in rows {
+        for (x, color) in row.into_iter().enumerate() {
+            target.set_pixel(x, y, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+    use crate::math::{rotation_y, translation, view_transform, Cylinder, Sphere};
+    use core::f64::consts::PI;
+
+    #[test]
+    fn should_contain_correct_data() {
+        let c = Camera::new(160, 120, PI / 2.0);
+
+        assert_eq!(160, c.hsize());
+        assert_eq!(120, c.vsize());
+        assert_eq!(PI / 2.0, c.field_of_view());
+        assert_eq!(Matrix::identity(4), c.transform);
+    }
+
+    #[test]
+    fn should_compute_pixel_size_for_horizontal_canvas() {
+        let c = Camera::new(200, 125, PI / 2.0);
+
+        assert!((c.pixel_size() - 0.01).abs() < EPSILON);
+    }
+
+    #[test]
+    fn should_compute_pixel_size_for_vertical_canvas() {
+        let c = Camera::new(125, 200, PI / 2.0);
+
+        assert!((c.pixel_size() - 0.01).abs() < EPSILON);
+    }
+
+    #[test]
+    fn should_accept_field_of_view_in_degrees() {
+        let c = Camera::new(200, 125, Angle::from_degrees(90.0));
+
+        assert!((c.field_of_view() - PI / 2.0).abs() < EPSILON);
+        assert!((c.pixel_size() - 0.01).abs() < EPSILON);
+    }
+
+    #[test]
+    fn should_construct_ray_through_center_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.position(0.0));
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), r.direction);
+    }
+
+    #[test]
+    fn should_construct_ray_through_corner_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(0, 0);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 0.0), r.position(0.0));
+        assert_eq!(Tuple::vector(0.66519, 0.33259, -0.66851), r.direction);
+    }
+
+    #[test]
+    fn should_construct_ray_when_camera_is_transformed() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        c.transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(Tuple::point(0.0, 2.0, -5.0), r.position(0.0));
+        assert_eq!(
+            Tuple::vector(2.0_f64.sqrt() / 2.0, 0.0, -(2.0_f64.sqrt() / 2.0)),
+            r.direction
+        );
+    }
+
+    #[test]
+    fn should_render_world_with_camera() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Tuple::point(0.0, 0.0, -5.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+        c.transform(view_transform(from, to, up));
+        let image = c.render(&w, &RenderSettings::default());
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), image.pixel_at(5, 5));
+    }
+
+    #[test]
+    fn render_with_should_match_render_for_the_default_integrator() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let settings = RenderSettings {
+            samples: 2,
+            threads: 3,
+            ..RenderSettings::default()
+        };
+        let custom = c.render_with(&w, &settings, |ray, world| {
+            world.color_at_with(ray, &settings)
+        });
+
+        let image = c.render(&w, &settings);
+
+        for y in 0..11 {
+            for x in 0..11 {
+                assert_eq!(image.pixel_at(x, y), custom.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn render_in_pool_should_match_render() {
+        let w = World::default_world();
+        let c = Camera::new(9, 7, PI / 2.0);
+        let settings = RenderSettings {
+            samples: 2,
+            yield_between_rows: true,
+            reserve_core: true,
+            ..RenderSettings::default()
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap();
+
+        let pooled = c.render_in(&pool, &w, &settings);
+        let image = c.render(&w, &settings);
+
+        for y in 0..7 {
+            for x in 0..9 {
+                assert_eq!(image.pixel_at(x, y), pooled.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn render_into_should_fill_external_framebuffer() {
+        struct Buffer(Vec<Color>);
+
+        impl Framebuffer for Buffer {
+            fn width(&self) -> usize {
+                4
+            }
+
+            fn height(&self) -> usize {
+                3
+            }
+
+            fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+                self.0[y * 4 + x] = color;
+            }
+        }
+
+        let w = World::default_world();
+        let c = Camera::new(4, 3, PI / 2.0);
+        let settings = RenderSettings {
+            threads: 2,
+            ..RenderSettings::default()
+        };
+        let mut buffer = Buffer(vec![Color::default(); 12]);
+
+        c.render_into(&w, &settings, &mut buffer);
+        let image = c.render(&w, &settings);
+
+        for y in 0..3 {
+            for x in 0..4 {
+                assert_eq!(image.pixel_at(x, y), buffer.0[y * 4 + x]);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "framebuffer size differs from the camera")]
+    fn render_into_should_reject_framebuffer_of_wrong_size() {
+        Camera::new(4, 3, PI / 2.0).render_into(
+            &World::new(),
+            &RenderSettings::default(),
+            &mut Canvas::new(3, 4),
+        );
+    }
+
+    #[test]
+    fn render_with_progress_should_report_every_row_across_threads() {
+        use crate::scene::RenderProgress;
+        use std::sync::Mutex;
+
+        let w = World::default_world();
+        let c = Camera::new(7, 5, PI / 2.0);
+        let settings = RenderSettings {
+            samples: 2,
+            threads: 3,
+            ..RenderSettings::default()
+        };
+        let updates = Mutex::new(Vec::new());
+        let sink = |p: &RenderProgress| updates.lock().unwrap().push(*p);
+
+        let image = c.render_with_progress(&w, &settings, &sink);
+        let reference = c.render(&w, &settings);
+        let last = updates
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .max_by_key(|p| p.tiles_done)
+            .unwrap();
+
+        assert_eq!(5, last.tiles_total);
+        assert!(last.is_finished());
+        assert_eq!(35, last.pixels_done);
+        assert_eq!(140, last.samples_traced);
+        assert_eq!(reference.pixel_at(3, 2), image.pixel_at(3, 2));
+    }
+
+    #[test]
+    fn should_apply_exposure_to_rendered_colors() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        c.exposure = Some(Exposure::new(1.0, 1.0, 100.0));
+        let image = c.render(&w, &RenderSettings::default());
+
+        assert_eq!(
+            Color::new(0.38066, 0.47583, 0.2855) * (1.0 / 1.2),
+            image.pixel_at(5, 5)
+        );
+    }
+
+    #[test]
+    fn lens_should_blur_objects_away_from_focal_distance() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let settings = RenderSettings {
+            samples: 4,
+            ..Default::default()
+        };
+        let same = |a: &Canvas, b: &Canvas| {
+            (0..11).all(|y| (0..11).all(|x| a.pixel_at(x, y) == b.pixel_at(x, y)))
+        };
+        let pinhole = c.render(&w, &settings);
+
+        c.lens = Some(Lens::new(0.0, 4.0));
+        assert!(same(&pinhole, &c.render(&w, &settings)));
+
+        c.lens = Some(Lens::new(0.5, 4.0));
+        assert!(!same(&pinhole, &c.render(&w, &settings)));
+        assert!(same(
+            &c.render(&w, &RenderSettings::default()),
+            &Camera {
+                lens: None,
+                ..c.clone()
+            }
+            .render(&w, &RenderSettings::default())
+        ));
+    }
+
+    #[test]
+    fn should_pick_object_under_pixel() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let (id, comps) = c.pick(&w, 5, 5).unwrap();
+
+        assert_eq!(ShapeId(0), id);
+        assert_eq!(4.0, comps.t);
+        assert!(c.pick(&w, 0, 0).is_none());
+    }
+
+    #[test]
+    fn should_render_identically_on_multiple_threads() {
+        let w = World::default_world();
+        let mut c = Camera::new(16, 9, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let single = c.render(&w, &RenderSettings::default());
+        let settings = RenderSettings {
+            threads: 4,
+            ..Default::default()
+        };
+        let multi = c.render(&w, &settings);
+
+        for y in 0..9 {
+            for x in 0..16 {
+                assert_eq!(single.pixel_at(x, y), multi.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn should_average_antialiasing_samples() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let settings = RenderSettings {
+            samples: 2,
+            ..Default::default()
+        };
+        let inverse = c.transform.inverse();
+        let reference = [0.25, 0.75]
+            .iter()
+            .flat_map(|dy| [0.25, 0.75].map(|dx| (dx, *dy)))
+            .map(|(dx, dy)| w.color_at(&c.ray_through(&inverse, 3.0 + dx, 2.0 + dy)))
+            .fold(Color::default(), |a, b| a + b)
+            / 4.0;
+        let image = c.render(&w, &settings);
+
+        assert_eq!(reference, image.pixel_at(3, 2));
+    }
+
+    #[test]
+    fn should_trace_ray_through_pixel() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let settings = RenderSettings::default();
+        let trace = c.trace_pixel(&w, 5, 5, &settings);
+
+        assert_eq!(c.render(&w, &settings).pixel_at(5, 5), trace.color);
+        assert_eq!(c.ray_for_pixel(5, 5).direction, trace.ray.direction);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_recompute_pixel_size_when_deserialized() {
+        let mut c = Camera::new(200, 125, PI / 2.0);
+        c.transform(translation(0.0, 0.0, -5.0));
+        let json = serde_json::to_string(&c).unwrap();
+        let restored: Camera = serde_json::from_str(&json).unwrap();
+
+        assert!(!json.contains("pixel_size"));
+        assert!((restored.pixel_size() - 0.01).abs() < EPSILON);
+        assert_eq!(c.transform, restored.transform);
+    }
+
+    #[test]
+    fn should_render_coverage_mask_without_holdouts() {
+        let mut w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Tuple::point(0.0, 0.0, -5.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+        c.transform(view_transform(from, to, up));
+        let settings = RenderSettings::default();
+
+        let mask = c.render_coverage(&w, &settings);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), mask.pixel_at(5, 5));
+        assert_eq!(Color::default(), mask.pixel_at(0, 0));
+
+        w.objects[0].downcast_mut::<Sphere>().unwrap().holdout = true;
+        let mask = c.render_coverage(&w, &settings);
+
+        assert_eq!(Color::default(), mask.pixel_at(5, 5));
+        assert_eq!(Color::default(), c.render(&w, &settings).pixel_at(5, 5));
+    }
+
+    #[test]
+    fn should_project_points_back_onto_the_canvas() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        c.transform(rotation_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+        let r = c.ray_for_pixel(37, 80);
+        let p = c.project(r.position(3.0)).unwrap();
+
+        assert_eq!(Point2::new(37.5, 80.5), p);
+        assert_eq!(None, c.project(r.position(-3.0)));
+    }
+
+    #[test]
+    fn should_clip_objects_outside_of_near_and_far_distances() {
+        let w = World::default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Tuple::point(0.0, 0.0, -5.0);
+        let to = Tuple::point(0.0, 0.0, 0.0);
+        let up = Tuple::vector(0.0, 1.0, 0.0);
+        c.transform(view_transform(from, to, up));
+        let settings = RenderSettings::default();
+        let unclipped = c.render(&w, &settings).pixel_at(5, 5);
+
+        c.far = Some(3.0);
+
+        assert_eq!(Color::default(), c.render(&w, &settings).pixel_at(5, 5));
+
+        c.far = None;
+        c.near = Some(4.2);
+
+        assert_ne!(unclipped, c.render(&w, &settings).pixel_at(5, 5));
+        assert_eq!(
+            Color::default(),
+            c.render_coverage(&w, &settings).pixel_at(0, 0)
+        );
+    }
+
+    #[test]
+    fn should_overlay_bounding_boxes() {
+        let mut w = World::new();
+        w.objects.push(Box::new(Sphere::new()));
+        let mut c = Camera::new(21, 21, PI / 2.0);
+        c.transform(view_transform(
+            Tuple::point(0.0, 0.0, -5.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let mut image = Canvas::new(21, 21);
+        let red = Color::new(1.0, 0.0, 0.0);
+
+        c.overlay_bounds(&w, &mut image, red);
+
+        let p = c.project(Tuple::point(1.0, 1.0, -1.0)).unwrap();
+
+        assert_eq!(red, image.pixel_at(p.x as usize, p.y as usize));
+        assert_eq!(Color::default(), image.pixel_at(10, 10));
+        assert_eq!(Color::default(), image.pixel_at(0, 0));
+
+        w.objects[0] = Box::new(Cylinder::new());
+        let mut image = Canvas::new(21, 21);
+        c.overlay_bounds(&w, &mut image, red);
+
+        assert_eq!(Color::default(), image.pixel_at(p.x as usize, p.y as usize));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::math::{view_transform, Matrix, Spline, SplineKind, TransformProvider, Tuple};
+use alloc::vec;
+
+/// Camera motion along a spline, looking at a target which may move along a
+/// spline of its own, for fly-through animations
+///
+/// The camera reaches the end of both splines after the duration and stays
+/// there afterwards.
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{view_transform, Spline, SplineKind, Tuple};
+/// use libray::scene::{Animation, Camera, CameraPath, World};
+///
+/// let path = CameraPath::new(
+///     Spline::new(
+///         SplineKind::CatmullRom,
+///         vec![
+///             Tuple::point(-5.0, 1.0, -5.0),
+///             Tuple::point(0.0, 2.0, -6.0),
+///             Tuple::point(5.0, 1.0, -5.0),
+///         ],
+///     ),
+///     Tuple::point(0.0, 0.0, 0.0),
+///     4.0,
+/// );
+/// let mut animation = Animation::new();
+/// animation.animate_camera(path);
+///
+/// let mut camera = Camera::new(10, 10, 1.0);
+/// animation.apply(&mut World::new(), &mut camera, 2.0);
+///
+/// assert_eq!(
+///     view_transform(
+///         Tuple::point(0.0, 2.0, -6.0),
+///         Tuple::point(0.0, 0.0, 0.0),
+///         Tuple::vector(0.0, 1.0, 0.0)
+///     ),
+///     camera.transform
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraPath {
+    /// Path of the camera position
+    pub position: Spline,
+    /// Path of the point the camera looks at
+    pub target: Spline,
+    /// Vector pointing approximately upwards
+    pub up: Tuple,
+    /// Time taken to travel the whole path, in seconds
+    pub duration: f64,
+}
+
+impl CameraPath {
+    /// Returns a new path looking at a fixed point, with y pointing up
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - path of the camera position
+    /// * `target` - point the camera looks at
+    /// * `duration` - time taken to travel the whole path, in seconds
+    pub fn new(position: Spline, target: Tuple, duration: f64) -> CameraPath {
+        CameraPath {
+            position,
+            target: Spline::new(SplineKind::CatmullRom, vec![target]),
+            up: Tuple::vector(0.0, 1.0, 0.0),
+            duration,
+        }
+    }
+
+    /// Returns the camera position and the point it looks at the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - time since the start of the path, in seconds
+    pub fn look_at(&self, time: f64) -> (Tuple, Tuple) {
+        let u = if self.duration > 0.0 {
+            time / self.duration
+        } else {
+            1.0
+        };
+
+        (self.position.point_at(u), self.target.point_at(u))
+    }
+}
+
+impl TransformProvider for CameraPath {
+    fn transform_at(&self, time: f64) -> Matrix {
+        let (from, to) = self.look_at(time);
+
+        view_transform(from, to, self.up)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_should_follow_both_splines_and_stop_at_the_end() {
+        let mut path = CameraPath::new(
+            Spline::new(
+                SplineKind::Bezier,
+                vec![
+                    Tuple::point(0.0, 0.0, -5.0),
+                    Tuple::point(1.0, 0.0, -5.0),
+                    Tuple::point(2.0, 0.0, -5.0),
+                    Tuple::point(3.0, 0.0, -5.0),
+                ],
+            ),
+            Tuple::point(0.0, 0.0, 0.0),
+            2.0,
+        );
+        path.target = Spline::new(
+            SplineKind::CatmullRom,
+            vec![Tuple::point(0.0, 0.0, 0.0), Tuple::point(4.0, 0.0, 0.0)],
+        );
+
+        assert_eq!(
+            (Tuple::point(1.5, 0.0, -5.0), Tuple::point(2.0, 0.0, 0.0)),
+            path.look_at(1.0)
+        );
+        assert_eq!(path.look_at(2.0), path.look_at(10.0));
+        assert_eq!(
+            view_transform(
+                Tuple::point(3.0, 0.0, -5.0),
+                Tuple::point(4.0, 0.0, 0.0),
+                Tuple::vector(0.0, 1.0, 0.0)
+            ),
+            path.transform_at(5.0)
+        );
+    }
+}
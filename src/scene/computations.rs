@@ -0,0 +1,257 @@
+use super::SHADOW_BIAS;
+use crate::math::{reflect, Intersection, Ray, Shape, Tuple};
+use alloc::{vec, vec::Vec};
+
+/// Precomputed state of an intersection, used when shading a hit
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Computations<'a> {
+    /// Time of the intersection
+    pub t: f64,
+    /// Reference to intersected object
+    pub object: &'a dyn Shape,
+    /// Point in world space where the intersection occured
+    pub point: Tuple,
+    /// Point slightly above the surface, used to avoid shadow acne
+    pub over_point: Tuple,
+    /// Point slightly below the surface, where refracted rays originate
+    pub under_point: Tuple,
+    /// Vector pointing back towards the eye
+    pub eyev: Tuple,
+    /// Normal vector of the surface, facing the eye
+    pub normalv: Tuple,
+    /// Incoming ray direction reflected around the normal
+    pub reflectv: Tuple,
+    /// Whether the intersection occured inside the object
+    pub inside: bool,
+    /// Refractive index of the material the ray is leaving
+    pub n1: f64,
+    /// Refractive index of the material the ray is entering
+    pub n2: f64,
+}
+
+impl<'a> Intersection<'a> {
+    /// Precomputes the state of the intersection for the given ray
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray which produced the intersection
+    /// * `intersections` - all intersections along the ray, sorted by time,
+    ///   used to find the refractive indices on both sides of the surface
+    pub fn prepare_computations(
+        &self,
+        ray: &Ray,
+        intersections: &[Intersection<'a>],
+    ) -> Computations<'a> {
+        self.prepare_computations_with_bias(ray, intersections, SHADOW_BIAS)
+    }
+
+    /// Precomputes the state of the intersection for the given ray, offsetting
+    /// the over and under points by the given distance from the surface
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray which produced the intersection
+    /// * `intersections` - all intersections along the ray, sorted by time
+    /// * `bias` - distance between the surface and the over and under points
+    pub fn prepare_computations_with_bias(
+        &self,
+        ray: &Ray,
+        intersections: &[Intersection<'a>],
+        bias: f64,
+    ) -> Computations<'a> {
+        let point = ray.position(self.t);
+        let eyev = -ray.direction;
+        let mut normalv = self.object.normal_at(point);
+        let inside = Tuple::dot(&normalv, &eyev) < 0.0;
+
+        if inside {
+            normalv = -normalv;
+        }
+
+        let (n1, n2) = self.refractive_indices(intersections);
+
+        Computations {
+            t: self.t,
+            object: self.object,
+            point,
+            over_point: point + normalv * bias,
+            under_point: point - normalv * bias,
+            eyev,
+            normalv,
+            reflectv: reflect(ray.direction, normalv),
+            inside,
+            n1,
+            n2,
+        }
+    }
+
+    fn refractive_indices(&self, intersections: &[Intersection<'a>]) -> (f64, f64) {
+        let mut containers: Vec<&dyn Shape> = vec![];
+        let mut n1 = 1.0;
+
+        for i in intersections {
+            let is_hit = i == self;
+
+            if is_hit {
+                n1 = containers
+                    .last()
+                    .map_or(1.0, |s| s.material().refractive_index);
+            }
+
+            match containers
+                .iter()
+                .position(|s| core::ptr::addr_eq(*s, i.object))
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(i.object),
+            }
+
+            if is_hit {
+                let n2 = containers
+                    .last()
+                    .map_or(1.0, |s| s.material().refractive_index);
+                return (n1, n2);
+            }
+        }
+
+        (n1, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{scaling, translation, Sphere};
+
+    fn glass_sphere(refractive_index: f64) -> Sphere {
+        let mut s = Sphere::new();
+        s.material.refractive_index = refractive_index;
+        s
+    }
+
+    #[test]
+    fn should_precompute_state_of_intersection() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(4.0, &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(i.t, comps.t);
+        assert!(core::ptr::addr_eq(&s, comps.object));
+        assert_eq!(Tuple::point(0.0, 0.0, -1.0), comps.point);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.eyev);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.normalv);
+    }
+
+    #[test]
+    fn should_not_be_inside_when_hit_occurs_on_outside() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(4.0, &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn should_be_inside_and_invert_normal_when_hit_occurs_on_inside() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(1.0, &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(Tuple::point(0.0, 0.0, 1.0), comps.point);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.eyev);
+        assert!(comps.inside);
+        assert_eq!(Tuple::vector(0.0, 0.0, -1.0), comps.normalv);
+    }
+
+    #[test]
+    fn should_offset_over_point_above_the_surface() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let mut s = Sphere::new();
+        s.transform(translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert!(comps.over_point.z < -SHADOW_BIAS / 2.0);
+        assert!(comps.point.z > comps.over_point.z);
+    }
+
+    #[test]
+    fn should_offset_under_point_below_the_surface() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let mut s = glass_sphere(1.5);
+        s.transform(translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert!(comps.under_point.z > SHADOW_BIAS / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+
+    #[test]
+    fn should_precompute_reflection_vector() {
+        let r = Ray::new(
+            Tuple::point(0.0, 1.0, -1.0),
+            Tuple::vector(0.0, -(2.0_f64.sqrt() / 2.0), 2.0_f64.sqrt() / 2.0),
+        );
+        let mut s = Sphere::new();
+        s.transform(translation(0.0, -1.0, 0.0) * scaling(1.0, 0.0001, 1.0));
+        let i = Intersection::new(2.0_f64.sqrt(), &s);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(
+            Tuple::vector(0.0, 2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0),
+            comps.reflectv
+        );
+    }
+
+    #[test]
+    fn should_find_refractive_indices_at_various_intersections() {
+        let mut a = glass_sphere(1.5);
+        a.transform(scaling(2.0, 2.0, 2.0));
+        let mut b = glass_sphere(2.0);
+        b.transform(translation(0.0, 0.0, -0.25));
+        let mut c = glass_sphere(2.5);
+        c.transform(translation(0.0, 0.0, 0.25));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -4.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = [
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ];
+        let reference = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+
+        for (i, (n1, n2)) in xs.iter().zip(reference) {
+            let comps = i.prepare_computations(&r, &xs);
+
+            assert_eq!(n1, comps.n1);
+            assert_eq!(n2, comps.n2);
+        }
+    }
+
+    #[test]
+    fn should_offset_points_by_given_bias() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(4.0, &s);
+        let comps = i.prepare_computations_with_bias(&r, &[i], 0.1);
+
+        assert_eq!(Tuple::point(0.0, 0.0, -1.1), comps.over_point);
+        assert_eq!(Tuple::point(0.0, 0.0, -0.9), comps.under_point);
+    }
+}
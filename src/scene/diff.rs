@@ -0,0 +1,136 @@
+use super::{ShapeId, World};
+use alloc::vec::Vec;
+
+/// Differences between two versions of a world, as found when a scene is
+/// reloaded
+///
+/// Objects are matched by their index, so inserting an object in the
+/// middle of a scene marks every object after it as changed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorldDiff {
+    /// Objects present in both worlds whose properties differ
+    pub changed: Vec<ShapeId>,
+    /// Objects only present in the newer world
+    pub added: Vec<ShapeId>,
+    /// Objects only present in the older world
+    pub removed: Vec<ShapeId>,
+    /// Whether any light source or the ambient light differs
+    pub lights_changed: bool,
+}
+
+impl WorldDiff {
+    /// Returns true if both worlds are equal
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && !self.lights_changed
+    }
+}
+
+impl World {
+    /// Returns the differences between this world and a newer version of it
+    ///
+    /// # Arguments
+    ///
+    /// * `newer` - world to compare against
+    pub fn diff(&self, newer: &World) -> WorldDiff {
+        let shared = self.objects.len().min(newer.objects.len());
+
+        WorldDiff {
+            changed: (0..shared)
+                .filter(|&i| *self.objects[i] != *newer.objects[i])
+                .map(ShapeId)
+                .collect(),
+            added: (shared..newer.objects.len()).map(ShapeId).collect(),
+            removed: (shared..self.objects.len()).map(ShapeId).collect(),
+            lights_changed: self.lights != newer.lights || self.ambient != newer.ambient,
+        }
+    }
+
+    /// Updates this world to match a newer version of it, replacing only the
+    /// objects and lighting which differ, and returns what changed
+    ///
+    /// # Arguments
+    ///
+    /// * `newer` - world to update to
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn reload(&mut self, newer: World) -> WorldDiff {
+        let diff = self.diff(&newer);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            changed = diff.changed.len(),
+            added = diff.added.len(),
+            removed = diff.removed.len(),
+            lights_changed = diff.lights_changed,
+            "reloading world"
+        );
+        let mut objects = newer.objects;
+
+        for id in &diff.changed {
+            self.objects[id.0] = objects[id.0].clone();
+        }
+        self.objects.truncate(objects.len());
+        self.objects.extend(objects.drain(self.objects.len()..));
+
+        if diff.lights_changed {
+            self.lights = newer.lights;
+            self.ambient = newer.ambient;
+        }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::Color;
+    use crate::math::{translation, Cylinder, Sphere};
+
+    #[test]
+    fn diff_should_find_changed_added_and_removed_objects() {
+        let w = World::default_world();
+        let mut newer = w.clone();
+
+        assert!(w.diff(&newer).is_empty());
+
+        newer.objects[1].set_transformation(translation(1.0, 0.0, 0.0));
+        newer.objects.push(Box::new(Sphere::new()));
+        newer.lights[0].intensity = Color::new(0.5, 0.5, 0.5);
+        let diff = w.diff(&newer);
+
+        assert_eq!(vec![ShapeId(1)], diff.changed);
+        assert_eq!(vec![ShapeId(2)], diff.added);
+        assert!(diff.removed.is_empty());
+        assert!(diff.lights_changed);
+        assert_eq!(vec![ShapeId(2)], newer.diff(&w).removed);
+
+        let mut brighter = w.clone();
+        brighter.ambient = Color::new(0.1, 0.1, 0.1);
+        assert!(w.diff(&brighter).lights_changed);
+
+        let mut reshaped = w.clone();
+        reshaped.objects[0] = Box::new(Cylinder::new());
+        assert_eq!(vec![ShapeId(0)], w.diff(&reshaped).changed);
+    }
+
+    #[test]
+    fn reload_should_match_newer_world() {
+        let mut w = World::default_world();
+        let mut newer = w.clone();
+        newer.objects[0].material_mut().color = Color::new(1.0, 0.0, 0.0);
+        newer.objects.push(Box::new(Sphere::new()));
+        let diff = w.reload(newer.clone());
+
+        assert_eq!(vec![ShapeId(0)], diff.changed);
+        assert_eq!(newer.objects, w.objects);
+        assert!(w.diff(&newer).is_empty());
+
+        newer.objects.truncate(1);
+        let diff = w.reload(newer.clone());
+
+        assert_eq!(vec![ShapeId(1), ShapeId(2)], diff.removed);
+        assert_eq!(newer.objects, w.objects);
+    }
+}
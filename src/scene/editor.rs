@@ -0,0 +1,356 @@
+use super::{Camera, RenderSettings, ShapeId, World};
+use crate::graphics::{Canvas, Color, Light, Material};
+use crate::math::{Matrix, Shape};
+use alloc::{boxed::Box, vec, vec::Vec};
+
+/// World and camera wrapped for interactive editing, tracking which tiles
+/// of the image each edit invalidates so that a preview only re-renders
+/// those
+///
+/// Edits to an object mark the tiles covered by its projected bounds before
+/// and after the edit. Shadows and reflections the object casts onto other
+/// parts of the image are not tracked; call `invalidate_all` to refresh
+/// them. Edits to lights or the camera invalidate every tile.
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::Canvas;
+/// use libray::math::{translation, view_transform, Sphere, Tuple};
+/// use libray::scene::{Camera, RenderSettings, SceneEditor, World};
+///
+/// let mut camera = Camera::new(64, 64, 1.0);
+/// camera.transform(view_transform(
+///     Tuple::point(0.0, 0.0, -10.0),
+///     Tuple::point(0.0, 0.0, 0.0),
+///     Tuple::vector(0.0, 1.0, 0.0),
+/// ));
+///
+/// let mut editor = SceneEditor::new(World::new(), camera, 16);
+/// let mut image = Canvas::new(64, 64);
+/// let settings = RenderSettings::default();
+///
+/// assert_eq!(16, editor.render_dirty(&mut image, &settings));
+///
+/// let id = editor.add(Sphere::new());
+/// editor.set_transform(id, translation(1.0, 0.0, 0.0));
+///
+/// assert!(editor.render_dirty(&mut image, &settings) < 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SceneEditor {
+    world: World,
+    camera: Camera,
+    tile_size: usize,
+    columns: usize,
+    dirty: Vec<bool>,
+}
+
+impl SceneEditor {
+    /// Returns a new editor with every tile dirty
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to edit
+    /// * `camera` - camera the preview is rendered with
+    /// * `tile_size` - width and height of a tile in pixels
+    pub fn new(world: World, camera: Camera, tile_size: usize) -> SceneEditor {
+        let tile_size = tile_size.max(1);
+        let columns = camera.hsize().div_ceil(tile_size);
+        let rows = camera.vsize().div_ceil(tile_size);
+
+        SceneEditor {
+            world,
+            camera,
+            tile_size,
+            columns,
+            dirty: vec![true; columns * rows],
+        }
+    }
+
+    /// Returns the edited world
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Returns the camera the preview is rendered with
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Adds an object of any shape to the world and returns its identifier
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - object to add
+    pub fn add<S: Shape + 'static>(&mut self, object: S) -> ShapeId {
+        self.invalidate_object(&object);
+        self.world.objects.push(Box::new(object));
+
+        ShapeId(self.world.objects.len() - 1)
+    }
+
+    /// Removes an object from the world and returns it, shifting the
+    /// identifiers of every later object down by one
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to remove
+    pub fn remove(&mut self, id: ShapeId) -> Box<dyn Shape> {
+        let object = self.world.objects.remove(id.0);
+        self.invalidate_object(&*object);
+
+        object
+    }
+
+    /// Sets the transformation of an object
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to move
+    /// * `transform` - new transformation matrix of the object
+    pub fn set_transform(&mut self, id: ShapeId, transform: Matrix) {
+        self.edit(id, |object| object.set_transformation(transform));
+    }
+
+    /// Sets the material of an object
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object to change
+    /// * `material` - new material of the object
+    pub fn set_material(&mut self, id: ShapeId, material: Material) {
+        self.edit(id, |object| *object.material_mut() = material);
+    }
+
+    /// Replaces every light source, invalidating every tile
+    ///
+    /// # Arguments
+    ///
+    /// * `lights` - new light sources of the world
+    pub fn set_lights(&mut self, lights: Vec<Light>) {
+        self.world.lights = lights;
+        self.invalidate_all();
+    }
+
+    /// Replaces the ambient light of the world, invalidating every tile
+    ///
+    /// # Arguments
+    ///
+    /// * `ambient` - new ambient light of the world
+    pub fn set_ambient(&mut self, ambient: Color) {
+        self.world.ambient = ambient;
+        self.invalidate_all();
+    }
+
+    /// Replaces the camera, invalidating every tile
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new camera renders a canvas of a different size
+    ///
+    /// # Arguments
+    ///
+    /// * `camera` - new camera to render the preview with
+    pub fn set_camera(&mut self, camera: Camera) {
+        assert!(
+            camera.hsize() == self.camera.hsize() && camera.vsize() == self.camera.vsize(),
+            "cannot replace a {}x{} camera by a {}x{} one",
+            self.camera.hsize(),
+            self.camera.vsize(),
+            camera.hsize(),
+            camera.vsize()
+        );
+        self.camera = camera;
+        self.invalidate_all();
+    }
+
+    /// Marks every tile dirty
+    pub fn invalidate_all(&mut self) {
+        self.dirty.fill(true);
+    }
+
+    /// Returns the column and row of every dirty tile
+    pub fn dirty_tiles(&self) -> Vec<(usize, usize)> {
+        (0..self.dirty.len())
+            .filter(|&i| self.dirty[i])
+            .map(|i| (i % self.columns, i / self.columns))
+            .collect()
+    }
+
+    /// Renders every dirty tile onto the canvas, marks them clean and
+    /// returns how many were rendered
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - preview image, of the size of the camera
+    /// * `settings` - settings controlling sampling and shading
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all))]
+    pub fn render_dirty(&mut self, canvas: &mut Canvas, settings: &RenderSettings) -> usize {
+        let tiles = self.dirty_tiles();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tiles = tiles.len(), "rendering dirty tiles");
+
+        for &(column, row) in &tiles {
+            let x = column * self.tile_size;
+            let y = row * self.tile_size;
+
+            self.camera.render_region(
+                &self.world,
+                settings,
+                canvas,
+                x..(x + self.tile_size).min(self.camera.hsize()),
+                y..(y + self.tile_size).min(self.camera.vsize()),
+            );
+        }
+        self.dirty.fill(false);
+
+        tiles.len()
+    }
+
+    fn edit<F: FnOnce(&mut dyn Shape)>(&mut self, id: ShapeId, change: F) {
+        let mut object = self.world.objects[id.0].clone();
+        self.invalidate_object(&*object);
+        change(&mut *object);
+        self.invalidate_object(&*object);
+        self.world.objects[id.0] = object;
+    }
+
+    /// Marks the tiles covered by the projected bounds of the object dirty,
+    /// or every tile if the bounds are unbounded or reach behind the camera
+    fn invalidate_object(&mut self, object: &dyn Shape) {
+        let corners = object.bounding_corners();
+
+        if corners.iter().any(|c| !c.is_finite()) {
+            self.invalidate_all();
+            return;
+        }
+
+        let corners = corners.map(|c| self.camera.project(c));
+
+        if corners.iter().any(Option::is_none) {
+            self.invalidate_all();
+            return;
+        }
+
+        let corners = corners.map(Option::unwrap);
+        let min_x = corners.iter().map(|p| p.x).fold(f64::INFINITY, f64::min) - 1.0;
+        let max_x = corners
+            .iter()
+            .map(|p| p.x)
+            .fold(f64::NEG_INFINITY, f64::max)
+            + 1.0;
+        let min_y = corners.iter().map(|p| p.y).fold(f64::INFINITY, f64::min) - 1.0;
+        let max_y = corners
+            .iter()
+            .map(|p| p.y)
+            .fold(f64::NEG_INFINITY, f64::max)
+            + 1.0;
+        let (width, height) = (self.camera.hsize() as f64, self.camera.vsize() as f64);
+
+        if max_x < 0.0 || max_y < 0.0 || min_x >= width || min_y >= height {
+            return;
+        }
+
+        let tile = self.tile_size as f64;
+        let to_tile = |value: f64, limit: f64| (value.clamp(0.0, limit - 1.0) / tile) as usize;
+
+        for row in to_tile(min_y, height)..=to_tile(max_y, height) {
+            for column in to_tile(min_x, width)..=to_tile(max_x, width) {
+                self.dirty[row * self.columns + column] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::Color;
+    use crate::math::{scaling, translation, view_transform, Cylinder, Sphere, Tuple};
+
+    fn editor() -> SceneEditor {
+        let mut camera = Camera::new(40, 40, 1.0);
+        camera.transform(view_transform(
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+        let mut editor = SceneEditor::new(World::default_world(), camera, 10);
+        editor.render_dirty(&mut Canvas::new(40, 40), &RenderSettings::default());
+
+        editor
+    }
+
+    #[test]
+    fn new_editor_should_start_with_every_tile_dirty() {
+        let e = SceneEditor::new(World::new(), Camera::new(25, 10, 1.0), 10);
+
+        assert_eq!(3, e.dirty_tiles().len());
+        assert!(editor().dirty_tiles().is_empty());
+    }
+
+    #[test]
+    fn moving_an_object_should_dirty_tiles_it_left_and_entered() {
+        let mut e = editor();
+        let mut small = Sphere::new();
+        small.transform(scaling(0.1, 0.1, 0.1));
+        let id = e.add(small);
+
+        assert_eq!(vec![(1, 1), (2, 1), (1, 2), (2, 2)], e.dirty_tiles());
+
+        e.render_dirty(&mut Canvas::new(40, 40), &RenderSettings::default());
+        e.set_transform(id, translation(4.0, 4.0, 0.0) * scaling(0.1, 0.1, 0.1));
+        let moved = e.camera().project(Tuple::point(4.0, 4.0, 0.0)).unwrap();
+        let tile = ((moved.x / 10.0) as usize, (moved.y / 10.0) as usize);
+        let dirty = e.dirty_tiles();
+
+        assert_eq!(5, dirty.len());
+        assert!(dirty.contains(&(1, 1)));
+        assert!(dirty.contains(&tile));
+    }
+
+    #[test]
+    fn unbounded_objects_should_dirty_every_tile() {
+        let mut e = editor();
+        e.add(Cylinder::new());
+
+        assert_eq!(16, e.dirty_tiles().len());
+    }
+
+    #[test]
+    fn light_and_camera_edits_should_dirty_every_tile() {
+        let mut e = editor();
+        e.set_lights(vec![]);
+
+        assert_eq!(16, e.dirty_tiles().len());
+
+        let mut e = editor();
+        e.set_camera(e.camera().clone());
+
+        assert_eq!(16, e.dirty_tiles().len());
+    }
+
+    #[test]
+    fn render_dirty_should_match_full_render() {
+        let mut e = editor();
+        let settings = RenderSettings::default();
+        let mut image = Canvas::new(40, 40);
+        e.invalidate_all();
+        e.render_dirty(&mut image, &settings);
+
+        let mut material = Material::new();
+        material.color = Color::new(1.0, 0.0, 0.0);
+        e.set_material(ShapeId(1), material);
+        e.remove(ShapeId(0));
+        e.render_dirty(&mut image, &settings);
+
+        let expected = e.camera().render(e.world(), &settings);
+
+        for y in 0..40 {
+            for x in 0..40 {
+                assert_eq!(expected.pixel_at(x, y), image.pixel_at(x, y));
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+/// Physical camera exposure converting photometric luminance, in candela per
+/// square meter, into displayable values
+///
+/// Uses the saturation based sensitivity model: the luminance saturating
+/// the sensor is `1.2 * 2^ev100`, where `ev100` is the exposure value of
+/// the settings at ISO 100.
+///
+/// # Example
+///
+/// ```
+/// use libray::scene::Exposure;
+///
+/// // the sunny 16 rule
+/// let sunny = Exposure::new(16.0, 1.0 / 100.0, 100.0);
+///
+/// assert!((sunny.ev100() - 14.64).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exposure {
+    /// Relative aperture as an f-number
+    pub aperture: f64,
+    /// Time the shutter is open in seconds
+    pub shutter_time: f64,
+    /// Sensor sensitivity as an ISO speed
+    pub iso: f64,
+}
+
+impl Exposure {
+    /// Returns new exposure settings
+    ///
+    /// # Arguments
+    ///
+    /// * `aperture` - relative aperture as an f-number
+    /// * `shutter_time` - time the shutter is open in seconds
+    /// * `iso` - sensor sensitivity as an ISO speed
+    pub fn new(aperture: f64, shutter_time: f64, iso: f64) -> Exposure {
+        Exposure {
+            aperture,
+            shutter_time,
+            iso,
+        }
+    }
+
+    /// Returns the exposure value of the settings at ISO 100
+    pub fn ev100(&self) -> f64 {
+        (self.aperture * self.aperture / self.shutter_time * 100.0 / self.iso).log2()
+    }
+
+    /// Returns the factor luminance is multiplied by to map the luminance
+    /// saturating the sensor to one
+    pub fn factor(&self) -> f64 {
+        1.0 / (1.2 * 2f64.powf(self.ev100()))
+    }
+}
+
+impl Default for Exposure {
+    /// Returns the exposure of a typical indoor scene: f/2.8, 1/60 s at
+    /// ISO 800
+    fn default() -> Exposure {
+        Exposure::new(2.8, 1.0 / 60.0, 800.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+
+    #[test]
+    fn should_compute_exposure_value_and_factor() {
+        let e = Exposure::new(1.0, 1.0, 100.0);
+
+        assert_eq!(0.0, e.ev100());
+        assert!((e.factor() - 1.0 / 1.2).abs() < EPSILON);
+
+        let faster = Exposure::new(1.0, 0.5, 100.0);
+        let sensitive = Exposure::new(1.0, 1.0, 200.0);
+
+        assert!((faster.ev100() - 1.0).abs() < EPSILON);
+        assert!((sensitive.ev100() + 1.0).abs() < EPSILON);
+        assert!((sensitive.factor() - 2.0 * e.factor()).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::graphics::{CheckerMode, Color, Material, Pattern};
+use crate::math::{scaling, Plane};
+
+/// Fraction of light the floor reflects, enough to pick up a faint mirror
+/// image of the objects standing on it
+const FLOOR_REFLECTIVE: f64 = 0.1;
+
+/// Returns the classic checkered floor: the `y = 0` plane without highlights
+/// and slightly reflective, covered in square cells of two alternating
+/// colors
+///
+/// The checkers are planar, so cells stay free of the acne solid checkers
+/// show on a surface lying exactly on a cell boundary.
+///
+/// # Arguments
+///
+/// * `a` - color of cells with an even coordinate sum
+/// * `b` - color of cells with an odd coordinate sum
+/// * `cell_size` - width of a single cell in world units
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::Color;
+/// use libray::math::{Ray, Tuple};
+/// use libray::scene::checkered_floor;
+///
+/// let floor = checkered_floor(Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0), 0.5);
+///
+/// // flat all the way to the horizon
+/// for x in [0.25, 1000.0] {
+///     let ray = Ray::new(Tuple::point(x, 1.0, 0.25), Tuple::vector(0.0, -1.0, 0.0));
+///     assert_eq!(1.0, ray.intersect(&floor)[0].t);
+/// }
+/// assert!(floor.material.reflective > 0.0);
+/// ```
+pub fn checkered_floor(a: Color, b: Color, cell_size: f64) -> Plane {
+    let mut checkers = Pattern::checkers_with_mode(a, b, CheckerMode::Planar);
+    checkers.transform = scaling(cell_size, cell_size, cell_size);
+
+    let mut floor = Plane::new();
+    floor.material = Material {
+        pattern: Some(checkers),
+        specular: 0.0,
+        reflective: FLOOR_REFLECTIVE,
+        ..Material::new()
+    };
+
+    floor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::pattern_at_shape;
+    use crate::math::Tuple;
+
+    #[test]
+    fn floor_should_alternate_cells_of_the_given_size() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let floor = checkered_floor(white, black, 2.0);
+        let pattern = floor.material.pattern.as_ref().unwrap();
+        let at = |x, z| pattern_at_shape(pattern, &floor, Tuple::point(x, 0.0, z));
+
+        assert_eq!(white, at(0.5, 0.5));
+        assert_eq!(white, at(1.5, 1.5));
+        assert_eq!(black, at(2.5, 0.5));
+        assert_eq!(black, at(-0.5, 0.5));
+        assert_eq!(white, at(-2.5, 0.5));
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::Point2;
+
+/// Golden angle in radians, spreading consecutive lens samples evenly
+const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+/// Thin lens giving a camera a depth of field, keeping only the objects at
+/// the focal distance sharp
+///
+/// # Example
+///
+/// ```
+/// use libray::scene::{Camera, Lens};
+///
+/// let mut camera = Camera::new(10, 10, 1.0);
+/// camera.lens = Some(Lens::new(0.1, 5.0));
+///
+/// assert_eq!(5.0, camera.lens.unwrap().focal_distance);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lens {
+    /// Radius of the lens aperture in world space units, where larger
+    /// radiuses blur out of focus objects more
+    pub radius: f64,
+    /// Distance from the camera to the plane in focus, measured along the
+    /// view direction
+    pub focal_distance: f64,
+}
+
+impl Lens {
+    /// Returns a new lens
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - radius of the lens aperture in world space units
+    /// * `focal_distance` - distance from the camera to the plane in focus
+    pub fn new(radius: f64, focal_distance: f64) -> Lens {
+        Lens {
+            radius,
+            focal_distance,
+        }
+    }
+
+    /// Returns the point on the lens used by the given sample of a pixel,
+    /// relative to the lens center
+    ///
+    /// The samples follow a sunflower spiral starting at the center, so that
+    /// a single sample renders like a pinhole camera and every additional
+    /// sample covers the aperture evenly.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - index of the sample within the pixel
+    /// * `samples` - number of samples taken per pixel
+    pub fn sample(&self, sample: usize, samples: usize) -> Point2 {
+        let r = self.radius * (sample as f64 / samples.max(1) as f64).sqrt();
+        let theta = sample as f64 * GOLDEN_ANGLE;
+
+        Point2::new(r * theta.cos(), r * theta.sin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+
+    #[test]
+    fn samples_should_start_at_center_and_stay_within_aperture() {
+        let lens = Lens::new(0.5, 3.0);
+
+        assert_eq!(Point2::new(0.0, 0.0), lens.sample(0, 16));
+        for i in 0..16 {
+            let p = lens.sample(i, 16);
+            assert!((p.x * p.x + p.y * p.y).sqrt() <= 0.5 + EPSILON);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use super::{Camera, World};
+use crate::graphics::{Canvas, Color, Light, LightLinking, Material, Pattern, PatternKind};
+use crate::math::{Matrix, Shape};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+/// Estimate of the memory held by a value, including its heap allocations
+pub trait MemoryUsage {
+    /// Returns the estimated number of bytes used by the value
+    fn memory_usage(&self) -> usize;
+}
+
+impl MemoryUsage for Matrix {
+    fn memory_usage(&self) -> usize {
+        size_of::<Matrix>() + self.heap_size()
+    }
+}
+
+impl MemoryUsage for Canvas {
+    fn memory_usage(&self) -> usize {
+        size_of::<Canvas>()
+            + self.height * size_of::<Vec<Color>>()
+            + self.width * self.height * size_of::<Color>()
+    }
+}
+
+impl MemoryUsage for Pattern {
+    fn memory_usage(&self) -> usize {
+        let boxed = match &self.kind {
+            PatternKind::CubeMap(faces) => size_of_val(faces.as_ref()),
+            _ => 0,
+        };
+
+        size_of::<Pattern>() + self.transform.heap_size() + boxed
+    }
+}
+
+impl MemoryUsage for Material {
+    fn memory_usage(&self) -> usize {
+        let pattern = self
+            .pattern
+            .as_ref()
+            .map_or(0, |p| p.memory_usage() - size_of::<Pattern>());
+
+        size_of::<Material>() + pattern
+    }
+}
+
+impl MemoryUsage for LightLinking {
+    fn memory_usage(&self) -> usize {
+        let groups = match self {
+            LightLinking::Include(groups) | LightLinking::Exclude(groups) => {
+                groups.capacity() * size_of::<String>()
+                    + groups.iter().map(String::capacity).sum::<usize>()
+            }
+            _ => 0,
+        };
+
+        size_of::<LightLinking>() + groups
+    }
+}
+
+impl MemoryUsage for Light {
+    fn memory_usage(&self) -> usize {
+        size_of::<Light>() + self.group.as_ref().map_or(0, String::capacity)
+    }
+}
+
+impl MemoryUsage for dyn Shape {
+    fn memory_usage(&self) -> usize {
+        size_of_val(self) + self.transformation().heap_size() + self.material().memory_usage()
+            - size_of::<Material>()
+            + self.light_linking().memory_usage()
+            - size_of::<LightLinking>()
+    }
+}
+
+impl MemoryUsage for World {
+    fn memory_usage(&self) -> usize {
+        size_of::<World>()
+            + self.objects.capacity() * size_of::<Box<dyn Shape>>()
+            + self
+                .objects
+                .iter()
+                .map(|object| object.memory_usage())
+                .sum::<usize>()
+            + (self.lights.capacity() - self.lights.len()) * size_of::<Light>()
+            + self.lights.iter().map(Light::memory_usage).sum::<usize>()
+    }
+}
+
+/// Largest amount of memory a scene and its render may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryBudget {
+    /// Number of bytes available
+    pub limit: usize,
+}
+
+/// Error returned when an estimate exceeds a memory budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    /// Estimated number of bytes required
+    pub required: usize,
+    /// Number of bytes available
+    pub limit: usize,
+}
+
+impl MemoryBudget {
+    /// Returns a new budget of the given number of bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - number of bytes available
+    pub fn new(limit: usize) -> MemoryBudget {
+        MemoryBudget { limit }
+    }
+
+    /// Returns the estimated memory used by the value, or an error if it
+    /// exceeds the budget
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - value to estimate
+    pub fn check<T: MemoryUsage + ?Sized>(&self, value: &T) -> Result<usize, BudgetExceeded> {
+        self.fits(value.memory_usage())
+    }
+
+    /// Returns the estimated memory needed to render the world with the
+    /// given camera, holding the world, the rendered rows and the final
+    /// canvas at once, or an error if it exceeds the budget
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `camera` - camera to render with
+    pub fn check_render(&self, world: &World, camera: &Camera) -> Result<usize, BudgetExceeded> {
+        let canvas = size_of::<Canvas>()
+            + camera.vsize() * size_of::<Vec<Color>>()
+            + camera.hsize() * camera.vsize() * size_of::<Color>();
+
+        self.fits(world.memory_usage() + 2 * canvas)
+    }
+
+    fn fits(&self, required: usize) -> Result<usize, BudgetExceeded> {
+        if required > self.limit {
+            Err(BudgetExceeded {
+                required,
+                limit: self.limit,
+            })
+        } else {
+            Ok(required)
+        }
+    }
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "estimated memory use of {} bytes exceeds the budget of {} bytes",
+            self.required, self.limit
+        )
+    }
+}
+
+impl core::error::Error for BudgetExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::UvPattern;
+    use crate::math::Sphere;
+
+    #[test]
+    fn canvas_usage_should_grow_with_pixels() {
+        let small = Canvas::new(10, 10).memory_usage();
+        let large = Canvas::new(100, 100).memory_usage();
+
+        assert!(small >= 100 * size_of::<Color>());
+        assert!(large - small >= 9900 * size_of::<Color>());
+    }
+
+    #[test]
+    fn world_usage_should_include_objects_and_heap_data() {
+        let mut w = World::new();
+        let empty = w.memory_usage();
+        w.objects.push(Box::new(Sphere::new()));
+        let one = w.memory_usage();
+        w.objects[0].material_mut().pattern = Some(Pattern::cube_map(core::array::from_fn(|_| {
+            UvPattern::Solid(Color::default())
+        })));
+        let patterned = w.memory_usage();
+
+        assert!(one - empty >= size_of::<Sphere>() + 16 * size_of::<f64>());
+        assert!(patterned - one >= 6 * size_of::<UvPattern>());
+    }
+
+    #[test]
+    fn budget_should_reject_renders_too_large() {
+        let w = World::new();
+        let camera = Camera::new(100, 100, 1.0);
+        let required = MemoryBudget::new(usize::MAX)
+            .check_render(&w, &camera)
+            .unwrap();
+
+        assert_eq!(
+            Ok(required),
+            MemoryBudget::new(required).check_render(&w, &camera)
+        );
+        assert_eq!(
+            Err(BudgetExceeded {
+                required,
+                limit: 1000
+            }),
+            MemoryBudget::new(1000).check_render(&w, &camera)
+        );
+        assert!(MemoryBudget::new(1000).check(&Canvas::new(1, 1)).is_ok());
+    }
+}
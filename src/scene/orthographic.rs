@@ -0,0 +1,192 @@
+use super::{RenderSettings, World};
+use crate::graphics::{Canvas, Color};
+use crate::math::{Interval, Matrix, Ray, Tuple};
+use alloc::vec;
+
+/// Camera casting parallel rays through a rectangular window, used to
+/// render depth and height maps of a scene seen straight from one side
+///
+/// # Example
+///
+/// ```
+/// use libray::math::{view_transform, Sphere, Tuple};
+/// use libray::scene::{OrthographicCamera, RenderSettings, World};
+///
+/// let mut world = World::new();
+/// world.objects.push(Box::new(Sphere::new()));
+///
+/// // looking down onto the xz plane from above
+/// let mut camera = OrthographicCamera::new(32, 32, 4.0);
+/// camera.transform(view_transform(
+///     Tuple::point(0.0, 10.0, 0.0),
+///     Tuple::point(0.0, 0.0, 0.0),
+///     Tuple::vector(0.0, 0.0, 1.0),
+/// ));
+///
+/// let heights = camera.depth_map(&world, None, &RenderSettings::default());
+///
+/// assert_eq!(1.0, heights.pixel_at(16, 16).r);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrthographicCamera {
+    /// View transformation matrix of the camera
+    pub transform: Matrix,
+    /// Horizontal size of the canvas in pixels
+    pub hsize: usize,
+    /// Vertical size of the canvas in pixels
+    pub vsize: usize,
+    /// Width of the window rays are cast through, in world space units
+    pub width: f64,
+}
+
+impl OrthographicCamera {
+    /// Returns a new orthographic camera with an identity view
+    /// transformation
+    ///
+    /// # Arguments
+    ///
+    /// * `hsize` - horizontal size of the canvas in pixels
+    /// * `vsize` - vertical size of the canvas in pixels
+    /// * `width` - width of the window rays are cast through, in world space
+    ///   units
+    pub fn new(hsize: usize, vsize: usize, width: f64) -> OrthographicCamera {
+        OrthographicCamera {
+            transform: Matrix::identity(4),
+            hsize,
+            vsize,
+            width,
+        }
+    }
+
+    /// Sets the view transformation matrix of the camera
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - view transformation matrix to set for the camera
+    pub fn transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Returns the size of a single pixel in world space units
+    pub fn pixel_size(&self) -> f64 {
+        self.width / self.hsize as f64
+    }
+
+    /// Returns the ray cast through the center of the given pixel
+    ///
+    /// # Arguments
+    ///
+    /// * `px` - horizontal pixel coordinate
+    /// * `py` - vertical pixel coordinate
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        let size = self.pixel_size();
+        let x = self.width / 2.0 - (px as f64 + 0.5) * size;
+        let y = self.vsize as f64 * size / 2.0 - (py as f64 + 0.5) * size;
+        let inverse = self.transform.inverse();
+        let origin = &inverse * &Tuple::point(x, y, 0.0);
+        let direction = inverse * Tuple::vector(0.0, 0.0, -1.0);
+
+        Ray::new(origin, direction.normalize())
+    }
+
+    /// Renders the distance to the closest object seen through every pixel
+    /// as a grayscale map, white for the nearest distance in the range and
+    /// black for the farthest, so that seen from above brighter pixels are
+    /// higher up
+    ///
+    /// Pixels showing nothing are black.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `range` - nearest and farthest distance mapped onto the map, or
+    ///   `None` to use the range of distances found
+    /// * `settings` - settings to cast rays with
+    pub fn depth_map(
+        &self,
+        world: &World,
+        range: Option<Interval>,
+        settings: &RenderSettings,
+    ) -> Canvas {
+        let mut depths = vec![None; self.hsize * self.vsize];
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                depths[y * self.hsize + x] = world
+                    .cast(&self.ray_for_pixel(x, y), settings.shadow_bias)
+                    .map(|comps| comps.t);
+            }
+        }
+
+        let range = range.unwrap_or_else(|| {
+            depths.iter().flatten().fold(Interval::EMPTY, |found, &t| {
+                found.union(&Interval::new(t, t))
+            })
+        });
+        let span = range.size().max(f64::EPSILON);
+        let mut image = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                if let Some(t) = depths[y * self.hsize + x] {
+                    let height = (1.0 - (t - range.min) / span).clamp(0.0, 1.0);
+
+                    image.write_pixel(x, y, Color::new(height, height, height));
+                }
+            }
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{scaling, translation, view_transform, Sphere};
+
+    fn top_view() -> OrthographicCamera {
+        let mut camera = OrthographicCamera::new(20, 10, 10.0);
+        camera.transform(view_transform(
+            Tuple::point(0.0, 10.0, 0.0),
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(0.0, 0.0, 1.0),
+        ));
+        camera
+    }
+
+    #[test]
+    fn should_cast_parallel_rays() {
+        let camera = top_view();
+        let a = camera.ray_for_pixel(0, 0);
+        let b = camera.ray_for_pixel(19, 9);
+
+        assert_eq!(Tuple::vector(0.0, -1.0, 0.0), a.direction);
+        assert_eq!(a.direction, b.direction);
+        assert_eq!(Tuple::point(-4.75, 10.0, 2.25), a.position(0.0));
+        assert_eq!(Tuple::point(4.75, 10.0, -2.25), b.position(0.0));
+    }
+
+    #[test]
+    fn should_normalize_depths_into_heights() {
+        let mut world = World::new();
+        let mut floor = Sphere::new();
+        floor.transform(translation(0.0, -1.0, 0.0) * scaling(100.0, 1.0, 100.0));
+        let mut tall = Sphere::new();
+        tall.transform(translation(-3.0, 1.0, 0.0));
+        world.objects.push(Box::new(floor));
+        world.objects.push(Box::new(tall));
+        let camera = top_view();
+        let settings = RenderSettings::default();
+
+        let heights = camera.depth_map(&world, None, &settings);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), heights.pixel_at(3, 4));
+        assert!(heights.pixel_at(15, 5).r < 0.01);
+
+        let heights = camera.depth_map(&world, Some(Interval::new(6.0, 14.0)), &settings);
+
+        assert!((heights.pixel_at(15, 5).r - 0.5).abs() < 0.01);
+    }
+}
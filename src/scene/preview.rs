@@ -0,0 +1,144 @@
+use super::{checkered_floor, Camera, RenderSettings, World};
+use crate::graphics::{Canvas, Color, Light, Material};
+use crate::math::{
+    rotation_x, scaling, translation, view_transform, Angle, Plane, Sphere, Tuple, EPSILON,
+};
+use alloc::{boxed::Box, vec};
+use core::f64::consts::FRAC_PI_2;
+
+/// Returns the standard scene for previewing a material: a ball of the
+/// material resting on a pedestal, next to a neutral gray and a chrome
+/// reference ball, on a checkered gray floor in front of a gray backdrop, lit
+/// by a key, a fill and a rim light
+///
+/// The reference balls show the lighting itself, so materials previewed in
+/// separate renders can be compared consistently.
+///
+/// # Arguments
+///
+/// * `material` - material applied to the main ball
+pub fn shader_ball(material: Material) -> World {
+    let floor = checkered_floor(
+        Color::new(0.45, 0.45, 0.45),
+        Color::new(0.35, 0.35, 0.35),
+        0.5,
+    );
+
+    // a wall standing behind the balls, facing the camera
+    let mut backdrop = Plane::new();
+    backdrop.transform(translation(0.0, 0.0, 8.0) * rotation_x(-FRAC_PI_2));
+    backdrop.material = neutral(Color::new(0.5, 0.5, 0.5));
+
+    let mut pedestal = Sphere::new();
+    pedestal.transform(&translation(0.0, 0.1, 0.0) * &scaling(0.7, 0.15, 0.7));
+    pedestal.material = neutral(Color::new(0.2, 0.2, 0.2));
+
+    let mut ball = Sphere::new();
+    ball.transform(translation(0.0, 1.2, 0.0));
+    ball.material = material;
+
+    let mut gray = Sphere::new();
+    gray.transform(&translation(-1.9, 0.4, 0.6) * &scaling(0.4, 0.4, 0.4));
+    gray.material = neutral(Color::new(0.18, 0.18, 0.18));
+
+    let mut chrome = Sphere::new();
+    chrome.transform(&translation(1.9, 0.4, 0.6) * &scaling(0.4, 0.4, 0.4));
+    chrome.material = crate::graphics::presets::chrome();
+
+    World {
+        objects: vec![
+            Box::new(floor),
+            Box::new(backdrop),
+            Box::new(pedestal),
+            Box::new(ball),
+            Box::new(gray),
+            Box::new(chrome),
+        ],
+        lights: vec![
+            Light::new(Tuple::point(-6.0, 8.0, -6.0), Color::new(0.8, 0.8, 0.8)),
+            Light::new(Tuple::point(7.0, 3.0, -5.0), Color::new(0.25, 0.25, 0.25)),
+            Light::new(Tuple::point(0.0, 6.0, 8.0), Color::new(0.4, 0.4, 0.4)),
+        ],
+        ambient: Color::default(),
+        intersection_epsilon: EPSILON,
+    }
+}
+
+/// Returns the camera framing the scene of `shader_ball` on a square canvas
+///
+/// # Arguments
+///
+/// * `size` - width and height of the canvas in pixels
+pub fn shader_ball_camera(size: usize) -> Camera {
+    let mut camera = Camera::new(size, size, Angle::from_degrees(45.0));
+    camera.transform(view_transform(
+        Tuple::point(0.0, 2.2, -6.0),
+        Tuple::point(0.0, 0.9, 0.0),
+        Tuple::vector(0.0, 1.0, 0.0),
+    ));
+
+    camera
+}
+
+/// Renders a square preview of the given material on the scene of
+/// `shader_ball`
+///
+/// # Arguments
+///
+/// * `material` - material to preview
+/// * `size` - width and height of the preview in pixels
+/// * `settings` - settings controlling sampling, threading and shading
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::presets;
+/// use libray::scene::{render_material_preview, RenderSettings};
+///
+/// let preview = render_material_preview(&presets::gold(), 32, &RenderSettings::default());
+///
+/// assert_eq!((32, 32), (preview.width, preview.height));
+/// ```
+pub fn render_material_preview(
+    material: &Material,
+    size: usize,
+    settings: &RenderSettings,
+) -> Canvas {
+    shader_ball_camera(size).render(&shader_ball(material.clone()), settings)
+}
+
+/// Returns a matte material of the given color without any highlight
+fn neutral(color: Color) -> Material {
+    Material {
+        color,
+        specular: 0.0,
+        ..Material::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_should_show_the_material_at_the_center() {
+        let mut material = Material::new();
+        material.color = Color::new(1.0, 0.0, 0.0);
+        let preview = render_material_preview(&material, 41, &RenderSettings::default());
+        let center = preview.pixel_at(20, 16);
+
+        assert!(center.r > 0.3);
+        assert!(center.g < 0.1 && center.b < 0.1);
+    }
+
+    #[test]
+    fn shader_ball_should_keep_reference_objects_between_materials() {
+        let a = shader_ball(Material::new());
+        let b = shader_ball(crate::graphics::presets::rubber());
+
+        assert_eq!(a.objects.len(), b.objects.len());
+        assert_eq!(a.lights, b.lights);
+        assert!(*a.objects[0] == *b.objects[0]);
+        assert!(*a.objects[3] != *b.objects[3]);
+    }
+}
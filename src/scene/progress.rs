@@ -0,0 +1,253 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::{io::Write, sync::Mutex, time::Instant};
+
+/// Snapshot of the state of a render in progress
+///
+/// A tile is the unit of work a renderer reports, a row of pixels for full
+/// renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderProgress {
+    /// Number of finished tiles
+    pub tiles_done: usize,
+    /// Total number of tiles of the render
+    pub tiles_total: usize,
+    /// Number of finished pixels
+    pub pixels_done: usize,
+    /// Number of camera rays traced for the finished pixels
+    pub samples_traced: usize,
+    /// Time since the render started, always zero without the `std` feature
+    pub elapsed: Duration,
+}
+
+impl RenderProgress {
+    /// Returns the finished fraction of the render, from `0.0` to `1.0`
+    pub fn fraction(&self) -> f64 {
+        if self.tiles_total == 0 {
+            1.0
+        } else {
+            self.tiles_done as f64 / self.tiles_total as f64
+        }
+    }
+
+    /// Returns whether every tile of the render is finished
+    pub fn is_finished(&self) -> bool {
+        self.tiles_done >= self.tiles_total
+    }
+
+    /// Returns the estimated time until the render finishes, extrapolated
+    /// from the time spent on the finished tiles, or `None` before any time
+    /// was measured
+    pub fn eta(&self) -> Option<Duration> {
+        if self.is_finished() {
+            return Some(Duration::ZERO);
+        }
+
+        if self.tiles_done == 0 || self.elapsed.is_zero() {
+            return None;
+        }
+
+        let remaining = (self.tiles_total - self.tiles_done) as f64 / self.tiles_done as f64;
+
+        Some(self.elapsed.mul_f64(remaining))
+    }
+}
+
+/// Receiver of progress updates from a render
+///
+/// Updates are sent after every finished tile, possibly from several render
+/// threads at once, so implementations should return quickly. Closures
+/// taking a `&RenderProgress` are sinks as well.
+///
+/// # Example
+///
+/// ```
+/// use libray::scene::{Camera, RenderProgress, RenderSettings, World};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let finished = AtomicUsize::new(0);
+/// let sink = |progress: &RenderProgress| {
+///     finished.fetch_max(progress.tiles_done, Ordering::Relaxed);
+/// };
+///
+/// Camera::new(8, 6, 1.0).render_with_progress(&World::new(), &RenderSettings::default(), &sink);
+///
+/// assert_eq!(6, finished.into_inner());
+/// ```
+pub trait ProgressSink: Sync {
+    /// Receives a snapshot of the render after a tile finished
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - state of the render
+    fn update(&self, progress: &RenderProgress);
+}
+
+impl<F: Fn(&RenderProgress) + Sync> ProgressSink for F {
+    fn update(&self, progress: &RenderProgress) {
+        self(progress)
+    }
+}
+
+/// Progress sink drawing a single line text progress bar with the finished
+/// percentage and estimated time left, redrawn in place on every update
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ProgressBar<W> {
+    writer: Mutex<W>,
+    width: usize,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write + Send> ProgressBar<W> {
+    /// Creates a new progress bar
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the bar, usually standard error
+    /// * `width` - number of characters of the bar itself
+    pub fn new(writer: W, width: usize) -> ProgressBar<W> {
+        ProgressBar {
+            writer: Mutex::new(writer),
+            width,
+        }
+    }
+
+    /// Returns the writer the bar was drawn to
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write + Send> ProgressSink for ProgressBar<W> {
+    fn update(&self, progress: &RenderProgress) {
+        let filled = (progress.fraction() * self.width as f64).round() as usize;
+        let eta = match progress.eta() {
+            Some(eta) => format!("{}s", eta.as_secs()),
+            None => String::from("?"),
+        };
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+
+        // progress is best effort, a failing writer must not abort the render
+        let _ = write!(
+            writer,
+            "\r[{}{}] {:3.0}% eta {}",
+            "#".repeat(filled),
+            " ".repeat(self.width.saturating_sub(filled)),
+            progress.fraction() * 100.0,
+            eta
+        );
+
+        if progress.is_finished() {
+            let _ = writeln!(writer);
+        }
+        let _ = writer.flush();
+    }
+}
+
+/// Shared counters of a render reporting to a progress sink
+pub(crate) struct Reporter<'a> {
+    sink: &'a dyn ProgressSink,
+    tiles_total: usize,
+    samples_per_pixel: usize,
+    tiles_done: AtomicUsize,
+    pixels_done: AtomicUsize,
+    #[cfg(feature = "std")]
+    start: Instant,
+}
+
+impl<'a> Reporter<'a> {
+    /// Creates a new reporter, starting the clock of the render
+    pub(crate) fn new(
+        sink: &'a dyn ProgressSink,
+        tiles_total: usize,
+        samples_per_pixel: usize,
+    ) -> Reporter<'a> {
+        Reporter {
+            sink,
+            tiles_total,
+            samples_per_pixel,
+            tiles_done: AtomicUsize::new(0),
+            pixels_done: AtomicUsize::new(0),
+            #[cfg(feature = "std")]
+            start: Instant::now(),
+        }
+    }
+
+    /// Counts a finished tile and sends the updated progress to the sink
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - number of pixels in the tile
+    pub(crate) fn tile_done(&self, pixels: usize) {
+        let pixels_done = self.pixels_done.fetch_add(pixels, Ordering::Relaxed) + pixels;
+        let tiles_done = self.tiles_done.fetch_add(1, Ordering::Relaxed) + 1;
+
+        self.sink.update(&RenderProgress {
+            tiles_done,
+            tiles_total: self.tiles_total,
+            pixels_done,
+            samples_traced: pixels_done * self.samples_per_pixel,
+            #[cfg(feature = "std")]
+            elapsed: self.start.elapsed(),
+            #[cfg(not(feature = "std"))]
+            elapsed: Duration::ZERO,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn progress(tiles_done: usize, elapsed: Duration) -> RenderProgress {
+        RenderProgress {
+            tiles_done,
+            tiles_total: 4,
+            pixels_done: tiles_done * 10,
+            samples_traced: tiles_done * 10,
+            elapsed,
+        }
+    }
+
+    #[test]
+    fn eta_should_extrapolate_from_finished_tiles() {
+        let p = progress(1, Duration::from_secs(2));
+
+        assert_eq!(0.25, p.fraction());
+        assert_eq!(Some(Duration::from_secs(6)), p.eta());
+        assert_eq!(None, progress(0, Duration::from_secs(2)).eta());
+        assert_eq!(None, progress(1, Duration::ZERO).eta());
+        assert_eq!(Some(Duration::ZERO), progress(4, Duration::ZERO).eta());
+    }
+
+    #[test]
+    fn reporter_should_count_tiles_pixels_and_samples() {
+        let last = Mutex::new(None);
+        let sink = |p: &RenderProgress| *last.lock().unwrap() = Some(*p);
+        let reporter = Reporter::new(&sink, 2, 4);
+
+        reporter.tile_done(3);
+        reporter.tile_done(3);
+        let p = last.lock().unwrap().unwrap();
+
+        assert!(p.is_finished());
+        assert_eq!(6, p.pixels_done);
+        assert_eq!(24, p.samples_traced);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn progress_bar_should_draw_in_place_and_end_line_when_finished() {
+        let bar = ProgressBar::new(Vec::new(), 4);
+
+        bar.update(&progress(2, Duration::from_secs(1)));
+        bar.update(&progress(4, Duration::from_secs(2)));
+        let output = String::from_utf8(bar.into_inner()).unwrap();
+
+        assert_eq!("\r[##  ]  50% eta 1s\r[####] 100% eta 0s\n", output);
+    }
+}
@@ -0,0 +1,246 @@
+use super::{ShapeId, World};
+use crate::math::{Matrix, Sphere, Tuple, EPSILON};
+use alloc::vec::Vec;
+
+/// Maximum number of refinement steps when searching the closest point on
+/// a sphere which was not scaled uniformly
+const MAX_REFINEMENTS: usize = 256;
+
+impl World {
+    /// Returns the object whose surface is closest to the given point,
+    /// together with the closest point on that surface, or `None` if the
+    /// world holds no sphere
+    ///
+    /// Every sphere is tested, as the world keeps no spatial index. Objects
+    /// of other shapes are ignored by this and the other proximity queries.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in world space
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use libray::math::{Sphere, Tuple};
+    /// use libray::scene::{ShapeId, World};
+    ///
+    /// let mut world = World::new();
+    /// world.objects.push(Box::new(Sphere::new()));
+    ///
+    /// let (id, closest) = world.closest_point(Tuple::point(0.0, 3.0, 0.0)).unwrap();
+    ///
+    /// assert_eq!(ShapeId(0), id);
+    /// assert_eq!(Tuple::point(0.0, 1.0, 0.0), closest);
+    /// assert_eq!(Some(2.0), world.distance_to_surface(Tuple::point(0.0, 3.0, 0.0)));
+    /// assert_eq!(vec![ShapeId(0)], world.sphere_overlap(Tuple::point(0.0, 3.0, 0.0), 2.5));
+    /// ```
+    pub fn closest_point(&self, point: Tuple) -> Option<(ShapeId, Tuple)> {
+        self.spheres()
+            .map(|(id, object)| (id, closest_on_sphere(object, point)))
+            .min_by(|(_, a), (_, b)| {
+                (*a - point)
+                    .magnitude()
+                    .total_cmp(&(*b - point).magnitude())
+            })
+    }
+
+    /// Returns the signed distance from the given point to the closest
+    /// sphere in the world, negative inside of it, or `None` if the world
+    /// holds no sphere
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - point in world space
+    pub fn distance_to_surface(&self, point: Tuple) -> Option<f64> {
+        let (id, closest) = self.closest_point(point)?;
+        let distance = (closest - point).magnitude();
+
+        let sphere = self.objects[id.0].downcast_ref::<Sphere>()?;
+
+        if contains(sphere, point) {
+            Some(-distance)
+        } else {
+            Some(distance)
+        }
+    }
+
+    /// Returns the identifiers of every sphere touching the ball with the
+    /// given center and radius, in the order of the objects
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - center of the ball in world space
+    /// * `radius` - radius of the ball
+    pub fn sphere_overlap(&self, center: Tuple, radius: f64) -> Vec<ShapeId> {
+        self.spheres()
+            .filter(|(_, object)| {
+                contains(object, center)
+                    || (closest_on_sphere(object, center) - center).magnitude() <= radius
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns the identifier of every sphere in the world, together with
+    /// the sphere
+    fn spheres(&self) -> impl Iterator<Item = (ShapeId, &Sphere)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, object)| Some((ShapeId(i), object.downcast_ref::<Sphere>()?)))
+    }
+}
+
+/// Returns whether the point lies inside the sphere
+fn contains(object: &Sphere, point: Tuple) -> bool {
+    let local = &object.transform.inverse() * &point;
+
+    Tuple::vector(local.x, local.y, local.z).magnitude() < 1.0
+}
+
+/// Returns the point on the surface of the sphere closest to the given point
+///
+/// The point is projected onto the unit sphere in object space, which is
+/// exact for rotations, translations and uniform scaling. Other
+/// transformations turn the sphere into an ellipsoid, where the projection
+/// is refined by gradient descent over the unit sphere.
+fn closest_on_sphere(object: &Sphere, point: Tuple) -> Tuple {
+    let m = &object.transform;
+    let local = &m.inverse() * &point;
+    let mut u = Tuple::vector(local.x, local.y, local.z);
+
+    u = if u.magnitude() < EPSILON {
+        Tuple::vector(0.0, 1.0, 0.0)
+    } else {
+        u.normalize()
+    };
+
+    if !is_conformal(m) {
+        // the squared Frobenius norm bounds the largest squared stretch of
+        // the transformation, keeping each descent step stable
+        let stretch: f64 = (0..3)
+            .flat_map(|i| (0..3).map(move |j| m[(i, j)] * m[(i, j)]))
+            .sum();
+        let transpose = m.transpose();
+
+        for _ in 0..MAX_REFINEMENTS {
+            let offset = surface_point(m, u) - point;
+            let mut gradient = &transpose * &offset;
+            gradient.w = 0.0;
+            let tangent = gradient - u * Tuple::dot(&gradient, &u);
+
+            if tangent.magnitude() < EPSILON * EPSILON {
+                break;
+            }
+
+            u = (u - tangent / stretch).normalize();
+        }
+    }
+
+    surface_point(m, u)
+}
+
+/// Returns the world space point of the sphere at the given direction from
+/// its center in object space
+fn surface_point(m: &Matrix, u: Tuple) -> Tuple {
+    m * &Tuple::point(u.x, u.y, u.z)
+}
+
+/// Returns whether the transformation preserves angles, i.e. scales every
+/// direction equally
+fn is_conformal(m: &Matrix) -> bool {
+    let axes = [
+        m * &Tuple::vector(1.0, 0.0, 0.0),
+        m * &Tuple::vector(0.0, 1.0, 0.0),
+        m * &Tuple::vector(0.0, 0.0, 1.0),
+    ];
+    let scale = axes[0].magnitude();
+
+    axes.iter().all(|a| (a.magnitude() - scale).abs() < EPSILON)
+        && (0..3).all(|i| Tuple::dot(&axes[i], &axes[(i + 1) % 3]).abs() < EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{rotation_z, scaling, translation, Angle, Cylinder};
+    use alloc::vec;
+
+    #[test]
+    fn closest_point_should_pick_nearest_object() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[1].set_transformation(&translation(5.0, 0.0, 0.0) * &scaling(2.0, 2.0, 2.0));
+
+        assert_eq!(
+            None,
+            World::new().closest_point(Tuple::point(0.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            Some((ShapeId(1), Tuple::point(3.0, 0.0, 0.0))),
+            world.closest_point(Tuple::point(2.5, 0.0, 0.0))
+        );
+        assert_eq!(
+            Some(-1.0),
+            world.distance_to_surface(Tuple::point(5.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn closest_point_should_handle_stretched_spheres() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[0]
+            .set_transformation(&rotation_z(Angle::from_degrees(30.0)) * &scaling(4.0, 1.0, 1.0));
+        let point = Tuple::point(0.0, 3.0, 0.0);
+
+        let (_, closest) = world.closest_point(point).unwrap();
+        let normal = world.objects[0].normal_at(closest);
+        let naive = world.objects[0].transformation() * &Tuple::point(0.0, 1.0, 0.0);
+
+        // the closest point lies on the surface, straight below the point
+        let local = &world.objects[0].transformation().inverse() * &closest;
+        assert!((Tuple::vector(local.x, local.y, local.z).magnitude() - 1.0).abs() < EPSILON);
+        assert!(((point - closest).normalize() - normal).magnitude() < 1e-4);
+        assert!((point - closest).magnitude() < (point - naive).magnitude());
+    }
+
+    #[test]
+    fn sphere_overlap_should_include_touched_and_enclosing_objects() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[0].set_transformation(scaling(10.0, 10.0, 10.0));
+        world.objects[1].set_transformation(translation(3.0, 0.0, 0.0));
+
+        assert_eq!(
+            vec![ShapeId(0), ShapeId(1)],
+            world.sphere_overlap(Tuple::point(0.0, 0.0, 0.0), 2.0)
+        );
+        assert_eq!(
+            vec![ShapeId(0)],
+            world.sphere_overlap(Tuple::point(0.0, 0.0, 0.0), 1.5)
+        );
+        assert!(world
+            .sphere_overlap(Tuple::point(20.0, 0.0, 0.0), 5.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn queries_should_skip_other_shapes() {
+        let mut world = World::new();
+        world.objects.push(Box::new(Cylinder::new()));
+        world.objects.push(Box::new(Sphere::new()));
+        world.objects[1].set_transformation(translation(5.0, 0.0, 0.0));
+
+        assert_eq!(
+            Some((ShapeId(1), Tuple::point(4.0, 0.0, 0.0))),
+            world.closest_point(Tuple::point(0.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            vec![ShapeId(1)],
+            world.sphere_overlap(Tuple::point(0.0, 0.0, 0.0), 5.0)
+        );
+    }
+}
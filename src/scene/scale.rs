@@ -0,0 +1,197 @@
+use super::{Camera, RenderSettings, World, SHADOW_BIAS};
+use crate::math::{Angle, EPSILON};
+
+/// Distance in meters the default near clipping plane of a camera lies at
+const NEAR_METERS: f64 = 0.001;
+
+/// Physical size of one world space unit, used to derive tolerances and
+/// camera defaults suited to scenes modelled at very different scales
+///
+/// The defaults used throughout the crate assume one unit is one meter. A
+/// scene modelled in millimeters places its surfaces a thousand units apart
+/// where a meter scene would use one, so offsets such as the shadow bias
+/// have to grow by the same factor to stay above the rounding error.
+///
+/// # Example
+///
+/// ```
+/// use libray::scene::{SceneScale, World};
+///
+/// let scale = SceneScale::CENTIMETERS;
+/// let mut world = World::new();
+/// scale.apply(&mut world);
+///
+/// assert_eq!(250.0, scale.to_units(2.5));
+/// assert_eq!(100.0 * SceneScale::METERS.shadow_bias(), scale.shadow_bias());
+/// assert_eq!(scale.intersection_epsilon(), world.intersection_epsilon);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SceneScale {
+    /// Length of one world space unit in meters
+    pub meters_per_unit: f64,
+}
+
+impl SceneScale {
+    /// One unit is one kilometer
+    pub const KILOMETERS: SceneScale = SceneScale {
+        meters_per_unit: 1000.0,
+    };
+    /// One unit is one meter, the scale every default assumes
+    pub const METERS: SceneScale = SceneScale {
+        meters_per_unit: 1.0,
+    };
+    /// One unit is one centimeter
+    pub const CENTIMETERS: SceneScale = SceneScale {
+        meters_per_unit: 0.01,
+    };
+    /// One unit is one millimeter
+    pub const MILLIMETERS: SceneScale = SceneScale {
+        meters_per_unit: 0.001,
+    };
+
+    /// Returns a new scale
+    ///
+    /// # Arguments
+    ///
+    /// * `meters_per_unit` - length of one world space unit in meters
+    pub fn new(meters_per_unit: f64) -> SceneScale {
+        SceneScale { meters_per_unit }
+    }
+
+    /// Returns the given length converted from meters to world space units
+    ///
+    /// # Arguments
+    ///
+    /// * `meters` - length in meters
+    pub fn to_units(&self, meters: f64) -> f64 {
+        meters / self.meters_per_unit
+    }
+
+    /// Returns the given length converted from world space units to meters
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - length in world space units
+    pub fn to_meters(&self, units: f64) -> f64 {
+        units * self.meters_per_unit
+    }
+
+    /// Returns the shadow bias suited to the scale, the default bias of a
+    /// meter scene converted to world space units
+    pub fn shadow_bias(&self) -> f64 {
+        self.to_units(SHADOW_BIAS)
+    }
+
+    /// Returns the intersection tolerance suited to the scale, the default
+    /// tolerance of a meter scene converted to world space units, see
+    /// `World::intersection_epsilon`
+    ///
+    /// The tolerance is a squared length, so it grows with the square of
+    /// the number of units per meter.
+    pub fn intersection_epsilon(&self) -> f64 {
+        let units = self.to_units(1.0);
+
+        EPSILON * units * units
+    }
+
+    /// Sets the intersection tolerance of the given world to the one suited
+    /// to the scale
+    ///
+    /// Only the world is changed. The other tolerances and defaults are
+    /// returned by the methods of the scale and take effect where they are
+    /// used.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world modelled at this scale
+    pub fn apply(&self, world: &mut World) {
+        world.intersection_epsilon = self.intersection_epsilon();
+    }
+
+    /// Returns the default render settings with the shadow bias suited to
+    /// the scale
+    pub fn render_settings(&self) -> RenderSettings {
+        RenderSettings {
+            shadow_bias: self.shadow_bias(),
+            ..RenderSettings::new()
+        }
+    }
+
+    /// Returns the inverse square falloff of light after travelling the
+    /// given distance, measured in meters so that light intensities keep
+    /// their meaning across scales
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - distance travelled in world space units
+    pub fn falloff(&self, distance: f64) -> f64 {
+        let meters = self.to_meters(distance);
+
+        1.0 / (meters * meters)
+    }
+
+    /// Returns a new camera whose near clipping plane lies one millimeter in
+    /// front of it, keeping surfaces touching the lens from being rendered
+    /// with unstable precision
+    ///
+    /// # Arguments
+    ///
+    /// * `hsize` - horizontal size of the canvas in pixels
+    /// * `vsize` - vertical size of the canvas in pixels
+    /// * `field_of_view` - angle describing how much the camera can see, a
+    ///   bare `f64` being radians
+    pub fn camera(&self, hsize: usize, vsize: usize, field_of_view: impl Into<Angle>) -> Camera {
+        let mut camera = Camera::new(hsize, vsize, field_of_view);
+        camera.near = Some(self.to_units(NEAR_METERS));
+
+        camera
+    }
+}
+
+impl Default for SceneScale {
+    fn default() -> SceneScale {
+        SceneScale::METERS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_between_units_and_meters() {
+        let mm = SceneScale::MILLIMETERS;
+
+        assert!((mm.to_units(1.5) - 1500.0).abs() < EPSILON);
+        assert!((mm.to_meters(250.0) - 0.25).abs() < EPSILON);
+        assert_eq!(SceneScale::METERS, SceneScale::default());
+        assert_eq!(SceneScale::KILOMETERS, SceneScale::new(1000.0));
+    }
+
+    #[test]
+    fn should_scale_defaults_with_unit_size() {
+        let cm = SceneScale::CENTIMETERS;
+
+        assert_eq!(SHADOW_BIAS, SceneScale::METERS.shadow_bias());
+        assert!((cm.render_settings().shadow_bias - SHADOW_BIAS * 100.0).abs() < EPSILON);
+        assert!((cm.camera(10, 10, 1.0).near.unwrap() - 0.1).abs() < EPSILON);
+        assert_eq!(EPSILON, SceneScale::METERS.intersection_epsilon());
+        assert!((SceneScale::MILLIMETERS.intersection_epsilon() - EPSILON * 1.0e6).abs() < 1e-9);
+        assert!((cm.falloff(200.0) - 0.25).abs() < EPSILON);
+        assert!((SceneScale::METERS.falloff(2.0) - 0.25).abs() < EPSILON);
+    }
+
+    #[test]
+    fn apply_should_only_set_the_tolerance_of_the_given_world() {
+        let mut mm = World::new();
+        let meters = World::new();
+        SceneScale::MILLIMETERS.apply(&mut mm);
+
+        assert_eq!(
+            SceneScale::MILLIMETERS.intersection_epsilon(),
+            mm.intersection_epsilon
+        );
+        assert_eq!(EPSILON, meters.intersection_epsilon);
+    }
+}
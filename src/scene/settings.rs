@@ -0,0 +1,181 @@
+/// Offset along the surface normal applied to shaded points by default, used
+/// to avoid shadow acne
+pub(crate) const SHADOW_BIAS: f64 = 0.00001;
+
+/// Strategy used to compute the color seen along a camera ray
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Integrator {
+    /// Direct lighting from every light source with hard shadows
+    Whitted,
+    /// Direct lighting plus a cheap approximation of indirect light: one
+    /// bounce of diffuse light gathered with `n * n` cosine-distributed rays
+    /// from every hit
+    SingleBounce(usize),
+    /// Debug mode coloring each hit by its surface normal, mapping every
+    /// axis from `-1.0..=1.0` to `0.0..=1.0`
+    Normals,
+    /// Debug mode coloring each hit on a heat map by its distance from the
+    /// ray origin, reaching red at the given distance
+    Depth(f64),
+    /// Debug mode coloring each pixel on a heat map by the number of
+    /// intersections along the ray, reaching red at the given count
+    IntersectionCount(usize),
+}
+
+/// Settings controlling how a world is rendered by a camera
+///
+/// # Example
+///
+/// ```
+/// use libray::scene::RenderSettings;
+///
+/// let settings = RenderSettings {
+///     samples: 2,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(4, settings.samples_per_pixel());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderSettings {
+    /// Offset along the surface normal applied to shaded points, used to
+    /// avoid shadow acne
+    pub shadow_bias: f64,
+    /// Number of antialiasing samples along each axis of a pixel, a value of
+    /// `n` traces `n * n` evenly spaced rays per pixel
+    pub samples: usize,
+    /// Number of threads rendering in parallel, `0` uses every available
+    /// core
+    pub threads: usize,
+    /// Whether every render thread yields the processor after each row it
+    /// finishes, letting other threads of the application run in between
+    ///
+    /// The operating system priority of the render threads is left as it
+    /// is, so they still compete with other work on equal terms within a
+    /// row.
+    pub yield_between_rows: bool,
+    /// Whether a thread count of `0` leaves one core free for the rest of
+    /// the application instead of using every available core
+    pub reserve_core: bool,
+    /// Strategy used to compute the color seen along a ray
+    pub integrator: Integrator,
+    /// Largest component any single ray sample may reach, brighter samples
+    /// are scaled down keeping their hue, which suppresses fireflies
+    pub max_radiance: Option<f64>,
+    /// Largest component the contribution of a single light source may
+    /// reach at a shaded point
+    pub max_contribution: Option<f64>,
+    /// Number of lights shaded per hit, chosen by their power and distance,
+    /// `None` to shade every light
+    pub light_samples: Option<usize>,
+}
+
+impl RenderSettings {
+    /// Returns the default settings: a single sample per pixel on one thread
+    /// with direct lighting
+    pub fn new() -> RenderSettings {
+        RenderSettings {
+            shadow_bias: SHADOW_BIAS,
+            samples: 1,
+            threads: 1,
+            yield_between_rows: false,
+            reserve_core: false,
+            integrator: Integrator::Whitted,
+            max_radiance: None,
+            max_contribution: None,
+            light_samples: None,
+        }
+    }
+
+    /// Returns the total number of rays traced per pixel
+    pub fn samples_per_pixel(&self) -> usize {
+        self.samples.max(1).pow(2)
+    }
+
+    /// Returns the number of threads to render with, resolving `0` to the
+    /// available parallelism of the machine, less one core if
+    /// `reserve_core` is set
+    ///
+    /// Without the `std` feature every render runs on the calling thread.
+    pub fn thread_count(&self) -> usize {
+        match self.threads {
+            #[cfg(feature = "std")]
+            0 => {
+                let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+                if self.reserve_core {
+                    cores.saturating_sub(1).max(1)
+                } else {
+                    cores
+                }
+            }
+            #[cfg(not(feature = "std"))]
+            _ => 1,
+            #[cfg(feature = "std")]
+            n => n,
+        }
+    }
+}
+
+impl Default for RenderSettings {
+    fn default() -> RenderSettings {
+        RenderSettings::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_contain_correct_defaults() {
+        let s = RenderSettings::default();
+
+        assert_eq!(SHADOW_BIAS, s.shadow_bias);
+        assert_eq!(1, s.samples);
+        assert_eq!(1, s.threads);
+        assert!(!s.yield_between_rows);
+        assert!(!s.reserve_core);
+        assert_eq!(Integrator::Whitted, s.integrator);
+        assert_eq!(None, s.max_radiance);
+        assert_eq!(None, s.max_contribution);
+        assert_eq!(None, s.light_samples);
+    }
+
+    #[test]
+    fn should_resolve_sample_and_thread_counts() {
+        let mut s = RenderSettings::new();
+        s.samples = 0;
+        s.threads = 0;
+
+        assert_eq!(1, s.samples_per_pixel());
+        assert!(s.thread_count() >= 1);
+
+        s.samples = 3;
+        s.threads = 4;
+
+        assert_eq!(9, s.samples_per_pixel());
+        #[cfg(feature = "std")]
+        assert_eq!(4, s.thread_count());
+        #[cfg(not(feature = "std"))]
+        assert_eq!(1, s.thread_count());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reserve_core_should_leave_a_core_free() {
+        let mut s = RenderSettings::new();
+        s.threads = 0;
+        let cores = s.thread_count();
+        s.reserve_core = true;
+
+        assert_eq!(cores.saturating_sub(1).max(1), s.thread_count());
+
+        s.threads = 4;
+
+        assert_eq!(4, s.thread_count());
+    }
+}
@@ -0,0 +1,118 @@
+use super::{Camera, RenderSettings, World};
+use crate::graphics::Canvas;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::math::{rotation_y, translation};
+
+/// Pair of cameras rendering the views of a left and right eye placed around
+/// a central camera
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StereoCamera {
+    /// Camera positioned between both eyes
+    pub camera: Camera,
+    /// Distance between the eyes in world space units
+    pub interocular: f64,
+    /// Distance in front of the camera at which both eyes look at the same
+    /// point, infinite for parallel eyes
+    pub convergence: f64,
+}
+
+impl StereoCamera {
+    /// Returns a new stereo camera built around the given camera
+    ///
+    /// # Arguments
+    ///
+    /// * `camera` - camera positioned between both eyes
+    /// * `interocular` - distance between the eyes
+    /// * `convergence` - distance at which both eyes converge
+    pub fn new(camera: Camera, interocular: f64, convergence: f64) -> StereoCamera {
+        StereoCamera {
+            camera,
+            interocular,
+            convergence,
+        }
+    }
+
+    /// Returns the cameras of the left and right eye, each moved half the
+    /// interocular distance sideways and turned towards the convergence point
+    pub fn eyes(&self) -> (Camera, Camera) {
+        let offset = self.interocular / 2.0;
+        let angle = (offset / self.convergence).atan();
+        let mut left = self.camera.clone();
+        let mut right = self.camera.clone();
+
+        left.transform(
+            rotation_y(-angle) * translation(-offset, 0.0, 0.0) * self.camera.transform.clone(),
+        );
+        right.transform(
+            rotation_y(angle) * translation(offset, 0.0, 0.0) * self.camera.transform.clone(),
+        );
+
+        (left, right)
+    }
+
+    /// Renders the given world from both eyes, returning the left and right
+    /// image
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - world to render
+    /// * `settings` - settings controlling sampling, threading and shading
+    pub fn render(&self, world: &World, settings: &RenderSettings) -> (Canvas, Canvas) {
+        let (left, right) = self.eyes();
+
+        (left.render(world, settings), right.render(world, settings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+    use crate::math::{view_transform, Point2, Tuple};
+    use core::f64::consts::PI;
+
+    fn stereo(convergence: f64) -> StereoCamera {
+        let mut camera = Camera::new(101, 51, PI / 2.0);
+        camera.transform(view_transform(
+            Tuple::point(1.0, 2.0, -5.0),
+            Tuple::point(1.0, 2.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+        ));
+
+        StereoCamera::new(camera, 0.5, convergence)
+    }
+
+    #[test]
+    fn eyes_should_be_interocular_distance_apart() {
+        let (left, right) = stereo(5.0).eyes();
+        let origin = Tuple::point(0.0, 0.0, 0.0);
+        let l = &left.transform.inverse() * &origin;
+        let r = &right.transform.inverse() * &origin;
+
+        assert!(((l - r).magnitude() - 0.5).abs() < EPSILON);
+        assert!(l.x < r.x);
+    }
+
+    #[test]
+    fn eyes_should_see_convergence_point_at_the_center() {
+        let (left, right) = stereo(5.0).eyes();
+        let target = Tuple::point(1.0, 2.0, 0.0);
+
+        for eye in [left, right] {
+            assert_eq!(Some(Point2::new(50.5, 25.5)), eye.project(target));
+        }
+    }
+
+    #[test]
+    fn eyes_should_be_parallel_without_convergence() {
+        let (left, right) = stereo(f64::INFINITY).eyes();
+        let far = Tuple::point(1.0, 2.0, 1.0e9);
+
+        let lx = left.project(far).unwrap().x;
+        let rx = right.project(far).unwrap().x;
+
+        assert!((lx - rx).abs() < 0.001);
+    }
+}
@@ -0,0 +1,217 @@
+use super::{RenderSettings, ShapeId, World};
+use crate::graphics::Color;
+use crate::math::{Intersection, Ray, Tuple};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Single step recorded while tracing a ray through a world
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum TraceEvent {
+    /// The ray intersected an object at the given time
+    Intersection {
+        /// Object which was intersected
+        object: ShapeId,
+        /// Time of the intersection
+        t: f64,
+    },
+    /// The intersection closest to the ray origin was selected for shading
+    Hit {
+        /// Object which was hit
+        object: ShapeId,
+        /// Time of the hit
+        t: f64,
+        /// Point in world space where the hit occured
+        point: Tuple,
+        /// Normal vector of the surface, facing the eye
+        normal: Tuple,
+        /// Whether the hit occured inside the object
+        inside: bool,
+    },
+    /// The ray did not hit anything in front of its origin
+    Miss,
+    /// The shaded point was tested for visibility of a light source
+    ShadowTest {
+        /// Index of the light source in `World::lights`
+        light: usize,
+        /// Whether the light source is blocked, fully or partially, by another
+        /// object
+        shadowed: bool,
+        /// Color the light source contributes to the shaded point
+        contribution: Color,
+    },
+}
+
+/// Structured log of every step taken while tracing a single ray
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayTrace {
+    /// Ray which was traced
+    pub ray: Ray,
+    /// Steps taken while tracing the ray, in order
+    pub events: Vec<TraceEvent>,
+    /// Color returned for the ray
+    pub color: Color,
+}
+
+impl World {
+    /// Traces the given ray through the world, recording every intersection,
+    /// shadow test and light contribution along the way
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to trace through the world
+    /// * `settings` - settings to render with
+    pub fn trace(&self, ray: &Ray, settings: &RenderSettings) -> RayTrace {
+        let intersections = self.intersect(ray);
+        let mut events: Vec<TraceEvent> = intersections
+            .iter()
+            .filter_map(|i| {
+                self.id_of(i.object)
+                    .map(|object| TraceEvent::Intersection { object, t: i.t })
+            })
+            .collect();
+
+        match Intersection::hit(&intersections) {
+            Some(hit) => {
+                let comps =
+                    hit.prepare_computations_with_bias(ray, &intersections, settings.shadow_bias);
+
+                if let Some(object) = self.id_of(comps.object) {
+                    events.push(TraceEvent::Hit {
+                        object,
+                        t: comps.t,
+                        point: comps.point,
+                        normal: comps.normalv,
+                        inside: comps.inside,
+                    });
+                }
+
+                let linking = &comps.object.light_linking();
+
+                for (index, light) in self.lights.iter().enumerate() {
+                    if !linking.is_lit_by(light) {
+                        continue;
+                    }
+
+                    let visibility = self.light_visibility(light, comps.over_point);
+
+                    events.push(TraceEvent::ShadowTest {
+                        light: index,
+                        shadowed: visibility != Color::new(1.0, 1.0, 1.0),
+                        contribution: comps.object.material().lighting_with_visibility(
+                            comps.object,
+                            light,
+                            comps.over_point,
+                            comps.eyev,
+                            comps.normalv,
+                            visibility,
+                        ),
+                    });
+                }
+            }
+            None => events.push(TraceEvent::Miss),
+        }
+
+        RayTrace {
+            ray: *ray,
+            events,
+            color: self.color_at_with(ray, settings),
+        }
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceEvent::Intersection { object, t } => {
+                write!(f, "intersection object={} t={}", object.0, t)
+            }
+            TraceEvent::Hit {
+                object,
+                t,
+                point,
+                normal,
+                inside,
+            } => write!(
+                f,
+                "hit object={} t={} point={} normal={} inside={}",
+                object.0, t, point, normal, inside
+            ),
+            TraceEvent::Miss => write!(f, "miss"),
+            TraceEvent::ShadowTest {
+                light,
+                shadowed,
+                contribution,
+            } => write!(
+                f,
+                "shadow light={} shadowed={} contribution={}",
+                light, shadowed, contribution
+            ),
+        }
+    }
+}
+
+impl fmt::Display for RayTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{}", event)?;
+        }
+
+        write!(f, "color {}", self.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_every_step_of_a_hit() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let trace = w.trace(&r, &RenderSettings::default());
+
+        assert_eq!(6, trace.events.len());
+        assert_eq!(
+            TraceEvent::Intersection {
+                object: ShapeId(0),
+                t: 4.0
+            },
+            trace.events[0]
+        );
+        assert_eq!(
+            TraceEvent::Hit {
+                object: ShapeId(0),
+                t: 4.0,
+                point: Tuple::point(0.0, 0.0, -1.0),
+                normal: Tuple::vector(0.0, 0.0, -1.0),
+                inside: false,
+            },
+            trace.events[4]
+        );
+        assert_eq!(
+            TraceEvent::ShadowTest {
+                light: 0,
+                shadowed: false,
+                contribution: w.color_at(&r),
+            },
+            trace.events[5]
+        );
+        assert_eq!(w.color_at(&r), trace.color);
+    }
+
+    #[test]
+    fn should_record_a_miss() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+        let trace = w.trace(&r, &RenderSettings::default());
+
+        assert_eq!(vec![TraceEvent::Miss], trace.events);
+        assert_eq!(
+            "miss\ncolor color(0.00000, 0.00000, 0.00000)",
+            trace.to_string()
+        );
+    }
+}
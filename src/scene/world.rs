@@ -0,0 +1,1181 @@
+use super::{Computations, Integrator, RenderSettings, SHADOW_BIAS};
+use crate::graphics::{Color, Light};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+#[cfg(test)]
+use crate::math::Sphere;
+use crate::math::{Intersection, Interval, QuantizedTuple, Ray, Shape, Tuple, EPSILON};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::f64::consts::PI;
+use core::hash::{Hash, Hasher};
+
+/// Number of times light is followed from mirror to mirror before the
+/// reflections are cut off, keeping facing mirrors from recursing forever
+const MAX_REFLECTION_DEPTH: usize = 5;
+
+/// Collection of all objects and light sources in a scene
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct World {
+    /// Objects contained in the world, of any shape
+    ///
+    /// Only spheres, cylinders and planes can be serialized.
+    #[cfg_attr(feature = "serde", serde(with = "crate::math::tagged_shapes"))]
+    pub objects: Vec<Box<dyn Shape>>,
+    /// Light sources illuminating the world
+    pub lights: Vec<Light>,
+    /// Light reaching every surface evenly from all directions, added to
+    /// the surface color regardless of shadows and light linking
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ambient: Color,
+    /// Tolerance within which a ray missing a curved surface is still
+    /// treated as grazing it, given to every ray cast into the world
+    ///
+    /// Defaults to `math::EPSILON`, suited to scenes measured in meters; see
+    /// `SceneScale::intersection_epsilon` for other units.
+    #[cfg_attr(feature = "serde", serde(default = "default_intersection_epsilon"))]
+    pub intersection_epsilon: f64,
+}
+
+#[cfg(feature = "serde")]
+fn default_intersection_epsilon() -> f64 {
+    EPSILON
+}
+
+/// Identifier of an object in a world, i.e. its index in `World::objects`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeId(pub usize);
+
+impl World {
+    /// Returns a new world without any objects, light sources or ambient
+    /// light
+    pub fn new() -> World {
+        World {
+            objects: vec![],
+            lights: vec![],
+            ambient: Color::default(),
+            intersection_epsilon: EPSILON,
+        }
+    }
+
+    /// Returns the object with the given identifier, if it exists
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object
+    pub fn object(&self, id: ShapeId) -> Option<&dyn Shape> {
+        self.objects.get(id.0).map(|object| &**object)
+    }
+
+    /// Returns a mutable reference to the object with the given identifier,
+    /// if it exists
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - identifier of the object
+    pub fn object_mut(&mut self, id: ShapeId) -> Option<&mut dyn Shape> {
+        match self.objects.get_mut(id.0) {
+            Some(object) => Some(&mut **object),
+            None => None,
+        }
+    }
+
+    /// Returns the identifier of the given object if it is part of the world
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - reference to an object stored in the world
+    pub fn id_of(&self, object: &dyn Shape) -> Option<ShapeId> {
+        self.objects
+            .iter()
+            .position(|o| core::ptr::addr_eq(&**o, object))
+            .map(ShapeId)
+    }
+
+    /// Returns all intersections of the given ray with the objects in the
+    /// world, sorted by time, within the intersection tolerance of the world
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to intersect the world with
+    pub fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.with_tolerance(self.intersection_epsilon);
+        let mut intersections: Vec<Intersection> = self
+            .objects
+            .iter()
+            .flat_map(|object| ray.intersect(&**object))
+            .collect();
+
+        intersections.sort_by(|a, b| a.t.total_cmp(&b.t));
+        intersections
+    }
+
+    /// Returns the color at the precomputed intersection, summed over all
+    /// light sources
+    ///
+    /// # Arguments
+    ///
+    /// * `comps` - precomputed state of the intersection to shade
+    pub fn shade_hit(&self, comps: &Computations) -> Color {
+        self.shade_hit_with(comps, &RenderSettings::default())
+    }
+
+    /// Returns the color at the precomputed intersection using the given
+    /// render settings, summed over all light sources linked to the object
+    /// on top of the ambient light of the world, the light its material
+    /// emits and the light it reflects
+    ///
+    /// When `settings.light_samples` is set and fewer than the number of
+    /// linked lights, only that many lights are shaded, chosen with a
+    /// probability proportional to their power over their squared distance
+    /// and weighted so that the expected color is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `comps` - precomputed state of the intersection to shade
+    /// * `settings` - settings to render with
+    pub fn shade_hit_with(&self, comps: &Computations, settings: &RenderSettings) -> Color {
+        self.shade_hit_at_depth(comps, settings, MAX_REFLECTION_DEPTH)
+    }
+
+    fn shade_hit_at_depth(
+        &self,
+        comps: &Computations,
+        settings: &RenderSettings,
+        depth: usize,
+    ) -> Color {
+        let linking = &comps.object.light_linking();
+        let lights: Vec<&Light> = self
+            .lights
+            .iter()
+            .filter(|light| linking.is_lit_by(light))
+            .collect();
+        let shade = |light: &Light| {
+            let visibility = self.light_visibility(light, comps.over_point);
+            let contribution = comps.object.material().lighting_with_visibility(
+                comps.object,
+                light,
+                comps.over_point,
+                comps.eyev,
+                comps.normalv,
+                visibility,
+            );
+
+            match settings.max_contribution {
+                Some(limit) => contribution.limit(limit),
+                None => contribution,
+            }
+        };
+        let material = comps.object.material();
+        let unlit =
+            material.emission + material.color_at(comps.object, comps.over_point) * self.ambient;
+
+        let direct = match settings.light_samples {
+            Some(count) if count < lights.len() => select_lights(&lights, comps.over_point, count)
+                .into_iter()
+                .fold(unlit, |color, (light, weight)| {
+                    color + shade(light) * weight
+                }),
+            _ => lights
+                .into_iter()
+                .fold(unlit, |color, light| color + shade(light)),
+        };
+
+        direct + self.reflected_color(comps, settings, depth)
+    }
+
+    /// Returns the color reflected at the hit, seen along the reflection
+    /// vector and scaled by the reflectivity of the material, black once
+    /// `depth` further reflections have been followed
+    fn reflected_color(
+        &self,
+        comps: &Computations,
+        settings: &RenderSettings,
+        depth: usize,
+    ) -> Color {
+        let reflective = comps.object.material().reflective;
+
+        if reflective <= 0.0 || depth == 0 {
+            return Color::default();
+        }
+
+        match self.cast(
+            &Ray::new(comps.over_point, comps.reflectv),
+            settings.shadow_bias,
+        ) {
+            Some(hit) if !hit.object.is_holdout() => {
+                self.shade_hit_at_depth(&hit, settings, depth - 1) * reflective
+            }
+            _ => Color::default(),
+        }
+    }
+
+    /// Returns the color seen by the given ray, black if nothing is hit
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to trace through the world
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_with(ray, &RenderSettings::default())
+    }
+
+    /// Returns the color seen by the given ray using the given render
+    /// settings, black if nothing is hit
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to trace through the world
+    /// * `settings` - settings to render with
+    pub fn color_at_with(&self, ray: &Ray, settings: &RenderSettings) -> Color {
+        self.color_within(ray, settings, Interval::FORWARD)
+    }
+
+    /// Returns the color seen by the given ray using the given render
+    /// settings, ignoring objects hit outside of the given range of times,
+    /// black if nothing is hit within it
+    ///
+    /// Only the objects seen directly along the ray are clipped; shadows and
+    /// indirect light still take the whole world into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to trace through the world
+    /// * `settings` - settings to render with
+    /// * `range` - times at which intersections are accepted
+    pub fn color_within(&self, ray: &Ray, settings: &RenderSettings, range: Interval) -> Color {
+        let bias = settings.shadow_bias;
+        let color = match settings.integrator {
+            Integrator::Whitted => match self.cast_within(ray, bias, range) {
+                Some(comps) if comps.object.is_holdout() => Color::default(),
+                Some(comps) => self.shade_hit_with(&comps, settings),
+                None => Color::default(),
+            },
+            Integrator::SingleBounce(samples) => match self.cast_within(ray, bias, range) {
+                Some(comps) if comps.object.is_holdout() => Color::default(),
+                Some(comps) => {
+                    self.shade_hit_with(&comps, settings) + self.indirect(&comps, samples, settings)
+                }
+                None => Color::default(),
+            },
+            Integrator::Normals => match self.cast_within(ray, bias, range) {
+                Some(comps) => {
+                    let n = comps.normalv;
+                    Color::new(n.x + 1.0, n.y + 1.0, n.z + 1.0) * 0.5
+                }
+                None => Color::default(),
+            },
+            Integrator::Depth(max) => match self.cast_within(ray, bias, range) {
+                Some(comps) => Color::heat(comps.t / max),
+                None => Color::default(),
+            },
+            Integrator::IntersectionCount(max) => match self
+                .ray_cast_all(ray)
+                .iter()
+                .filter(|i| range.contains(i.t))
+                .count()
+            {
+                0 => Color::default(),
+                count => Color::heat(count as f64 / max.max(1) as f64),
+            },
+        };
+
+        match settings.max_radiance {
+            Some(limit) => color.limit(limit),
+            None => color,
+        }
+    }
+
+    /// Returns 1.0 if the given ray hits an object which is not a holdout,
+    /// 0.0 if it hits a holdout or nothing at all
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to trace through the world
+    /// * `settings` - settings to render with
+    pub fn coverage_at(&self, ray: &Ray, settings: &RenderSettings) -> f64 {
+        self.coverage_within(ray, settings, Interval::FORWARD)
+    }
+
+    pub(crate) fn coverage_within(
+        &self,
+        ray: &Ray,
+        settings: &RenderSettings,
+        range: Interval,
+    ) -> f64 {
+        match self.cast_within(ray, settings.shadow_bias, range) {
+            Some(comps) if !comps.object.is_holdout() => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the diffuse light reflected at the hit after gathering the
+    /// directly lit color seen along `n * n` stratified, cosine-distributed
+    /// rays around the normal
+    fn indirect(&self, comps: &Computations, samples: usize, settings: &RenderSettings) -> Color {
+        let n = samples.max(1);
+        let normal = comps.normalv;
+        let helper = if normal.x.abs() > 0.9 {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else {
+            Tuple::vector(1.0, 0.0, 0.0)
+        };
+        let tangent = Tuple::cross(&helper, &normal).normalize();
+        let bitangent = Tuple::cross(&normal, &tangent);
+        let mut gathered = Color::default();
+
+        for i in 0..n {
+            for j in 0..n {
+                let u = (i as f64 + 0.5) / n as f64;
+                let phi = 2.0 * PI * (j as f64 + 0.5) / n as f64;
+                let r = u.sqrt();
+                let direction = tangent * (r * phi.cos())
+                    + bitangent * (r * phi.sin())
+                    + normal * (1.0 - u).sqrt();
+
+                if let Some(hit) =
+                    self.cast(&Ray::new(comps.over_point, direction), settings.shadow_bias)
+                {
+                    gathered = gathered + self.shade_hit_with(&hit, settings);
+                }
+            }
+        }
+
+        let material = &comps.object.material();
+
+        material.color_at(comps.object, comps.over_point)
+            * (gathered / (n * n) as f64)
+            * material.diffuse
+    }
+
+    /// Returns true if objects between the given point and light block any
+    /// of its light
+    ///
+    /// Transparent objects are handled as in shading, see `transmittance`,
+    /// so that a point behind glass counts as shadowed while one behind a
+    /// fully transparent object does not.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - light source to test against
+    /// * `point` - point to test for shadow
+    pub fn is_shadowed(&self, light: &Light, point: Tuple) -> bool {
+        let v = light.position - point;
+        let distance = v.magnitude();
+
+        self.transmittance(&Ray::new(point, v.normalize()), distance) < 1.0
+    }
+
+    /// Returns the fraction of the light reaching the given point in each
+    /// channel, blending towards the shadow color of the light as more of its
+    /// shadow rays are blocked
+    ///
+    /// Shadow rays passing through transparent objects are only partly
+    /// blocked, see `transmittance`.
+    ///
+    /// Lights with a softness cast their shadow rays towards points spread
+    /// evenly over a sphere of that radius around their position, softening
+    /// the edges of shadows even though the light itself has no size.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - light source to test against
+    /// * `point` - point to test for shadow
+    pub fn light_visibility(&self, light: &Light, point: Tuple) -> Color {
+        let samples = if light.softness > 0.0 {
+            light.shadow_samples.max(1)
+        } else {
+            1
+        };
+        let golden_angle = PI * (3.0 - 5.0_f64.sqrt());
+        let blocked: f64 = (0..samples)
+            .map(|i| {
+                let target = if samples == 1 {
+                    light.position
+                } else {
+                    let y = 1.0 - 2.0 * (i as f64 + 0.5) / samples as f64;
+                    let r = (1.0 - y * y).sqrt();
+                    let theta = golden_angle * i as f64;
+
+                    light.position
+                        + Tuple::vector(r * theta.cos(), y, r * theta.sin()) * light.softness
+                };
+                let v = target - point;
+
+                1.0 - self.transmittance(&Ray::new(point, v.normalize()), v.magnitude())
+            })
+            .sum();
+        let shadowed = blocked / samples as f64;
+
+        Color::new(1.0, 1.0, 1.0) * (1.0 - shadowed) + light.shadow_color * shadowed
+    }
+
+    /// Returns the precomputed state of the closest hit along the ray, if
+    /// any object is hit in front of its origin
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to cast into the world
+    pub fn ray_cast(&self, ray: &Ray) -> Option<Computations<'_>> {
+        self.cast(ray, SHADOW_BIAS)
+    }
+
+    pub(crate) fn cast(&self, ray: &Ray, bias: f64) -> Option<Computations<'_>> {
+        self.cast_within(ray, bias, Interval::FORWARD)
+    }
+
+    fn cast_within(&self, ray: &Ray, bias: f64, range: Interval) -> Option<Computations<'_>> {
+        let intersections = self.intersect(ray);
+
+        intersections
+            .iter()
+            .find(|i| range.contains(i.t))
+            .map(|hit| hit.prepare_computations_with_bias(ray, &intersections, bias))
+    }
+
+    /// Returns all intersections in front of the ray origin, sorted by time
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to cast into the world
+    pub fn ray_cast_all(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut intersections = self.intersect(ray);
+
+        intersections.retain(|i| i.t >= 0.0);
+        intersections
+    }
+
+    /// Returns true if any object is hit by the ray between its origin and
+    /// the given time, stopping at the first such object found
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to cast into the world
+    /// * `max_t` - time beyond which intersections are ignored
+    pub fn occluded(&self, ray: &Ray, max_t: f64) -> bool {
+        let ray = ray.with_tolerance(self.intersection_epsilon);
+        self.objects.iter().any(|object| {
+            ray.intersect(&**object)
+                .iter()
+                .any(|i| i.t >= 0.0 && i.t < max_t)
+        })
+    }
+
+    /// Returns the fraction of light passing along the ray between its
+    /// origin and the given time, the product of the transparency of every
+    /// object hit on the way
+    ///
+    /// Stops at the first opaque object found, without sorting intersections,
+    /// so it costs no more than `occluded` in scenes without transparency.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - ray to cast into the world
+    /// * `max_t` - time beyond which intersections are ignored
+    pub fn transmittance(&self, ray: &Ray, max_t: f64) -> f64 {
+        let ray = ray.with_tolerance(self.intersection_epsilon);
+        let mut transmitted = 1.0;
+
+        for object in &self.objects {
+            if ray
+                .intersect(&**object)
+                .iter()
+                .any(|i| i.t >= 0.0 && i.t < max_t)
+            {
+                transmitted *= object.material().transparency.clamp(0.0, 1.0);
+
+                if transmitted <= 0.0 {
+                    return 0.0;
+                }
+            }
+        }
+
+        transmitted
+    }
+}
+
+impl Default for World {
+    fn default() -> World {
+        World::new()
+    }
+}
+
+/// 64-bit FNV-1a hasher, a deterministic stand-in for the standard library
+/// hasher that is also available without `std`
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Fnv1a {
+        Fnv1a(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Chooses `count` of the given lights with a probability proportional to
+/// their power over their squared distance to the point, returning every
+/// choice with the weight making its contribution an unbiased estimate of
+/// the sum over all lights
+///
+/// The choices are stratified over the distribution, offset by a hash of
+/// the point so that neighbouring points pick different lights. Falls back
+/// to every light with a weight of one when no light has any power.
+fn select_lights<'a>(lights: &[&'a Light], point: Tuple, count: usize) -> Vec<(&'a Light, f64)> {
+    let weights: Vec<f64> = lights
+        .iter()
+        .map(|light| {
+            let i = light.intensity;
+            let distance = (light.position - point).magnitude();
+
+            (i.r + i.g + i.b) / 3.0 / (distance * distance).max(EPSILON)
+        })
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    if count == 0 || total <= 0.0 {
+        return lights.iter().map(|&light| (light, 1.0)).collect();
+    }
+
+    let mut hasher = Fnv1a::new();
+    QuantizedTuple::new(point).hash(&mut hasher);
+    let offset = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+
+    (0..count)
+        .map(|k| {
+            let target = (k as f64 + offset) / count as f64 * total;
+            let mut cumulative = 0.0;
+            let index = weights
+                .iter()
+                .position(|w| {
+                    cumulative += w;
+                    cumulative > target
+                })
+                .unwrap_or_else(|| weights.iter().rposition(|&w| w > 0.0).unwrap_or(0));
+
+            (lights[index], total / (count as f64 * weights[index]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+impl World {
+    /// Returns the default world used throughout the tests: two concentric
+    /// spheres lit by a single white point light
+    pub(crate) fn default_world() -> World {
+        let mut outer = Sphere::new();
+        outer.material.color = Color::new(0.8, 1.0, 0.6);
+        outer.material.diffuse = 0.7;
+        outer.material.specular = 0.2;
+
+        let mut inner = Sphere::new();
+        inner.transform(crate::math::scaling(0.5, 0.5, 0.5));
+
+        World {
+            objects: vec![Box::new(outer), Box::new(inner)],
+            lights: vec![Light::new(
+                Tuple::point(-10.0, 10.0, -10.0),
+                Color::new(1.0, 1.0, 1.0),
+            )],
+            ambient: Color::default(),
+            intersection_epsilon: EPSILON,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::LightLinking;
+    use crate::math::{rotation_x, translation, Cylinder, Plane};
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn should_create_an_empty_world() {
+        let w = World::new();
+
+        assert!(w.objects.is_empty());
+        assert!(w.lights.is_empty());
+    }
+
+    #[test]
+    fn should_intersect_world_in_sorted_order() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = w.intersect(&r);
+
+        assert_eq!(4, xs.len());
+        assert_eq!(4.0, xs[0].t);
+        assert_eq!(4.5, xs[1].t);
+        assert_eq!(5.5, xs[2].t);
+        assert_eq!(6.0, xs[3].t);
+    }
+
+    #[test]
+    fn should_intersect_mixed_shapes() {
+        let mut w = World::default_world();
+        let mut cylinder = Cylinder::new();
+        cylinder.transform(translation(0.0, 0.0, 3.0));
+        cylinder.minimum = -1.0;
+        cylinder.maximum = 1.0;
+        w.objects.push(Box::new(cylinder));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = w.intersect(&r);
+        let times: Vec<f64> = xs.iter().map(|i| i.t).collect();
+
+        assert_eq!(vec![4.0, 4.5, 5.5, 6.0, 7.0, 9.0], times);
+        assert_eq!(Some(ShapeId(2)), w.id_of(xs[4].object));
+
+        let r = Ray::new(Tuple::point(0.0, 0.0, 5.0), Tuple::vector(0.0, 0.0, -1.0));
+        let comps = w.cast(&r, SHADOW_BIAS).unwrap();
+
+        assert_eq!(Some(ShapeId(2)), w.id_of(comps.object));
+        assert_eq!(Tuple::vector(0.0, 0.0, 1.0), comps.normalv);
+    }
+
+    #[test]
+    fn reflective_surfaces_should_add_the_reflected_color() {
+        let mut w = World::default_world();
+        let mut plane = Plane::new();
+        plane.transform(translation(0.0, -1.0, 0.0));
+        w.objects.push(Box::new(plane));
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let matte = w.color_at(&r);
+
+        w.objects[2].material_mut().reflective = 0.5;
+        let comps = w.ray_cast(&r).unwrap();
+        let mirrored = w.color_at(&Ray::new(comps.over_point, comps.reflectv));
+
+        assert_ne!(Color::default(), mirrored);
+        assert_eq!(matte + mirrored * 0.5, w.color_at(&r));
+    }
+
+    #[test]
+    fn facing_mirrors_should_not_reflect_forever() {
+        let mut w = World::new();
+        w.lights.push(Light::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+
+        for (height, tilt) in [(-1.0, 0.0), (1.0, PI)] {
+            let mut mirror = Plane::new();
+            mirror.transform(translation(0.0, height, 0.0) * rotation_x(tilt));
+            mirror.material.reflective = 1.0;
+            w.objects.push(Box::new(mirror));
+        }
+
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert_ne!(Color::default(), w.color_at(&r));
+    }
+
+    #[test]
+    fn worlds_should_intersect_within_their_own_tolerance() {
+        let strict = World::default_world();
+        let mut loose = World::default_world();
+        loose.intersection_epsilon = 1e-3;
+        let r = Ray::new(
+            Tuple::point(0.0, 1.0001, -5.0),
+            Tuple::vector(0.0, 0.0, 1.0),
+        );
+
+        assert!(strict.intersect(&r).is_empty());
+        assert_eq!(2, loose.intersect(&r).len());
+        assert!(!strict.occluded(&r, 10.0));
+        assert!(loose.occluded(&r, 10.0));
+        assert_eq!(EPSILON, World::new().intersection_epsilon);
+    }
+
+    #[test]
+    fn should_shade_an_intersection() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), w.shade_hit(&comps));
+    }
+
+    #[test]
+    fn light_samples_should_weight_selected_lights_by_power() {
+        let mut w = World::default_world();
+        let position = w.lights[0].position;
+        w.lights = vec![
+            Light::new(position, Color::new(0.25, 0.25, 0.25)),
+            Light::new(position, Color::new(0.75, 0.75, 0.75)),
+        ];
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+        let settings = RenderSettings {
+            light_samples: Some(1),
+            ..RenderSettings::default()
+        };
+
+        // contributions are proportional to power, so sampling by power is
+        // exact whichever light is chosen
+        assert_eq!(
+            Color::new(0.38066, 0.47583, 0.2855),
+            w.shade_hit_with(&comps, &settings)
+        );
+    }
+
+    #[test]
+    fn select_lights_should_be_unbiased_on_average() {
+        let near = Light::new(Tuple::point(0.0, 1.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        let far = Light::new(Tuple::point(0.0, 3.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        let lights = [&near, &far];
+        let choices = select_lights(&lights, Tuple::point(0.0, 0.0, 0.0), 10);
+
+        assert_eq!(10, choices.len());
+        assert_eq!(9, choices.iter().filter(|(l, _)| **l == near).count());
+        assert!((choices[0].1 - 1.0 / 9.0).abs() < EPSILON);
+
+        let dark = Light::new(Tuple::point(0.0, 1.0, 0.0), Color::new(0.0, 0.0, 0.0));
+        assert_eq!(
+            vec![(&dark, 1.0)],
+            select_lights(&[&dark], Tuple::point(0.0, 0.0, 0.0), 1)
+        );
+    }
+
+    #[test]
+    fn should_add_emission_without_lights() {
+        let mut w = World::default_world();
+        w.lights.clear();
+        w.objects[0].material_mut().emission = Color::new(0.5, 0.25, 0.0);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(Color::new(0.5, 0.25, 0.0), w.shade_hit(&comps));
+    }
+
+    #[test]
+    fn ambient_light_should_tint_surfaces_evenly() {
+        let mut w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let lit = w.shade_hit(&i.prepare_computations(&r, &[i]));
+
+        w.ambient = Color::new(0.5, 0.0, 0.25);
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(lit + Color::new(0.4, 0.0, 0.15), w.shade_hit(&comps));
+
+        w.lights.clear();
+        assert_eq!(Color::new(0.4, 0.0, 0.15), w.shade_hit(&comps));
+    }
+
+    #[test]
+    fn should_shade_an_intersection_from_the_inside() {
+        let mut w = World::default_world();
+        w.lights = vec![Light::new(
+            Tuple::point(0.0, 0.25, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )];
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(0.5, &*w.objects[1]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(Color::new(0.90498, 0.90498, 0.90498), w.shade_hit(&comps));
+    }
+
+    #[test]
+    fn should_shade_an_intersection_in_shadow() {
+        let mut w = World::new();
+        w.lights.push(Light::new(
+            Tuple::point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let mut s = Sphere::new();
+        w.objects.push(Box::new(s.clone()));
+        s.transform(translation(0.0, 0.0, 10.0));
+        w.objects.push(Box::new(s));
+        let r = Ray::new(Tuple::point(0.0, 0.0, 5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[1]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(Color::new(0.1, 0.1, 0.1), w.shade_hit(&comps));
+    }
+
+    #[test]
+    fn should_sum_contributions_of_multiple_lights() {
+        let mut w = World::default_world();
+        w.lights.push(w.lights[0].clone());
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+
+        assert_eq!(
+            Color::new(0.38066, 0.47583, 0.2855) * 2.0,
+            w.shade_hit(&comps)
+        );
+    }
+
+    #[test]
+    fn color_should_be_black_when_ray_misses() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), w.color_at(&r));
+    }
+
+    #[test]
+    fn should_compute_color_when_ray_hits() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), w.color_at(&r));
+    }
+
+    #[test]
+    fn should_compute_color_with_intersection_behind_ray() {
+        let mut w = World::default_world();
+        w.objects[0].material_mut().ambient = 1.0;
+        w.objects[1].material_mut().ambient = 1.0;
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.75), Tuple::vector(0.0, 0.0, -1.0));
+
+        assert_eq!(w.objects[1].material().color, w.color_at(&r));
+    }
+
+    #[test]
+    fn should_not_be_shadowed_when_nothing_is_collinear_with_point_and_light() {
+        let w = World::default_world();
+
+        assert!(!w.is_shadowed(&w.lights[0], Tuple::point(0.0, 10.0, 0.0)));
+    }
+
+    #[test]
+    fn should_be_shadowed_when_object_is_between_point_and_light() {
+        let w = World::default_world();
+
+        assert!(w.is_shadowed(&w.lights[0], Tuple::point(10.0, -10.0, 10.0)));
+    }
+
+    #[test]
+    fn should_not_be_shadowed_when_object_is_behind_light_or_point() {
+        let w = World::default_world();
+
+        assert!(!w.is_shadowed(&w.lights[0], Tuple::point(-20.0, 20.0, -20.0)));
+        assert!(!w.is_shadowed(&w.lights[0], Tuple::point(-2.0, 2.0, -2.0)));
+    }
+
+    #[test]
+    fn should_be_shadowed_by_transparent_objects_like_shading() {
+        let mut w = World::default_world();
+        let point = Tuple::point(10.0, -10.0, 10.0);
+        w.objects[0].material_mut().transparency = 0.5;
+        w.objects[1].material_mut().transparency = 1.0;
+
+        assert!(w.is_shadowed(&w.lights[0], point));
+
+        w.objects[0].material_mut().transparency = 1.0;
+
+        assert!(!w.is_shadowed(&w.lights[0], point));
+        assert_eq!(
+            Color::new(1.0, 1.0, 1.0),
+            w.light_visibility(&w.lights[0], point)
+        );
+    }
+
+    #[test]
+    fn ray_cast_should_return_closest_hit_in_front_of_ray() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let comps = w.ray_cast(&r).unwrap();
+
+        assert_eq!(0.5, comps.t);
+        assert!(core::ptr::addr_eq(&*w.objects[1], comps.object));
+        assert_eq!(Tuple::point(0.0, 0.0, 0.5), comps.point);
+    }
+
+    #[test]
+    fn ray_cast_should_return_none_when_ray_misses() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert!(w.ray_cast(&r).is_none());
+        assert!(w.ray_cast_all(&r).is_empty());
+    }
+
+    #[test]
+    fn ray_cast_all_should_skip_intersections_behind_ray() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = w.ray_cast_all(&r);
+
+        assert_eq!(2, xs.len());
+        assert_eq!(0.5, xs[0].t);
+        assert_eq!(1.0, xs[1].t);
+    }
+
+    #[test]
+    fn occluded_should_only_consider_intersections_before_max_t() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(w.occluded(&r, 10.0));
+        assert!(!w.occluded(&r, 4.0));
+        assert!(!w.occluded(&Ray::new(Tuple::point(0.0, 0.0, 5.0), r.direction), 10.0));
+    }
+
+    #[test]
+    fn transmittance_should_multiply_transparency_of_objects_hit() {
+        let mut w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert_eq!(0.0, w.transmittance(&r, 10.0));
+        assert_eq!(1.0, w.transmittance(&r, 4.0));
+
+        w.objects[0].material_mut().transparency = 0.5;
+        w.objects[1].material_mut().transparency = 0.8;
+        assert_eq!(0.4, w.transmittance(&r, 10.0));
+        assert_eq!(0.5, w.transmittance(&r, 4.25));
+    }
+
+    #[test]
+    fn transparent_objects_should_cast_lighter_shadows() {
+        let mut w = World::new();
+        let mut glass = Sphere::new();
+        glass.material.transparency = 0.75;
+        w.objects.push(Box::new(glass));
+        let light = Light::new(Tuple::point(0.0, 10.0, 0.0), Color::new(1.0, 1.0, 1.0));
+
+        assert_eq!(
+            Color::new(0.75, 0.75, 0.75),
+            w.light_visibility(&light, Tuple::point(0.0, -10.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn should_look_up_objects_by_identity() {
+        let mut w = World::default_world();
+        let copy = w.objects[1].clone();
+
+        assert_eq!(Some(ShapeId(1)), w.id_of(&*w.objects[1]));
+        assert_eq!(None, w.id_of(&*copy));
+        assert!(Some(&*copy) == w.object(ShapeId(1)));
+        assert!(w.object(ShapeId(2)).is_none());
+
+        w.object_mut(ShapeId(0)).unwrap().material_mut().ambient = 1.0;
+        assert_eq!(1.0, w.objects[0].material().ambient);
+    }
+
+    #[test]
+    fn should_clamp_radiance_of_each_sample() {
+        let mut w = World::default_world();
+        w.lights[0].intensity = Color::new(10.0, 10.0, 10.0);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let settings = RenderSettings {
+            max_radiance: Some(1.0),
+            ..Default::default()
+        };
+        let unclamped = w.color_at(&r);
+        let clamped = w.color_at_with(&r, &settings);
+
+        assert!(unclamped.max_component() > 1.0);
+        assert_eq!(unclamped.limit(1.0), clamped);
+    }
+
+    #[test]
+    fn should_clamp_contribution_of_each_light() {
+        let mut w = World::default_world();
+        w.lights[0].intensity = Color::new(10.0, 10.0, 10.0);
+        w.lights.push(w.lights[0].clone());
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Intersection::new(4.0, &*w.objects[0]);
+        let comps = i.prepare_computations(&r, &[i]);
+        let settings = RenderSettings {
+            max_contribution: Some(0.5),
+            ..Default::default()
+        };
+
+        assert_eq!(1.0, w.shade_hit_with(&comps, &settings).max_component());
+    }
+
+    #[test]
+    fn should_render_debug_visualizations() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let miss = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+        let with = |integrator| RenderSettings {
+            integrator,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Color::new(0.5, 0.5, 0.0),
+            w.color_at_with(&r, &with(Integrator::Normals))
+        );
+        assert_eq!(
+            Color::heat(0.5),
+            w.color_at_with(&r, &with(Integrator::Depth(8.0)))
+        );
+        assert_eq!(
+            Color::heat(1.0),
+            w.color_at_with(&r, &with(Integrator::IntersectionCount(4)))
+        );
+
+        for integrator in [
+            Integrator::Normals,
+            Integrator::Depth(8.0),
+            Integrator::IntersectionCount(4),
+        ] {
+            assert_eq!(Color::default(), w.color_at_with(&miss, &with(integrator)));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_through_serde() {
+        let w = World::default_world();
+        let json = serde_json::to_string(&w).unwrap();
+        let restored: World = serde_json::from_str(&json).unwrap();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+
+        assert!(w.objects == restored.objects);
+        assert_eq!(w.color_at(&r), restored.color_at(&r));
+
+        let mut w = w;
+        w.objects.push(Box::new(Cylinder::new()));
+        w.objects.push(Box::new(Plane::new()));
+        w.intersection_epsilon = 1e-3;
+        let restored: World = serde_json::from_str(&serde_json::to_string(&w).unwrap()).unwrap();
+
+        assert!(w.objects == restored.objects);
+        assert_eq!(1e-3, restored.intersection_epsilon);
+    }
+
+    #[test]
+    fn single_bounce_should_add_light_reflected_by_nearby_objects() {
+        let mut w = World::new();
+        w.lights.push(Light::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let mut floor = Sphere::new();
+        floor.transform(crate::math::scaling(10.0, 0.01, 10.0));
+        let mut wall = Sphere::new();
+        wall.transform(translation(1.0, 0.0, 0.0) * crate::math::scaling(1.0, 10.0, 10.0));
+        w.objects = vec![Box::new(floor), Box::new(wall)];
+        let r = Ray::new(Tuple::point(-0.5, 5.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        let settings = RenderSettings {
+            integrator: Integrator::SingleBounce(4),
+            ..Default::default()
+        };
+        let direct = w.color_at(&r);
+        let bounced = w.color_at_with(&r, &settings);
+
+        assert!(bounced.r > direct.r);
+        assert!(bounced.g > direct.g);
+        assert!(bounced.b > direct.b);
+
+        w.objects.truncate(1);
+        assert_eq!(w.color_at(&r), w.color_at_with(&r, &settings));
+    }
+
+    #[test]
+    fn should_only_shade_with_linked_lights() {
+        let mut w = World::default_world();
+        let rim = Light::new(Tuple::point(10.0, 10.0, -10.0), Color::new(1.0, 0.0, 0.0));
+        w.lights.push(rim.in_group("rim"));
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let with_rim = w.color_at(&r);
+
+        w.objects[0].downcast_mut::<Sphere>().unwrap().light_linking =
+            LightLinking::Exclude(vec!["rim".to_string()]);
+        assert_eq!(Color::new(0.38066, 0.47583, 0.2855), w.color_at(&r));
+
+        w.objects[0].downcast_mut::<Sphere>().unwrap().light_linking =
+            LightLinking::Include(vec!["rim".to_string()]);
+        assert_eq!(
+            with_rim - Color::new(0.38066, 0.47583, 0.2855),
+            w.color_at(&r)
+        );
+    }
+
+    #[test]
+    fn soft_lights_should_partially_shadow_points_near_shadow_edges() {
+        let mut w = World::new();
+        let mut blocker = Sphere::new();
+        blocker.transform(translation(0.0, 5.0, 0.0));
+        w.objects.push(Box::new(blocker));
+        let mut light = Light::new(Tuple::point(1.0, 10.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        let edge = Tuple::point(2.1, 0.0, 0.0);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), w.light_visibility(&light, edge));
+
+        light.softness = 2.0;
+        light.shadow_samples = 64;
+        let soft = w.light_visibility(&light, edge);
+
+        assert!(soft.r > 0.0 && soft.r < 1.0);
+
+        light.softness = 0.0;
+        light.shadow_color = Color::new(0.2, 0.4, 0.6);
+        let hidden = Tuple::point(0.0, 0.0, 0.0);
+        light.position = Tuple::point(0.0, 10.0, 0.0);
+
+        assert_eq!(
+            Color::new(0.2, 0.4, 0.6),
+            w.light_visibility(&light, hidden)
+        );
+    }
+
+    #[test]
+    fn holdouts_should_render_black_and_uncovered() {
+        let mut w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let settings = RenderSettings::default();
+
+        assert_eq!(1.0, w.coverage_at(&r, &settings));
+
+        w.objects[0].downcast_mut::<Sphere>().unwrap().holdout = true;
+
+        assert_eq!(Color::default(), w.color_at(&r));
+        assert_eq!(0.0, w.coverage_at(&r, &settings));
+
+        let miss = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert_eq!(0.0, w.coverage_at(&miss, &settings));
+    }
+
+    #[test]
+    fn should_ignore_objects_outside_of_clipping_range() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let settings = RenderSettings::default();
+
+        assert_eq!(
+            w.color_at(&r),
+            w.color_within(&r, &settings, Interval::new(0.0, 4.5))
+        );
+        assert_eq!(
+            Color::default(),
+            w.color_within(&r, &settings, Interval::new(0.0, 3.0))
+        );
+        assert_eq!(
+            Color::default(),
+            w.color_within(&r, &settings, Interval::new(6.5, 100.0))
+        );
+
+        let clipped = w.color_within(&r, &settings, Interval::new(4.2, 100.0));
+
+        assert_ne!(w.color_at(&r), clipped);
+        assert_ne!(Color::default(), clipped);
+    }
+}
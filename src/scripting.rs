@@ -0,0 +1,401 @@
+//! Scripting module
+//!
+//! Builds worlds from [Rhai](https://rhai.rs) scripts behind the `scripting`
+//! feature, for parameterized and generated scenes.
+//!
+//! A script evaluates to the world to render. It can call the constructors
+//! `point`, `vector`, `color`, `translation`, `scaling`, `rotation_x`,
+//! `rotation_y`, `rotation_z`, `material`, `sphere`, `cylinder`, `plane`,
+//! `light` and `world`, combine transformations with `*`, set the public
+//! fields of materials and shapes and the `ambient` light of a world, and
+//! add objects and lights to a world with `add` and `add_light`. Numbers
+//! must be written as floats, e.g. `1.0` rather than `1`.
+//!
+//! # Example
+//!
+//! ```
+//! use libray::scripting::world_from_script;
+//!
+//! let script = r#"
+//!     let w = world();
+//!     w.add_light(light(point(-10.0, 10.0, -10.0), color(1.0, 1.0, 1.0)));
+//!
+//!     for i in 0..count {
+//!         let s = sphere();
+//!         s.transform = translation(i.to_float() * 3.0, 0.0, 0.0);
+//!         w.add(s);
+//!     }
+//!
+//!     w
+//! "#;
+//!
+//! let world = world_from_script(script, &[("count", 4.0)]).unwrap();
+//!
+//! assert_eq!(4, world.objects.len());
+//! ```
+
+use crate::graphics::{Color, Light, Material};
+use crate::math::{rotation_x, rotation_y, rotation_z, scaling, translation};
+use crate::math::{Cylinder, Matrix, Plane, Sphere, Tuple};
+use crate::scene::{World, WorldDiff};
+use rhai::{Engine, Scope, INT};
+use std::fs;
+use std::path::Path;
+
+/// Evaluates the given script and returns the world it evaluates to
+///
+/// Every parameter is visible to the script as a variable, as an integer if
+/// it has no fractional part and as a float otherwise.
+///
+/// # Arguments
+///
+/// * `source` - source code of the script
+/// * `parameters` - names and values of the variables passed to the script
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "info", skip_all, fields(length = source.len()), err)
+)]
+pub fn world_from_script(source: &str, parameters: &[(&str, f64)]) -> Result<World, String> {
+    let engine = engine();
+    let mut scope = Scope::new();
+
+    for &(name, value) in parameters {
+        if value.fract() == 0.0 && value.abs() < INT::MAX as f64 {
+            scope.push(name.to_string(), value as INT);
+        } else {
+            scope.push(name.to_string(), value);
+        }
+    }
+
+    engine
+        .eval_with_scope::<World>(&mut scope, source)
+        .map_err(|e| e.to_string())
+}
+
+/// Reads and evaluates the script at the given path, returning the world it
+/// evaluates to
+///
+/// # Arguments
+///
+/// * `path` - path of the script file
+/// * `parameters` - names and values of the variables passed to the script
+pub fn world_from_file<P: AsRef<Path>>(
+    path: P,
+    parameters: &[(&str, f64)],
+) -> Result<World, String> {
+    let path = path.as_ref();
+    let source =
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+    world_from_script(&source, parameters)
+}
+
+/// Re-evaluates the script at the given path and updates the world to match
+/// it, replacing only the objects and lights which changed
+///
+/// The world is left untouched if the script cannot be evaluated.
+///
+/// # Arguments
+///
+/// * `world` - world previously built from the script
+/// * `path` - path of the script file
+/// * `parameters` - names and values of the variables passed to the script
+pub fn reload_from_file<P: AsRef<Path>>(
+    world: &mut World,
+    path: P,
+    parameters: &[(&str, f64)],
+) -> Result<WorldDiff, String> {
+    Ok(world.reload(world_from_file(path, parameters)?))
+}
+
+/// Returns an engine with the scene building API registered
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Tuple>("Tuple")
+        .register_fn("point", Tuple::point)
+        .register_fn("vector", Tuple::vector)
+        .register_get("x", |t: &mut Tuple| t.x)
+        .register_get("y", |t: &mut Tuple| t.y)
+        .register_get("z", |t: &mut Tuple| t.z)
+        .register_fn("+", |a: Tuple, b: Tuple| a + b)
+        .register_fn("-", |a: Tuple, b: Tuple| a - b)
+        .register_fn("*", |a: Tuple, b: f64| a * b);
+
+    engine
+        .register_type_with_name::<Color>("Color")
+        .register_fn("color", Color::new)
+        .register_fn("*", |a: Color, b: f64| a * b);
+
+    engine
+        .register_type_with_name::<Matrix>("Matrix")
+        .register_fn("translation", translation)
+        .register_fn("scaling", scaling)
+        .register_fn("rotation_x", |r: f64| rotation_x(r))
+        .register_fn("rotation_y", |r: f64| rotation_y(r))
+        .register_fn("rotation_z", |r: f64| rotation_z(r))
+        .register_fn("*", |a: Matrix, b: Matrix| a * b)
+        .register_fn("*", |a: Matrix, b: Tuple| a * b);
+
+    engine
+        .register_type_with_name::<Material>("Material")
+        .register_fn("material", Material::new)
+        .register_get_set(
+            "color",
+            |m: &mut Material| m.color,
+            |m: &mut Material, c| m.color = c,
+        )
+        .register_get_set(
+            "ambient",
+            |m: &mut Material| m.ambient,
+            |m: &mut Material, v| m.ambient = v,
+        )
+        .register_get_set(
+            "diffuse",
+            |m: &mut Material| m.diffuse,
+            |m: &mut Material, v| m.diffuse = v,
+        )
+        .register_get_set(
+            "specular",
+            |m: &mut Material| m.specular,
+            |m: &mut Material, v| m.specular = v,
+        )
+        .register_get_set(
+            "shininess",
+            |m: &mut Material| m.shininess,
+            |m: &mut Material, v| m.shininess = v,
+        )
+        .register_get_set(
+            "emission",
+            |m: &mut Material| m.emission,
+            |m: &mut Material, c| m.emission = c,
+        )
+        .register_get_set(
+            "transparency",
+            |m: &mut Material| m.transparency,
+            |m: &mut Material, v| m.transparency = v,
+        )
+        .register_get_set(
+            "reflective",
+            |m: &mut Material| m.reflective,
+            |m: &mut Material, v| m.reflective = v,
+        );
+
+    engine
+        .register_type_with_name::<Sphere>("Sphere")
+        .register_fn("sphere", Sphere::new)
+        .register_get_set(
+            "transform",
+            |s: &mut Sphere| s.transform.clone(),
+            |s: &mut Sphere, m: Matrix| s.transform(m),
+        )
+        .register_get_set(
+            "material",
+            |s: &mut Sphere| s.material.clone(),
+            |s: &mut Sphere, m: Material| s.material = m,
+        )
+        .register_get_set(
+            "holdout",
+            |s: &mut Sphere| s.holdout,
+            |s: &mut Sphere, h| s.holdout = h,
+        );
+
+    engine
+        .register_type_with_name::<Cylinder>("Cylinder")
+        .register_fn("cylinder", Cylinder::new)
+        .register_get_set(
+            "transform",
+            |c: &mut Cylinder| c.transform.clone(),
+            |c: &mut Cylinder, m: Matrix| c.transform(m),
+        )
+        .register_get_set(
+            "material",
+            |c: &mut Cylinder| c.material.clone(),
+            |c: &mut Cylinder, m: Material| c.material = m,
+        )
+        .register_get_set(
+            "minimum",
+            |c: &mut Cylinder| c.minimum,
+            |c: &mut Cylinder, y| c.minimum = y,
+        )
+        .register_get_set(
+            "maximum",
+            |c: &mut Cylinder| c.maximum,
+            |c: &mut Cylinder, y| c.maximum = y,
+        )
+        .register_get_set(
+            "closed",
+            |c: &mut Cylinder| c.closed,
+            |c: &mut Cylinder, closed| c.closed = closed,
+        );
+
+    engine
+        .register_type_with_name::<Plane>("Plane")
+        .register_fn("plane", Plane::new)
+        .register_get_set(
+            "transform",
+            |p: &mut Plane| p.transform.clone(),
+            |p: &mut Plane, m: Matrix| p.transform(m),
+        )
+        .register_get_set(
+            "material",
+            |p: &mut Plane| p.material.clone(),
+            |p: &mut Plane, m: Material| p.material = m,
+        );
+
+    engine
+        .register_type_with_name::<Light>("Light")
+        .register_fn("light", Light::new);
+
+    engine
+        .register_type_with_name::<World>("World")
+        .register_fn("world", World::new)
+        .register_fn("add", |w: &mut World, s: Sphere| {
+            w.objects.push(Box::new(s))
+        })
+        .register_fn("add", |w: &mut World, c: Cylinder| {
+            w.objects.push(Box::new(c))
+        })
+        .register_fn("add", |w: &mut World, p: Plane| w.objects.push(Box::new(p)))
+        .register_fn("add_light", |w: &mut World, l: Light| w.lights.push(l))
+        .register_get_set(
+            "ambient",
+            |w: &mut World| w.ambient,
+            |w: &mut World, c: Color| w.ambient = c,
+        );
+
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::ShapeId;
+
+    #[test]
+    fn should_build_world_from_script() {
+        let script = r#"
+            let m = material();
+            m.color = color(1.0, 0.2, 0.2);
+            m.diffuse = 0.5;
+
+            let s = sphere();
+            s.material = m;
+            s.transform = translation(0.0, 1.0, 0.0) * scaling(2.0, 2.0, 2.0);
+
+            let w = world();
+            w.add(s);
+            w.add_light(light(point(0.0, 10.0, 0.0), color(1.0, 1.0, 1.0) * 0.5));
+            w.ambient = color(0.1, 0.1, 0.2);
+            w
+        "#;
+        let w = world_from_script(script, &[]).unwrap();
+
+        assert_eq!(1, w.objects.len());
+        assert_eq!(Color::new(1.0, 0.2, 0.2), w.objects[0].material().color);
+        assert_eq!(0.5, w.objects[0].material().diffuse);
+        assert_eq!(
+            translation(0.0, 1.0, 0.0) * scaling(2.0, 2.0, 2.0),
+            *w.objects[0].transformation()
+        );
+        assert_eq!(Color::new(0.5, 0.5, 0.5), w.lights[0].intensity);
+        assert_eq!(Color::new(0.1, 0.1, 0.2), w.ambient);
+    }
+
+    #[test]
+    fn should_add_cylinders_from_script() {
+        let script = r#"
+            let c = cylinder();
+            c.minimum = 0.0;
+            c.maximum = 2.0;
+            c.closed = true;
+            c.transform = translation(1.0, 0.0, 0.0);
+
+            let w = world();
+            w.add(c);
+            w.add(sphere());
+            w
+        "#;
+        let w = world_from_script(script, &[]).unwrap();
+        let c = w.objects[0].downcast_ref::<Cylinder>().unwrap();
+
+        assert_eq!(2, w.objects.len());
+        assert_eq!((0.0, 2.0, true), (c.minimum, c.maximum, c.closed));
+        assert_eq!(translation(1.0, 0.0, 0.0), c.transform);
+        assert!(w.objects[1].downcast_ref::<Sphere>().is_some());
+    }
+
+    #[test]
+    fn should_add_reflective_planes_from_script() {
+        let script = r#"
+            let m = material();
+            m.reflective = 0.25;
+
+            let p = plane();
+            p.material = m;
+            p.transform = translation(0.0, -1.0, 0.0);
+
+            let w = world();
+            w.add(p);
+            w
+        "#;
+        let w = world_from_script(script, &[]).unwrap();
+        let p = w.objects[0].downcast_ref::<Plane>().unwrap();
+
+        assert_eq!(0.25, p.material.reflective);
+        assert_eq!(translation(0.0, -1.0, 0.0), p.transform);
+    }
+
+    #[test]
+    fn should_pass_parameters_to_script() {
+        let script = "let w = world(); for i in 0..count { w.add(sphere()) } w";
+
+        assert_eq!(
+            3,
+            world_from_script(script, &[("count", 3.0)])
+                .unwrap()
+                .objects
+                .len()
+        );
+        let script = "let w = world(); if scale == 0.5 { w.add(sphere()) } w";
+
+        assert_eq!(
+            1,
+            world_from_script(script, &[("scale", 0.5)])
+                .unwrap()
+                .objects
+                .len()
+        );
+    }
+
+    #[test]
+    fn reload_should_only_replace_changed_objects() {
+        let path = std::env::temp_dir().join("libray_reload_test.rhai");
+        let script = |x: f64| {
+            format!(
+                "let w = world(); w.add(sphere()); let s = sphere(); \
+                 s.transform = translation({:.1}, 0.0, 0.0); w.add(s); w",
+                x
+            )
+        };
+
+        fs::write(&path, script(1.0)).unwrap();
+        let mut w = world_from_file(&path, &[]).unwrap();
+        fs::write(&path, script(2.0)).unwrap();
+        let diff = reload_from_file(&mut w, &path, &[]).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec![ShapeId(1)], diff.changed);
+        assert_eq!(translation(2.0, 0.0, 0.0), *w.objects[1].transformation());
+    }
+
+    #[test]
+    fn should_report_script_errors() {
+        assert!(world_from_script("sphere(", &[]).is_err());
+        assert!(world_from_script("sphere()", &[]).is_err());
+        assert!(world_from_file("does/not/exist.rhai", &[])
+            .unwrap_err()
+            .starts_with("cannot read"));
+    }
+}
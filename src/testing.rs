@@ -0,0 +1,194 @@
+//! Testing module
+//!
+//! Contains helpers for image-based regression testing of renders, and
+//! `Arbitrary` implementations for property-based testing behind the
+//! `proptest` and `quickcheck` features.
+
+#[cfg(any(test, feature = "proptest"))]
+mod prop;
+#[cfg(any(test, feature = "quickcheck"))]
+mod quick;
+
+use crate::graphics::{Canvas, Color};
+#[cfg(any(test, feature = "proptest", feature = "quickcheck"))]
+use crate::math::Matrix;
+use crate::math::EPSILON;
+
+/// Invertible transformation matrix, generated as a random chain of
+/// translations, scalings, rotations and shears
+#[cfg(any(test, feature = "proptest", feature = "quickcheck"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform(pub Matrix);
+
+/// Statistics describing how much two canvases differ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiffStats {
+    /// Largest absolute difference found in each channel
+    pub max: Color,
+    /// Mean absolute difference of each channel over all pixels
+    pub mean: Color,
+    /// Number of pixels differing in at least one channel
+    pub differing: usize,
+    /// Total number of pixels compared
+    pub pixels: usize,
+}
+
+impl DiffStats {
+    /// Returns true if no pixel differs between the canvases
+    pub fn is_identical(&self) -> bool {
+        self.differing == 0
+    }
+
+    /// Returns true if no channel of any pixel differs by more than the
+    /// tolerance given for that channel
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - largest accepted difference in each channel
+    pub fn within(&self, tolerance: Color) -> bool {
+        self.max.r <= tolerance.r && self.max.g <= tolerance.g && self.max.b <= tolerance.b
+    }
+}
+
+impl Canvas {
+    /// Compares the canvas with another one of the same size pixel by pixel
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - canvas to compare with
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvases differ in size
+    pub fn diff(&self, other: &Canvas) -> DiffStats {
+        let difference = self.difference(other);
+        let mut max = Color::default();
+        let mut sum = Color::default();
+        let mut differing = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let d = difference.pixel_at(x, y);
+
+                max = Color::new(max.r.max(d.r), max.g.max(d.g), max.b.max(d.b));
+                sum = sum + d;
+
+                if d.max_component() > EPSILON {
+                    differing += 1;
+                }
+            }
+        }
+
+        let pixels = self.width * self.height;
+
+        DiffStats {
+            max,
+            mean: sum / pixels.max(1) as f64,
+            differing,
+            pixels,
+        }
+    }
+
+    /// Returns a canvas holding the absolute difference of each channel
+    /// between the canvas and another one of the same size
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - canvas to compare with
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvases differ in size
+    pub fn difference(&self, other: &Canvas) -> Canvas {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "cannot compare a {}x{} canvas with a {}x{} canvas",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+
+        let mut image = Canvas::new(self.width, self.height);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let d = self.pixel_at(x, y) - other.pixel_at(x, y);
+
+                image.write_pixel(x, y, Color::new(d.r.abs(), d.g.abs(), d.b.abs()));
+            }
+        }
+
+        image
+    }
+}
+
+/// Asserts that two canvases match within the given per-channel tolerance
+///
+/// # Arguments
+///
+/// * `actual` - rendered canvas
+/// * `expected` - reference canvas
+/// * `tolerance` - largest accepted difference in each channel
+///
+/// # Example
+///
+/// ```
+/// use libray::graphics::{Canvas, Color};
+/// use libray::testing::assert_similar;
+///
+/// let expected = Canvas::new(4, 4);
+/// let mut actual = Canvas::new(4, 4);
+/// actual.write_pixel(1, 1, Color::new(0.01, 0.0, 0.0));
+///
+/// assert_similar(&actual, &expected, Color::new(0.02, 0.0, 0.0));
+/// ```
+pub fn assert_similar(actual: &Canvas, expected: &Canvas, tolerance: Color) {
+    let stats = actual.diff(expected);
+
+    assert!(
+        stats.within(tolerance),
+        "canvases differ by up to {} in {} of {} pixels, tolerance is {}",
+        stats.max,
+        stats.differing,
+        stats.pixels,
+        tolerance
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_canvases_should_not_differ() {
+        let mut a = Canvas::new(3, 2);
+        a.write_pixel(1, 1, Color::new(0.5, 0.25, 1.0));
+        let stats = a.diff(&a.clone());
+
+        assert!(stats.is_identical());
+        assert_eq!(Color::default(), stats.max);
+        assert_eq!(6, stats.pixels);
+    }
+
+    #[test]
+    fn should_compute_difference_statistics() {
+        let a = Canvas::new(2, 2);
+        let mut b = Canvas::new(2, 2);
+        b.write_pixel(0, 0, Color::new(0.4, -0.2, 0.0));
+        let stats = a.diff(&b);
+
+        assert_eq!(1, stats.differing);
+        assert_eq!(Color::new(0.4, 0.2, 0.0), stats.max);
+        assert_eq!(Color::new(0.1, 0.05, 0.0), stats.mean);
+        assert!(stats.within(Color::new(0.4, 0.2, 0.0)));
+        assert!(!stats.within(Color::new(0.3, 1.0, 1.0)));
+        assert_eq!(Color::new(0.4, 0.2, 0.0), a.difference(&b).pixel_at(0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot compare a 2x2 canvas with a 3x2 canvas")]
+    fn should_panic_when_sizes_differ() {
+        Canvas::new(2, 2).diff(&Canvas::new(3, 2));
+    }
+}
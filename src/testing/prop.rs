@@ -0,0 +1,105 @@
+use super::Transform;
+use crate::graphics::Color;
+use crate::math::{rotation_x, rotation_y, rotation_z, scaling, shearing, translation};
+use crate::math::{Matrix, Tuple};
+use core::f64::consts::PI;
+use proptest::prelude::*;
+
+impl Arbitrary for Tuple {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Tuple>;
+
+    /// Generates points and vectors with components in `-1000.0..1000.0`
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Tuple> {
+        (
+            -1000.0..1000.0,
+            -1000.0..1000.0,
+            -1000.0..1000.0,
+            prop_oneof![Just(0.0), Just(1.0)],
+        )
+            .prop_map(|(x, y, z, w)| Tuple::new(x, y, z, w))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Color {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Color>;
+
+    /// Generates colors with components in `0.0..=1.0`
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Color> {
+        (0.0..=1.0, 0.0..=1.0, 0.0..=1.0)
+            .prop_map(|(r, g, b)| Color::new(r, g, b))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Matrix {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Matrix>;
+
+    /// Generates 4x4 matrices with elements in `-10.0..10.0`, which are not
+    /// necessarily invertible
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Matrix> {
+        proptest::collection::vec(proptest::collection::vec(-10.0..10.0, 4), 4)
+            .prop_map(Matrix::from)
+            .boxed()
+    }
+}
+
+impl Arbitrary for Transform {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Transform>;
+
+    /// Generates chains of one to four translations, scalings, rotations and
+    /// mild shears
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Transform> {
+        let step = prop_oneof![
+            (-10.0..10.0, -10.0..10.0, -10.0..10.0).prop_map(|(x, y, z)| translation(x, y, z)),
+            (0.1..10.0, 0.1..10.0, 0.1..10.0).prop_map(|(x, y, z)| scaling(x, y, z)),
+            (0.0..2.0 * PI).prop_map(rotation_x),
+            (0.0..2.0 * PI).prop_map(rotation_y),
+            (0.0..2.0 * PI).prop_map(rotation_z),
+            proptest::collection::vec(-0.25..0.25, 6)
+                .prop_map(|s| shearing(s[0], s[1], s[2], s[3], s[4], s[5])),
+        ];
+
+        proptest::collection::vec(step, 1..=4)
+            .prop_map(|steps| {
+                Transform(
+                    steps
+                        .into_iter()
+                        .fold(Matrix::identity(4), |transform, step| step * transform),
+                )
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn transform_times_inverse_should_be_identity(t in any::<Transform>()) {
+            prop_assert!(t.0.is_invertible());
+            prop_assert_eq!(Matrix::identity(4), &t.0 * &t.0.inverse());
+        }
+
+        #[test]
+        fn inverse_should_undo_transform(t in any::<Transform>(), p in any::<Tuple>()) {
+            prop_assert_eq!(p, &t.0.inverse() * &(&t.0 * &p));
+        }
+
+        #[test]
+        fn transpose_should_be_its_own_inverse(m in any::<Matrix>()) {
+            prop_assert_eq!(m.clone(), m.transpose().transpose());
+        }
+
+        #[test]
+        fn adding_colors_should_commute(a in any::<Color>(), b in any::<Color>()) {
+            prop_assert_eq!(a + b, b + a);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::Transform;
+use crate::graphics::Color;
+use crate::math::{rotation_x, rotation_y, rotation_z, scaling, shearing, translation};
+use crate::math::{Matrix, Tuple};
+use core::f64::consts::PI;
+use quickcheck::{Arbitrary, Gen};
+
+/// Returns a uniformly distributed value in the given half-open range
+fn range(g: &mut Gen, min: f64, max: f64) -> f64 {
+    min + (max - min) * (u32::arbitrary(g) as f64 / (u32::MAX as f64 + 1.0))
+}
+
+impl Arbitrary for Tuple {
+    /// Generates points and vectors with components in `-1000.0..1000.0`
+    fn arbitrary(g: &mut Gen) -> Tuple {
+        Tuple::new(
+            range(g, -1000.0, 1000.0),
+            range(g, -1000.0, 1000.0),
+            range(g, -1000.0, 1000.0),
+            *g.choose(&[0.0, 1.0]).unwrap(),
+        )
+    }
+}
+
+impl Arbitrary for Color {
+    /// Generates colors with components in `0.0..1.0`
+    fn arbitrary(g: &mut Gen) -> Color {
+        Color::new(range(g, 0.0, 1.0), range(g, 0.0, 1.0), range(g, 0.0, 1.0))
+    }
+}
+
+impl Arbitrary for Matrix {
+    /// Generates 4x4 matrices with elements in `-10.0..10.0`, which are not
+    /// necessarily invertible
+    fn arbitrary(g: &mut Gen) -> Matrix {
+        Matrix::from(
+            (0..4)
+                .map(|_| (0..4).map(|_| range(g, -10.0, 10.0)).collect())
+                .collect::<Vec<Vec<f64>>>(),
+        )
+    }
+}
+
+impl Arbitrary for Transform {
+    /// Generates chains of one to four translations, scalings, rotations and
+    /// mild shears
+    fn arbitrary(g: &mut Gen) -> Transform {
+        let steps = 1 + usize::arbitrary(g) % 4;
+        let mut transform = Matrix::identity(4);
+
+        for _ in 0..steps {
+            let step = match usize::arbitrary(g) % 6 {
+                0 => translation(
+                    range(g, -10.0, 10.0),
+                    range(g, -10.0, 10.0),
+                    range(g, -10.0, 10.0),
+                ),
+                1 => scaling(
+                    range(g, 0.1, 10.0),
+                    range(g, 0.1, 10.0),
+                    range(g, 0.1, 10.0),
+                ),
+                2 => rotation_x(range(g, 0.0, 2.0 * PI)),
+                3 => rotation_y(range(g, 0.0, 2.0 * PI)),
+                4 => rotation_z(range(g, 0.0, 2.0 * PI)),
+                _ => shearing(
+                    range(g, -0.25, 0.25),
+                    range(g, -0.25, 0.25),
+                    range(g, -0.25, 0.25),
+                    range(g, -0.25, 0.25),
+                    range(g, -0.25, 0.25),
+                    range(g, -0.25, 0.25),
+                ),
+            };
+
+            transform = step * transform;
+        }
+
+        Transform(transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+    use quickcheck::quickcheck;
+
+    quickcheck! {
+        fn transform_times_inverse_should_be_identity(t: Transform) -> bool {
+            Matrix::identity(4) == &t.0 * &t.0.inverse()
+        }
+
+        fn normalized_vectors_should_have_unit_length(v: Tuple) -> bool {
+            let v = Tuple::vector(v.x, v.y, v.z);
+
+            v.magnitude() == 0.0 || (v.normalize().magnitude() - 1.0).abs() < EPSILON
+        }
+    }
+}